@@ -0,0 +1,254 @@
+//! Baseline files: accept known diagnostics, report only new ones.
+//!
+//! `--write-baseline <FILE>` records every current warning/failure; `--baseline <FILE>`
+//! suppresses diagnostics recorded there and lists entries that no longer fire.
+//!
+//! An entry is a fingerprint, not a position: check id, repo-relative path and the
+//! whitespace-normalized message. Line numbers are left out on purpose so that
+//! editing unrelated parts of a file doesn't invalidate the baseline.
+
+use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
+
+use anyhow::{Context, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::reporter::{Diagnostic, Level};
+
+/// Version of the on-disk baseline format.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// One accepted diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BaselineEntry {
+    pub check_id: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub message: String,
+}
+
+impl BaselineEntry {
+    /// Fingerprint a diagnostic.
+    pub fn of(d: &Diagnostic) -> Self {
+        Self {
+            check_id: d.check_id,
+            path: d.location.as_ref().map(|l| l.path.replace('\\', "/")),
+            message: normalize_message(&d.message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BaselineFile {
+    schema_version: u32,
+    entries: Vec<BaselineEntry>,
+}
+
+/// A loaded baseline, tracking which entries matched during this run.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    entries: BTreeSet<BaselineEntry>,
+    matched: HashSet<BaselineEntry>,
+}
+
+impl Baseline {
+    pub fn from_entries(entries: impl IntoIterator<Item = BaselineEntry>) -> Self {
+        Self {
+            entries: entries.into_iter().collect(),
+            matched: HashSet::new(),
+        }
+    }
+
+    /// Build a baseline from a run's diagnostics (passes are ignored).
+    pub fn from_diagnostics(diagnostics: &[Diagnostic]) -> Self {
+        Self::from_entries(
+            diagnostics
+                .iter()
+                .filter(|d| d.level != Level::Pass)
+                .map(BaselineEntry::of),
+        )
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("read baseline {}", path.display()))?;
+        let file: BaselineFile = serde_json::from_str(&text)
+            .with_context(|| format!("parse baseline {}", path.display()))?;
+        if file.schema_version != SCHEMA_VERSION {
+            bail!(
+                "baseline {} has schema_version {} (expected {SCHEMA_VERSION})",
+                path.display(),
+                file.schema_version
+            );
+        }
+        Ok(Self::from_entries(file.entries))
+    }
+
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let file = BaselineFile {
+            schema_version: SCHEMA_VERSION,
+            entries: self.entries.iter().cloned().collect(),
+        };
+        let mut text = serde_json::to_string_pretty(&file)?;
+        text.push('\n');
+        std::fs::write(path, text).with_context(|| format!("write baseline {}", path.display()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// True if `d` is accepted by the baseline; the entry is marked as still firing.
+    pub fn suppresses(&mut self, d: &Diagnostic) -> bool {
+        if d.level == Level::Pass {
+            return false;
+        }
+        let entry = BaselineEntry::of(d);
+        if self.entries.contains(&entry) {
+            self.matched.insert(entry);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Entries that did not fire, limited to checks that actually ran.
+    pub fn stale(&self, checks_run: &HashSet<u32>) -> Vec<BaselineEntry> {
+        self.entries
+            .iter()
+            .filter(|e| checks_run.contains(&e.check_id) && !self.matched.contains(*e))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Collapse whitespace runs so re-wrapping or trailing spaces don't change a fingerprint.
+pub fn normalize_message(msg: &str) -> String {
+    msg.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::Location;
+    use tempfile::TempDir;
+
+    fn diag(level: Level, check_id: u32, msg: &str, location: Option<Location>) -> Diagnostic {
+        Diagnostic {
+            level,
+            check_id,
+            check_title: "Title".to_string(),
+            message: msg.to_string(),
+            location,
+        }
+    }
+
+    #[test]
+    fn test_normalize_message_collapses_whitespace() {
+        assert_eq!(normalize_message("  a   b\tc \n"), "a b c");
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_line_and_column() {
+        let a = diag(
+            Level::Fail,
+            7,
+            "Found FR-001",
+            Some(Location::file("a.md").with_line(3).with_column(1)),
+        );
+        let b = diag(
+            Level::Fail,
+            7,
+            "Found  FR-001 ",
+            Some(Location::file("a.md").with_line(40)),
+        );
+        assert_eq!(BaselineEntry::of(&a), BaselineEntry::of(&b));
+    }
+
+    #[test]
+    fn test_fingerprint_distinguishes_check_and_path() {
+        let a = diag(Level::Fail, 7, "msg", Some(Location::file("a.md")));
+        let b = diag(Level::Fail, 8, "msg", Some(Location::file("a.md")));
+        let c = diag(Level::Fail, 7, "msg", Some(Location::file("b.md")));
+        assert_ne!(BaselineEntry::of(&a), BaselineEntry::of(&b));
+        assert_ne!(BaselineEntry::of(&a), BaselineEntry::of(&c));
+    }
+
+    #[test]
+    fn test_from_diagnostics_skips_pass_and_dedups() {
+        let diags = vec![
+            diag(Level::Pass, 1, "ok", None),
+            diag(Level::Warn, 2, "w", None),
+            diag(Level::Warn, 2, "w", None),
+        ];
+        assert_eq!(Baseline::from_diagnostics(&diags).len(), 1);
+    }
+
+    #[test]
+    fn test_suppresses_and_stale() {
+        let known = diag(Level::Fail, 7, "old", Some(Location::file("a.md")));
+        let gone = diag(Level::Warn, 8, "fixed", None);
+        let other_check = diag(Level::Warn, 9, "not run", None);
+        let mut baseline =
+            Baseline::from_diagnostics(&[known.clone(), gone.clone(), other_check.clone()]);
+
+        assert!(baseline.suppresses(&known));
+        assert!(!baseline.suppresses(&diag(Level::Fail, 7, "new", None)));
+
+        let ran: HashSet<u32> = [7, 8].into_iter().collect();
+        let stale = baseline.stale(&ran);
+        assert_eq!(stale, vec![BaselineEntry::of(&gone)]);
+    }
+
+    #[test]
+    fn test_write_and_load_roundtrip() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("pack-check-baseline.json");
+        let diags = vec![
+            diag(
+                Level::Fail,
+                7,
+                "x",
+                Some(Location::file("a.md").with_line(2)),
+            ),
+            diag(Level::Warn, 2, "y", None),
+        ];
+
+        Baseline::from_diagnostics(&diags).write(&path).unwrap();
+        let mut loaded = Baseline::load(&path).unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        assert!(loaded.suppresses(&diags[0]));
+        assert!(loaded.suppresses(&diags[1]));
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.contains("\"schema_version\": 1"));
+        assert!(!text.contains("\"line\""));
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_version() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("b.json");
+        std::fs::write(&path, r#"{"schema_version": 9, "entries": []}"#).unwrap();
+
+        let err = Baseline::load(&path).unwrap_err();
+        assert!(err.to_string().contains("schema_version 9"), "{err}");
+    }
+
+    #[test]
+    fn test_load_missing_file_names_path() {
+        let err = Baseline::load(Path::new("/definitely/missing/baseline.json")).unwrap_err();
+        assert!(format!("{err:#}").contains("baseline.json"));
+    }
+}
@@ -0,0 +1,563 @@
+//! Library entry point: run the checks on a pack and get a typed report.
+//!
+//! `PackChecker` is also what the CLI runs; the CLI only adds printing,
+//! baselines, `--fix` and `--graph` around it.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, bail};
+
+use crate::{
+    checks::{self, Category, CheckCtx, CheckSpec, Selection},
+    contracts::{Contracts, Regexes},
+    contracts_file,
+    ctx::{Ctx, Reads},
+    files::FileProvider,
+    inventory::Inventory,
+    reporter::{PackCounts, Reporter, RunReport},
+    suppress::Suppressions,
+};
+
+/// Builder for a pack-check run.
+///
+/// ```no_run
+/// use demoswarm_pack_check::{MemoryFiles, PackChecker, Selection};
+///
+/// let report = PackChecker::new("pack")
+///     .files(MemoryFiles::new().with_file("pack/.claude/agents/a.md", "# A\n"))
+///     .selection(Selection { only: vec![7], ..Default::default() })
+///     .run()?;
+/// assert!(report.passed(false));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PackChecker {
+    repo_root: Option<PathBuf>,
+    files: Option<Arc<dyn FileProvider>>,
+    contracts: Option<Contracts>,
+    selection: Selection,
+    jobs: Option<usize>,
+    docs_root: Option<PathBuf>,
+    /// `Some` in runs mode, with the `--run-id` filter.
+    runs: Option<Option<String>>,
+    git_ref: Option<String>,
+}
+
+impl PackChecker {
+    /// Check the pack at `repo_root` (the directory that contains `.claude/`).
+    pub fn new(repo_root: impl Into<PathBuf>) -> Self {
+        Self {
+            repo_root: Some(repo_root.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Check the pack found by walking up from the current directory.
+    pub fn from_cwd() -> Self {
+        Self::default()
+    }
+
+    /// Read the pack through `files` instead of the file system. The repo root
+    /// is then used as given, so it may be a virtual path.
+    pub fn files(mut self, files: impl FileProvider + 'static) -> Self {
+        self.files = Some(Arc::new(files));
+        self
+    }
+
+    /// Check the pack as committed at git revision `rev` (a branch, tag or
+    /// commit) instead of the working tree, without checking it out. Without a
+    /// repo root the pack is at the repository's top level.
+    pub fn git_ref(mut self, rev: impl Into<String>) -> Self {
+        self.git_ref = Some(rev.into());
+        self
+    }
+
+    /// Use these contracts instead of the defaults plus the pack's
+    /// `.claude/pack-contracts.{toml,json}`.
+    pub fn contracts(mut self, contracts: Contracts) -> Self {
+        self.contracts = Some(contracts);
+        self
+    }
+
+    /// Run only the selected checks (default: all of them).
+    pub fn selection(mut self, selection: Selection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Run checks on up to `jobs` threads (default: one per CPU; `1` runs them
+    /// in turn). Reports do not depend on it.
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = Some(jobs.max(1));
+        self
+    }
+
+    /// Run the selected checks. Nothing is printed.
+    ///
+    /// Errors are usage problems (no pack at the root, unknown check IDs, an
+    /// invalid contracts file); check findings are in the report.
+    pub fn run(&self) -> anyhow::Result<RunReport> {
+        let run = self.execute(Reporter::silent())?;
+        Ok(run.rep.into_report(&run.repo_root, run.counts))
+    }
+
+    /// Also run the `docs` checks against the docs tree at `docs_root`
+    /// (relative to the repo root, e.g. `docs`). Without it they are skipped.
+    pub fn docs_root(mut self, docs_root: impl Into<PathBuf>) -> Self {
+        self.docs_root = Some(docs_root.into());
+        self
+    }
+
+    /// Check `.runs/` instead of the pack: run the `runs` checks (and only
+    /// those), on every run or just `run_id`.
+    pub fn runs(mut self, run_id: Option<String>) -> Self {
+        self.runs = Some(run_id);
+        self
+    }
+
+    /// The same checker, for the pack at `repo_root`.
+    pub(crate) fn at(mut self, repo_root: PathBuf) -> Self {
+        self.repo_root = Some(repo_root);
+        self
+    }
+
+    /// The selected checks, in ID order. The `docs` checks only run with a
+    /// docs root, and the `runs` checks only in runs mode (where nothing else
+    /// runs); asking for one that can't run by ID or category is an error.
+    pub(crate) fn checks(&self) -> anyhow::Result<Vec<CheckSpec>> {
+        self.select().context("select checks")
+    }
+
+    fn select(&self) -> anyhow::Result<Vec<CheckSpec>> {
+        let sel = &self.selection;
+        let mut checks = checks::select(sel)?;
+        let unavailable = checks.iter().find(|c| {
+            !self.available(c) && (sel.only.contains(&c.id) || sel.categories.contains(&c.category))
+        });
+        if let Some(c) = unavailable {
+            match c.category {
+                Category::Runs => bail!(
+                    "check {} validates .runs/; run it with `pack-check runs`",
+                    c.id
+                ),
+                _ if self.runs.is_some() => bail!("check {} validates the pack, not .runs/", c.id),
+                _ => bail!(
+                    "check {} compares the pack against its docs and needs --docs-root",
+                    c.id
+                ),
+            }
+        }
+        checks.retain(|c| self.available(c));
+        if checks.is_empty() {
+            bail!("no checks selected");
+        }
+        Ok(checks)
+    }
+
+    /// Whether `check` can run in this checker's mode.
+    fn available(&self, check: &CheckSpec) -> bool {
+        match check.category {
+            Category::Runs => self.runs.is_some(),
+            _ if self.runs.is_some() => false,
+            Category::Docs => self.docs_root.is_some(),
+            _ => true,
+        }
+    }
+
+    /// Whether `checks` is every check this checker can run (so unused
+    /// suppressions can be reported).
+    fn is_complete(&self, checks: &[CheckSpec]) -> bool {
+        let available = checks::all().iter().filter(|c| self.available(c)).count();
+        checks.len() == available
+    }
+
+    /// Threads to run checks on.
+    pub(crate) fn threads(&self) -> usize {
+        self.jobs
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+    }
+
+    /// Load the pack: context, inventory, contracts and regexes.
+    pub(crate) fn load(&self) -> anyhow::Result<Pack> {
+        let mut ctx = match (&self.files, &self.repo_root, &self.git_ref) {
+            (Some(_), _, Some(_)) => {
+                bail!("a pack read through a file provider cannot also be read at a git revision")
+            }
+            (Some(files), Some(root), None) => Ctx::with_files(root.clone(), files.clone()),
+            (Some(_), None, None) => bail!("a pack read through a file provider needs a repo root"),
+            (None, root, Some(rev)) => Ctx::discover_at_git_ref(root.clone(), rev),
+            (None, root, None) => Ctx::discover(root.clone()),
+        }
+        .context("discover repo root")?;
+        ctx.docs_root = self.docs_root.as_ref().map(|d| ctx.repo_root.join(d));
+        if let Some(run_id) = &self.runs {
+            let runs_dir = ctx.repo_root.join(".runs");
+            if !ctx.is_dir(&runs_dir) {
+                bail!("no .runs/ directory in {}", ctx.repo_root.display());
+            }
+            if let Some(id) = run_id.as_ref().filter(|id| !ctx.is_dir(&runs_dir.join(id))) {
+                bail!("no run `{id}` in .runs/");
+            }
+            ctx.runs_dir = Some(runs_dir);
+            ctx.run_id = run_id.clone();
+        }
+
+        let inv = Inventory::from_ctx(&ctx).context("build inventory")?;
+        ctx.preload(
+            inv.agent_md_files
+                .iter()
+                .chain(&inv.command_md_files)
+                .chain(&inv.skill_md_files),
+        );
+
+        // Defaults + optional .claude/pack-contracts.{toml,json}, unless overridden
+        let contracts = match &self.contracts {
+            Some(contracts) => contracts.clone(),
+            None => {
+                contracts_file::load(ctx.files(), &ctx.claude_dir).context("load pack contracts")?
+            }
+        };
+
+        let re = Regexes::compile().context("compile regexes")?;
+
+        Ok(Pack {
+            ctx,
+            inv,
+            contracts,
+            re,
+        })
+    }
+
+    /// Run the selected checks into `rep`, which decides what gets printed.
+    pub(crate) fn execute(&self, rep: Reporter) -> anyhow::Result<Run> {
+        let checks = self.checks()?;
+        let pack = self.load()?;
+
+        let mut rep = rep.with_suppressions(
+            Suppressions::scan(&pack.ctx).context("scan suppression directives")?,
+        );
+        rep.print_banner();
+
+        // Run selected checks (keep-going: catch errors and continue), then
+        // report them in check order
+        let outcomes = run_parallel(&pack.cx(), &checks, self.threads());
+        let quiet = self.selection.only_unused_suppressions();
+        for (check, outcome) in checks.iter().zip(outcomes) {
+            if quiet {
+                rep.absorb(check.id, outcome.buffer);
+                continue;
+            }
+            rep.section(check.id, check.title);
+            outcome.report(&mut rep);
+            rep.blank_line();
+        }
+
+        // Directives are only known to be unused once every check has run
+        if rep.has_suppressions() && self.selection.includes_unused_suppressions() {
+            rep.section(
+                checks::UNUSED_SUPPRESSION_ID,
+                checks::UNUSED_SUPPRESSION_TITLE,
+            );
+            rep.report_unused_suppressions(self.is_complete(&checks));
+            rep.blank_line();
+        }
+
+        let counts = PackCounts {
+            agents: pack.inv.agent_md_files.len(),
+            commands: pack.inv.command_md_files.len(),
+            skills: pack.inv.skill_md_files.len(),
+        };
+        rep.print_summary_header();
+        rep.print_counts(&counts);
+
+        Ok(Run {
+            rep,
+            repo_root: pack.ctx.repo_root.display().to_string(),
+            counts,
+            checks,
+        })
+    }
+}
+
+/// One check's run: its buffered diagnostics, whether it ran to completion,
+/// and what it read.
+pub(crate) struct CheckOutcome {
+    pub buffer: Reporter,
+    pub result: anyhow::Result<()>,
+    pub reads: Reads,
+}
+
+impl CheckOutcome {
+    /// Replay the diagnostics into `rep` (under the check's section).
+    pub fn report(self, rep: &mut Reporter) {
+        rep.append(self.buffer);
+        if let Err(e) = self.result {
+            rep.fail(format!("check crashed: {e:#}"));
+        }
+    }
+}
+
+/// Run `checks` on up to `jobs` threads, each check into its own buffered
+/// reporter. Results come back in `checks` order, however the threads ran.
+pub(crate) fn run_parallel(cx: &CheckCtx, checks: &[CheckSpec], jobs: usize) -> Vec<CheckOutcome> {
+    let next = AtomicUsize::new(0);
+    let slots: Vec<Mutex<Option<CheckOutcome>>> = checks.iter().map(|_| Mutex::new(None)).collect();
+
+    std::thread::scope(|s| {
+        for _ in 0..jobs.min(checks.len()) {
+            s.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(check) = checks.get(i) else { break };
+                    let mut buffer = Reporter::buffered();
+                    let (result, reads) = Ctx::traced(|| (check.run)(cx, &mut buffer));
+                    *slots[i].lock().expect("slot lock") = Some(CheckOutcome {
+                        buffer,
+                        result,
+                        reads,
+                    });
+                }
+            });
+        }
+    });
+
+    slots
+        .into_iter()
+        .map(|slot| {
+            slot.into_inner()
+                .expect("slot lock")
+                .expect("every check ran")
+        })
+        .collect()
+}
+
+/// A loaded pack, ready to build a `CheckCtx` from.
+pub(crate) struct Pack {
+    pub ctx: Ctx,
+    pub inv: Inventory,
+    pub contracts: Contracts,
+    pub re: Regexes,
+}
+
+impl Pack {
+    pub fn cx(&self) -> CheckCtx<'_> {
+        CheckCtx {
+            ctx: &self.ctx,
+            inv: &self.inv,
+            re: &self.re,
+            c: &self.contracts,
+        }
+    }
+}
+
+/// What `execute` leaves for the caller to report.
+pub(crate) struct Run {
+    pub rep: Reporter,
+    pub repo_root: String,
+    pub counts: PackCounts,
+    pub checks: Vec<CheckSpec>,
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::MemoryFiles;
+    use crate::reporter::Level;
+
+    fn only(ids: &[u32]) -> Selection {
+        Selection {
+            only: ids.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_run_in_memory_pack_reports_typed_diagnostics() {
+        let files = MemoryFiles::new()
+            .with_file("pack/.claude/agents/a.md", "Traces FR-001\n")
+            .with_file("pack/.claude/agents/b.md", "Traces REQ-001\n");
+
+        let report = PackChecker::new("pack")
+            .files(files)
+            .selection(only(&[7]))
+            .run()
+            .unwrap();
+
+        assert_eq!(report.repo_root, "pack");
+        assert_eq!(report.counts.agents, 2);
+        assert_eq!(report.errors, 1);
+        assert!(!report.passed(false));
+        let diag = &report.diagnostics[0];
+        assert_eq!(diag.level, Level::Fail);
+        assert_eq!(diag.check_id, 7);
+        let loc = diag.location.as_ref().unwrap();
+        assert_eq!(
+            (loc.path.as_str(), loc.line),
+            (".claude/agents/a.md", Some(1))
+        );
+    }
+
+    #[test]
+    fn test_run_honors_contract_override() {
+        let files = MemoryFiles::new().with_file("pack/.claude/agents/signal-cleanup.md", "x\n");
+        let contracts = Contracts {
+            required_agents: vec!["signal-cleanup".to_string()],
+            ..Contracts::default()
+        };
+
+        let report = PackChecker::new("pack")
+            .files(files)
+            .contracts(contracts)
+            .selection(only(&[1]))
+            .run()
+            .unwrap();
+
+        assert!(report.passed(true), "{:?}", report.diagnostics);
+    }
+
+    #[test]
+    fn test_run_output_is_independent_of_jobs() {
+        let files = MemoryFiles::new()
+            .with_file("pack/CLAUDE.md", "# CLAUDE.md\n")
+            .with_file("pack/.claude/agents/a.md", "Traces FR-001\nNFR-1\n")
+            .with_file("pack/.claude/agents/b.md", "---\nname: c\n---\n@FR-002\n")
+            .with_file("pack/.claude/commands/flow-1-signal.md", "# Flow 1\n");
+        let checker = PackChecker::new("pack").files(files);
+
+        let sequential = checker.clone().jobs(1).run().unwrap();
+        let parallel = checker.jobs(8).run().unwrap();
+
+        assert!(sequential.errors > 0 && sequential.warnings > 0);
+        assert_eq!(
+            serde_json::to_string(&sequential).unwrap(),
+            serde_json::to_string(&parallel).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_only_and_skip_unused_suppressions() {
+        let files = MemoryFiles::new().with_file(
+            "pack/.claude/agents/a.md",
+            "<!-- pack-check-disable-next-line 7 -->\nTraces FR-001\n<!-- pack-check-disable-next-line 8 -->\nfine\n",
+        );
+        let checker = PackChecker::new("pack").files(files);
+        let located = |report: &RunReport| -> Vec<(u32, Option<usize>)> {
+            report
+                .diagnostics
+                .iter()
+                .filter(|d| d.level != Level::Pass)
+                .map(|d| (d.check_id, d.location.as_ref().and_then(|l| l.line)))
+                .collect()
+        };
+
+        // Every check runs, so the directive for 7 is used, but only 63 is reported.
+        let report = checker.clone().selection(only(&[63])).run().unwrap();
+        assert_eq!(located(&report), vec![(63, Some(3))]);
+
+        let report = checker
+            .selection(Selection {
+                only: vec![7, 8],
+                skip: vec![63],
+                ..Default::default()
+            })
+            .run()
+            .unwrap();
+        assert!(located(&report).is_empty(), "{:?}", report.diagnostics);
+    }
+
+    #[test]
+    fn test_run_errors() {
+        let in_memory = PackChecker::from_cwd().files(MemoryFiles::new());
+        assert!(in_memory.run().is_err());
+
+        let no_pack = PackChecker::new("pack").files(MemoryFiles::new().with_file("pack/x", ""));
+        let err = no_pack.run().unwrap_err();
+        assert!(format!("{err:#}").contains(".claude"), "{err:#}");
+
+        let files = MemoryFiles::new().with_file("pack/.claude/agents/a.md", "");
+        let unknown = PackChecker::new("pack")
+            .files(files)
+            .selection(only(&[999]));
+        assert!(unknown.run().is_err());
+    }
+
+    #[test]
+    fn test_docs_checks_need_docs_root() {
+        let ids = |checker: &PackChecker| -> Vec<u32> {
+            let checks = checker.checks().unwrap();
+            checks
+                .iter()
+                .map(|c| c.id)
+                .filter(|id| (74..=76).contains(id))
+                .collect()
+        };
+        let files = MemoryFiles::new()
+            .with_file("pack/.claude/agents/a.md", "")
+            .with_file(
+                "pack/docs/reference/agents-index.md",
+                "| Agent |\n|---|\n| a |\n",
+            )
+            .with_file("pack/docs/reference/skills-index.md", "# Skills\n");
+        let checker = PackChecker::new("pack").files(files);
+
+        assert_eq!(ids(&checker), Vec::<u32>::new());
+        let err = checker.clone().selection(only(&[74])).run().unwrap_err();
+        assert!(format!("{err:#}").contains("--docs-root"), "{err:#}");
+
+        let with_docs = checker.docs_root("docs");
+        assert_eq!(ids(&with_docs), vec![74, 75, 76]);
+        let report = with_docs.selection(only(&[74, 75])).run().unwrap();
+        assert!(report.passed(true), "{:?}", report.diagnostics);
+    }
+
+    #[test]
+    fn test_runs_mode_runs_only_runs_checks() {
+        let files = MemoryFiles::new()
+            .with_file("pack/.claude/agents/a.md", "")
+            .with_file("pack/.runs/index.json", r#"{"runs": [{"run_id": "r"}]}"#)
+            .with_file("pack/.runs/r/run_meta.json", r#"{"run_id": "r"}"#);
+        let checker = PackChecker::new("pack").files(files);
+        let ids = |checker: &PackChecker| -> Vec<u32> {
+            checker.checks().unwrap().iter().map(|c| c.id).collect()
+        };
+
+        assert!(!ids(&checker).contains(&77));
+        let err = checker.clone().selection(only(&[77])).run().unwrap_err();
+        assert!(format!("{err:#}").contains("pack-check runs"), "{err:#}");
+
+        let runs = checker.clone().runs(None);
+        assert_eq!(ids(&runs), vec![77, 78, 79, 80]);
+        assert!(runs.clone().selection(only(&[7])).run().is_err());
+        let report = runs.run().unwrap();
+        assert!(report.passed(true), "{:?}", report.diagnostics);
+
+        let err = checker.runs(Some("other".into())).run().unwrap_err();
+        assert!(format!("{err:#}").contains("no run `other`"), "{err:#}");
+    }
+
+    /// Test: At a git revision the committed pack is checked, not the work tree.
+    #[test]
+    fn test_git_ref_checks_the_committed_pack() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        crate::files::test_utils::commit_files(root, &[(".claude/agents/a.md", "# A\n")]);
+        std::fs::write(root.join(".claude/agents/b.md"), "# B\n").unwrap();
+        let checker = PackChecker::new(root).selection(only(&[7]));
+
+        assert_eq!(checker.clone().run().unwrap().counts.agents, 2);
+        let report = checker.clone().git_ref("HEAD").run().unwrap();
+        assert_eq!(report.counts.agents, 1);
+
+        let err = checker.git_ref("HEAD~1").run().unwrap_err();
+        assert!(
+            format!("{err:#}").contains("unknown git revision `HEAD~1`"),
+            "{err:#}"
+        );
+    }
+}
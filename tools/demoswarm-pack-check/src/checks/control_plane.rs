@@ -22,13 +22,21 @@
 //! - Check 32: CANNOT_PROCEED invariant (useful for mechanical failures)
 //! - Check 54: Critics have Handoff section (NEW - Claude-native)
 //! - Check 55: Agents have clear job section (NEW - Claude-native)
+//! - Check 71: Agent prompts have Inputs/Outputs/Handoff Targets, no duplicate sections
+//! - Check 72: Handoff targets and skill references resolve
 //!
-//! Checks: 4, 17, 18, 19, 32, 54, 55
+//! Checks: 4, 17, 18, 19, 32, 54, 55, 71, 72
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::LazyLock;
+
+use regex::Regex;
 
 use super::contracts::headings;
-use crate::reporter::Reporter;
+use crate::markdown::{Markdown, Scope};
+use crate::reporter::{Finding, Location, Reporter};
 
-use super::{CheckCtx, CheckSpec};
+use super::{Category, CheckCtx, CheckSpec};
 
 pub fn checks() -> Vec<CheckSpec> {
     vec![
@@ -36,39 +44,85 @@ pub fn checks() -> Vec<CheckSpec> {
         CheckSpec {
             id: 4,
             title: "Checking cleanup agents reference receipts + index.json...",
+            category: Category::ControlPlane,
+            rationale: "Cleanup agents reference receipts + index.json.",
             run: check_cleanup_receipts,
         },
         CheckSpec {
             id: 17,
             title: "Checking gh-reporter output constraints...",
+            category: Category::ControlPlane,
+            rationale: "gh-reporter safe output contract.",
             run: check_gh_reporter_output,
         },
         CheckSpec {
             id: 18,
             title: "Checking repo-operator has Repo Operator Result block...",
+            category: Category::ControlPlane,
+            rationale: "repo-operator has Repo Operator Result block.",
             run: check_repo_operator_result,
         },
         CheckSpec {
             id: 19,
             title: "Checking GH agents enforce two gates...",
+            category: Category::ControlPlane,
+            rationale: "GH agents enforce two gates.",
             run: check_gh_agents_two_gates,
         },
         CheckSpec {
             id: 32,
             title: "Checking CANNOT_PROCEED invariant...",
+            category: Category::ControlPlane,
+            rationale: "CANNOT_PROCEED requires missing_required.",
             run: check_cannot_proceed_invariant,
         },
         // == NEW CLAUDE-NATIVE CHECKS ==
         CheckSpec {
             id: 54,
             title: "Checking critics have Handoff section...",
+            category: Category::ControlPlane,
+            rationale: "Critics have Handoff section.\n\
+                \n\
+                In Claude-native mode, critics communicate routing decisions via prose Handoff\n\
+                sections instead of structured YAML Machine Summary blocks.",
             run: check_critics_handoff_section,
         },
         CheckSpec {
             id: 55,
             title: "Checking agents have clear job section...",
+            category: Category::ControlPlane,
+            rationale: "Agents have clear job section.\n\
+                \n\
+                Claude-native agents should have a clear job description, typically under\n\
+                ## Your Job, ## Job, or similar heading.",
             run: check_agents_clear_job,
         },
+        CheckSpec {
+            id: 71,
+            title: "Checking agent prompts have Inputs, Outputs and Handoff Targets sections...",
+            category: Category::ControlPlane,
+            rationale: "Agent prompts have Inputs, Outputs and Handoff Targets sections,\n\
+                and no `##` section twice.\n\
+                \n\
+                Missing sections are warnings (older agents predate the template). A\n\
+                repeated section is a failure: the agent reads two versions of the same\n\
+                instructions. Headings compare without a trailing parenthetical, so\n\
+                `## Handoff Targets (reference)` repeats `## Handoff Targets`.",
+            run: check_agent_prompt_sections,
+        },
+        CheckSpec {
+            id: 72,
+            title: "Checking agent handoff targets and skill references resolve...",
+            category: Category::ControlPlane,
+            rationale: "Handoff targets name existing agents and skill references name\n\
+                existing skills.\n\
+                \n\
+                Targets are the `- **agent-name**:` items under `## Handoff Targets`.\n\
+                Skill references are \"the `name` skill\", `.claude/skills/<name>` paths and\n\
+                `- **name**:` items under `## Skills`. Quoted examples and frontmatter\n\
+                don't count.",
+            run: check_agent_prompt_references,
+        },
     ]
 }
 
@@ -91,7 +145,7 @@ fn check_critics_machine_summary(_cx: &CheckCtx, _rep: &mut Reporter) -> anyhow:
 
 /// Check 4: Cleanup agents reference receipts + index.json.
 fn check_cleanup_receipts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
-    for (agent, receipt) in cx.c.cleanup_agents {
+    for (agent, receipt) in &cx.c.cleanup_agents {
         let Some(file) = cx.inv.agent(agent) else {
             continue;
         };
@@ -101,13 +155,19 @@ fn check_cleanup_receipts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<(
         if content.contains(receipt) {
             rep.pass(format!("{agent} references {receipt}"));
         } else {
-            rep.fail(format!("{agent} does NOT reference {receipt}"));
+            rep.fail_at(
+                Location::file(cx.ctx.rel(file)),
+                format!("{agent} does NOT reference {receipt}"),
+            );
         }
 
         if content.contains("index.json") {
             rep.pass(format!("{agent} references index.json updates"));
         } else {
-            rep.fail(format!("{agent} does NOT reference index.json updates"));
+            rep.fail_at(
+                Location::file(cx.ctx.rel(file)),
+                format!("{agent} does NOT reference index.json updates"),
+            );
         }
     }
 
@@ -122,13 +182,19 @@ fn check_gh_reporter_output(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result
         if content.contains(headings::SAFE_OUTPUT_CONTRACT) {
             rep.pass("gh-reporter has 'Safe Output Contract' section");
         } else {
-            rep.fail("gh-reporter MISSING 'Safe Output Contract' section");
+            rep.fail_at(
+                Location::file(cx.ctx.rel(gh_reporter)),
+                "gh-reporter MISSING 'Safe Output Contract' section",
+            );
         }
 
         if content.contains("must NOT paste verbatim") {
             rep.pass("gh-reporter documents output constraints");
         } else {
-            rep.warn("gh-reporter may be missing output constraint documentation");
+            rep.warn_at(
+                Location::file(cx.ctx.rel(gh_reporter)),
+                "gh-reporter may be missing output constraint documentation",
+            );
         }
     }
 
@@ -148,22 +214,28 @@ fn check_repo_operator_result(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Resu
         rep.pass("repo-operator.md has '## Repo Operator Result' section");
 
         let mut missing = Vec::new();
-        for f in cx.c.repo_operator_result_fields {
+        for f in &cx.c.repo_operator_result_fields {
             if !content.contains(f) {
-                missing.push(*f);
+                missing.push(f.as_str());
             }
         }
 
         if missing.is_empty() {
             rep.pass("repo-operator.md has required Repo Operator Result fields");
         } else {
-            rep.fail(format!(
-                "repo-operator.md missing Repo Operator Result fields: {}",
-                missing.join(" ")
-            ));
+            rep.fail_at(
+                Location::file(cx.ctx.rel(repo_operator)),
+                format!(
+                    "repo-operator.md missing Repo Operator Result fields: {}",
+                    missing.join(" ")
+                ),
+            );
         }
     } else {
-        rep.fail("repo-operator.md MISSING '## Repo Operator Result' section");
+        rep.fail_at(
+            Location::file(cx.ctx.rel(repo_operator)),
+            "repo-operator.md MISSING '## Repo Operator Result' section",
+        );
     }
 
     Ok(())
@@ -171,7 +243,7 @@ fn check_repo_operator_result(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Resu
 
 /// Check 19: GH agents enforce two gates.
 fn check_gh_agents_two_gates(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
-    for agent in cx.c.gh_agents {
+    for agent in &cx.c.gh_agents {
         let Some(file) = cx.inv.agent(agent) else {
             continue;
         };
@@ -180,9 +252,12 @@ fn check_gh_agents_two_gates(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Resul
         if content.contains("safe_to_publish") && content.contains("proceed_to_github_ops") {
             rep.pass(format!("{agent} enforces two gates"));
         } else {
-            rep.fail(format!(
-                "{agent} does NOT enforce both gates (safe_to_publish AND proceed_to_github_ops)"
-            ));
+            rep.fail_at(
+                Location::file(cx.ctx.rel(file)),
+                format!(
+                    "{agent} does NOT enforce both gates (safe_to_publish AND proceed_to_github_ops)"
+                ),
+            );
         }
     }
 
@@ -221,7 +296,7 @@ fn check_route_fields(_cx: &CheckCtx, _rep: &mut Reporter) -> anyhow::Result<()>
 
 /// Check 32: CANNOT_PROCEED requires missing_required.
 fn check_cannot_proceed_invariant(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
-    for agent in cx.c.critic_and_verifier_agents {
+    for agent in &cx.c.critic_and_verifier_agents {
         let Some(file) = cx.inv.agent(agent) else {
             continue;
         };
@@ -233,9 +308,16 @@ fn check_cannot_proceed_invariant(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::
                     "{agent} documents missing_required for CANNOT_PROCEED"
                 ));
             } else {
-                rep.fail(format!(
-                    "{agent} uses CANNOT_PROCEED but missing missing_required documentation"
-                ));
+                let mut loc = Location::file(cx.ctx.rel(file));
+                if let Some(idx) = content.lines().position(|l| l.contains("CANNOT_PROCEED")) {
+                    loc = loc.with_line(idx + 1);
+                }
+                rep.fail_at(
+                    loc,
+                    format!(
+                        "{agent} uses CANNOT_PROCEED but missing missing_required documentation"
+                    ),
+                );
             }
         } else {
             rep.pass(format!("{agent} (no CANNOT_PROCEED mention)"));
@@ -290,13 +372,10 @@ fn check_critics_observations_field(_cx: &CheckCtx, _rep: &mut Reporter) -> anyh
 // =============================================================================
 
 /// Check 54: Critics have Handoff section.
-///
-/// In Claude-native mode, critics communicate routing decisions via prose Handoff
-/// sections instead of structured YAML Machine Summary blocks.
 fn check_critics_handoff_section(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let mut missing = Vec::new();
 
-    for critic in cx.c.critics {
+    for critic in &cx.c.critics {
         let Some(file) = cx.inv.agent(critic) else {
             continue;
         };
@@ -305,7 +384,10 @@ fn check_critics_handoff_section(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::R
 
         // Look for ## Handoff section (Claude-native pattern)
         if !content.contains("## Handoff") {
-            missing.push(critic.to_string());
+            missing.push(Finding::new(
+                Location::file(cx.ctx.rel(file)),
+                format!("({critic})"),
+            ));
         }
     }
 
@@ -313,19 +395,16 @@ fn check_critics_handoff_section(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::R
         rep.pass("All critics have ## Handoff section");
     } else {
         // Warn rather than fail during transition period
-        rep.warn(format!(
-            "Critics missing ## Handoff section (Claude-native pattern): {}",
-            missing.join(", ")
-        ));
+        rep.warn_findings(
+            "Critics missing ## Handoff section (Claude-native pattern):",
+            missing,
+        );
     }
 
     Ok(())
 }
 
 /// Check 55: Agents have clear job section.
-///
-/// Claude-native agents should have a clear job description, typically under
-/// ## Your Job, ## Job, or similar heading.
 fn check_agents_clear_job(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     // Check a subset of critical agents for clear job descriptions
     let critical_agents = [
@@ -354,7 +433,10 @@ fn check_agents_clear_job(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<(
             || content.contains("You are the");
 
         if !has_job_section {
-            missing.push(agent.to_string());
+            missing.push(Finding::new(
+                Location::file(cx.ctx.rel(file)),
+                format!("({agent})"),
+            ));
         }
     }
 
@@ -362,15 +444,208 @@ fn check_agents_clear_job(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<(
         rep.pass("Critical agents have clear job descriptions");
     } else {
         // Warn rather than fail during transition period
-        rep.warn(format!(
-            "Agents missing clear job section: {}",
-            missing.join(", ")
-        ));
+        rep.warn_findings("Agents missing clear job section:", missing);
+    }
+
+    Ok(())
+}
+
+/// Heading prefixes (lowercase) of an agent's Inputs section.
+pub(super) const INPUT_SECTIONS: &[&str] = &[
+    "input",
+    "required input",
+    "what you'll need",
+    "what you\u{2019}ll need",
+];
+
+/// Section groups every agent prompt needs: a `##` heading starting with
+/// one of the prefixes (case-insensitive) satisfies the group.
+const PROMPT_SECTIONS: [(&str, &[&str]); 3] = [
+    ("Inputs", INPUT_SECTIONS),
+    ("Outputs", &["output"]),
+    ("Handoff Targets", &["handoff targets"]),
+];
+
+/// Check 71: Agent prompts have Inputs, Outputs and Handoff Targets sections,
+/// and no `##` section twice.
+fn check_agent_prompt_sections(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let mut missing = Vec::new();
+    let mut duplicates = Vec::new();
+
+    for file in &cx.inv.agent_md_files {
+        let md = cx.ctx.markdown(file)?;
+        let rel = cx.ctx.rel(file);
+        let h2: Vec<_> = md.headings.iter().filter(|h| h.level == 2).collect();
+
+        for (group, prefixes) in PROMPT_SECTIONS {
+            let found = h2.iter().any(|h| {
+                let text = h.text.to_lowercase();
+                prefixes.iter().any(|p| text.starts_with(p))
+            });
+            if !found {
+                missing.push(Finding::new(
+                    Location::file(rel.clone()),
+                    format!("no {group} section"),
+                ));
+            }
+        }
+
+        let mut first_seen: HashMap<&str, usize> = HashMap::new();
+        let mut reported = BTreeSet::new();
+        for h in &h2 {
+            let name = section_name(&h.text);
+            match first_seen.get(name) {
+                Some(&first) if reported.insert(name) => duplicates.push(Finding::new(
+                    Location::file(rel.clone()).with_line(h.line),
+                    format!("`## {name}` repeats the section at line {first}"),
+                )),
+                Some(_) => {}
+                None => {
+                    first_seen.insert(name, h.line);
+                }
+            }
+        }
+    }
+
+    if duplicates.is_empty() && missing.is_empty() {
+        rep.pass("All agent prompts have Inputs, Outputs and Handoff Targets sections");
+    }
+    if !duplicates.is_empty() {
+        rep.fail_findings("Agent prompts repeat a section:", duplicates);
+    }
+    if !missing.is_empty() {
+        rep.warn_findings("Agent prompts missing a required section:", missing);
+    }
+
+    Ok(())
+}
+
+/// A heading without its trailing parenthetical (`Inputs (best-effort)` -> `Inputs`).
+fn section_name(text: &str) -> &str {
+    let text = text.trim();
+    match text
+        .strip_suffix(')')
+        .and_then(|t| t.rfind('(').map(|i| t[..i].trim_end()))
+    {
+        Some(head) if !head.is_empty() => head,
+        _ => text,
+    }
+}
+
+/// `- **name**:` list items (handoff targets, Skills sections).
+static BOLD_ITEM: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[-*]\s+\*\*([a-z][a-z0-9-]*)\*\*\s*:").expect("valid regex"));
+/// "the `name` skill"
+static SKILL_MENTION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)`([a-z][a-z0-9-]+)`\s+skill").expect("valid regex"));
+/// `.claude/skills/<name>`
+static SKILL_PATH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\.claude/skills/([a-z][a-z0-9-]+)").expect("valid regex"));
+
+/// Check 72: Handoff targets name existing agents and skill references name
+/// existing skills.
+fn check_agent_prompt_references(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let skills: BTreeSet<String> = cx
+        .ctx
+        .files_under(&cx.ctx.skills_dir)
+        .iter()
+        .filter_map(|p| {
+            let rel = p.strip_prefix(&cx.ctx.skills_dir).ok()?;
+            let mut parts = rel.components();
+            let dir = parts.next()?;
+            parts.next()?;
+            Some(dir.as_os_str().to_string_lossy().into_owned())
+        })
+        .collect();
+
+    let mut broken = Vec::new();
+    for file in &cx.inv.agent_md_files {
+        let md = cx.ctx.markdown(file)?;
+        let rel = cx.ctx.rel(file);
+        let at = |line: usize, text: String| {
+            Finding::new(Location::file(rel.clone()).with_line(line), text)
+        };
+
+        for (line, target) in bold_items(&md, |h| h.starts_with("Handoff Targets")) {
+            if cx.inv.agent(&target).is_none() {
+                broken.push(at(
+                    line,
+                    format!("handoff target `{target}` is not an agent in .claude/agents/"),
+                ));
+            }
+        }
+
+        // One finding per missing skill and file, at its first mention.
+        let mut reported = BTreeSet::new();
+        for (line, skill) in skill_references(&md) {
+            if !skills.contains(&skill) && reported.insert(skill.clone()) {
+                broken.push(at(
+                    line,
+                    format!("skill `{skill}` is not in .claude/skills/"),
+                ));
+            }
+        }
+    }
+
+    if broken.is_empty() {
+        rep.pass("All handoff targets and skill references resolve");
+    } else {
+        rep.fail_findings(
+            "Agent prompts reference agents or skills that do not exist:",
+            broken,
+        );
     }
 
     Ok(())
 }
 
+/// `(line, skill)` of every skill reference in an agent prompt, in line order:
+/// "the `name` skill", `.claude/skills/<name>` paths and `- **name**:` items
+/// under `## Skills`.
+pub(super) fn skill_references(md: &Markdown) -> Vec<(usize, String)> {
+    let mut refs: Vec<(usize, String)> = md
+        .lines(Scope::Both)
+        .flat_map(|(line, text)| {
+            SKILL_MENTION
+                .captures_iter(text)
+                .chain(SKILL_PATH.captures_iter(text))
+                .map(|c| (line, c[1].to_string()))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    refs.extend(bold_items(md, |h| h == "Skills"));
+    refs.sort();
+    refs
+}
+
+/// `(line, name)` of the `- **name**:` items in the `##` sections whose
+/// heading text satisfies `wanted`.
+fn bold_items(md: &Markdown, wanted: impl Fn(&str) -> bool) -> Vec<(usize, String)> {
+    let ranges: Vec<(usize, usize)> = md
+        .sections()
+        .into_iter()
+        .filter(|s| {
+            let h = &md.headings[s.heading];
+            h.level == 2 && wanted(h.text.trim())
+        })
+        .map(|s| (s.start, s.end))
+        .collect();
+
+    md.lines(Scope::Both)
+        .filter(|(line, _)| {
+            ranges
+                .iter()
+                .any(|&(start, end)| (start..=end).contains(line))
+        })
+        .flat_map(|(line, text)| {
+            BOLD_ITEM
+                .captures_iter(text)
+                .map(|c| (line, c[1].to_string()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
 // =============================================================================
 // Unit Tests
 // =============================================================================
@@ -599,7 +874,10 @@ mod tests {
 
         for field in expected_fields {
             assert!(
-                contracts.repo_operator_result_fields.contains(&field),
+                contracts
+                    .repo_operator_result_fields
+                    .iter()
+                    .any(|x| x == field),
                 "Repo Operator Result should require field: {}",
                 field
             );
@@ -642,7 +920,7 @@ mod tests {
 
         for critic in expected_critics {
             assert!(
-                contracts.critics.contains(&critic),
+                contracts.critics.iter().any(|x| x == critic),
                 "Critics list should contain: {}",
                 critic
             );
@@ -686,7 +964,7 @@ mod tests {
         let contracts = crate::contracts::Contracts::default();
 
         // All critics should be in critic_and_verifier_agents
-        for critic in contracts.critics {
+        for critic in &contracts.critics {
             assert!(
                 contracts.critic_and_verifier_agents.contains(critic),
                 "critic_and_verifier_agents should contain critic: {}",
@@ -709,7 +987,10 @@ mod tests {
 
         for verifier in additional_verifiers {
             assert!(
-                contracts.critic_and_verifier_agents.contains(&verifier),
+                contracts
+                    .critic_and_verifier_agents
+                    .iter()
+                    .any(|x| x == verifier),
                 "critic_and_verifier_agents should contain verifier: {}",
                 verifier
             );
@@ -729,7 +1010,7 @@ mod tests {
 
         for agent in expected_gate_agents {
             assert!(
-                contracts.gate_agents.contains(&agent),
+                contracts.gate_agents.iter().any(|x| x == agent),
                 "Gate agents should contain: {}",
                 agent
             );
@@ -749,7 +1030,7 @@ mod tests {
 
         for agent in expected_gh_agents {
             assert!(
-                contracts.gh_agents.contains(&agent),
+                contracts.gh_agents.iter().any(|x| x == agent),
                 "GH agents should contain: {}",
                 agent
             );
@@ -822,7 +1103,7 @@ mod tests {
         let contracts = crate::contracts::Contracts::default();
 
         // All receipt names should end with _receipt.json
-        for (_, receipt) in contracts.cleanup_agents {
+        for (_, receipt) in &contracts.cleanup_agents {
             assert!(
                 receipt.ends_with("_receipt.json"),
                 "Receipt {} should end with _receipt.json",
@@ -1035,7 +1316,7 @@ publish_surface: PUSHED | NOT_PUSHED
 anomaly_paths: []
 "#;
 
-        for field in contracts.repo_operator_result_fields {
+        for field in &contracts.repo_operator_result_fields {
             assert!(
                 content.contains(field),
                 "Content should contain Repo Operator Result field: {}",
@@ -1249,6 +1530,15 @@ concerns: []
         impl TestFixture {
             /// Create a new test fixture with the given agents and commands.
             fn new(agents: &[(&str, &str)], commands: &[(&str, &str)]) -> anyhow::Result<Self> {
+                Self::with_skills(agents, commands, &[])
+            }
+
+            /// Like `new`, with skill directories (each holding a SKILL.md).
+            fn with_skills(
+                agents: &[(&str, &str)],
+                commands: &[(&str, &str)],
+                skills: &[&str],
+            ) -> anyhow::Result<Self> {
                 let temp_dir = TempDir::new()?;
                 let root = temp_dir.path();
 
@@ -1274,6 +1564,11 @@ concerns: []
                     fs::write(&path, content)?;
                 }
 
+                for name in skills {
+                    fs::create_dir_all(skills_dir.join(name))?;
+                    fs::write(skills_dir.join(name).join("SKILL.md"), "")?;
+                }
+
                 // Build Ctx and Inventory
                 let ctx = Ctx::discover(Some(root.to_path_buf()))?;
                 let inv = Inventory::from_ctx(&ctx)?;
@@ -1939,5 +2234,85 @@ Some vague content without clear job description.
                 "Should have warning for missing clear job section"
             );
         }
+
+        /// Non-pass diagnostics as `level path[:line]: message`.
+        fn findings(rep: &Reporter) -> Vec<String> {
+            use crate::reporter::Level;
+            rep.diagnostics()
+                .iter()
+                .filter(|d| d.level != Level::Pass)
+                .map(|d| {
+                    let level = if d.level == Level::Fail {
+                        "fail"
+                    } else {
+                        "warn"
+                    };
+                    let loc = d.location.as_ref().unwrap();
+                    match loc.line {
+                        Some(line) => format!("{level} {}:{line}: {}", loc.path, d.message),
+                        None => format!("{level} {}: {}", loc.path, d.message),
+                    }
+                })
+                .collect()
+        }
+
+        // ---------------------------------------------------------------------
+        // Check 71: Agent prompt sections
+        // ---------------------------------------------------------------------
+
+        #[test]
+        fn test_check_agent_prompt_sections() {
+            let complete = "# A\n\n## Inputs (best-effort)\n\n## Outputs\n\n## Handoff Targets\n";
+            let broken = "# B\n\n## What you'll need\n\n## Handoff Targets\n\n\
+                ```md\n## Handoff Targets\n```\n\n\
+                ## Handoff Targets (reference)\n\n## handoff targets\n";
+            let fixture = TestFixture::new(&[("a", complete), ("b", broken)], &[])
+                .expect("Failed to create fixture");
+            let mut rep = test_reporter();
+
+            check_agent_prompt_sections(&fixture.check_ctx(), &mut rep).expect("Check failed");
+            assert_eq!(
+                findings(&rep),
+                [
+                    "fail .claude/agents/b.md:11: Agent prompts repeat a section: `## Handoff Targets` repeats the section at line 5",
+                    "warn .claude/agents/b.md: Agent prompts missing a required section: no Outputs section",
+                ]
+            );
+        }
+
+        // ---------------------------------------------------------------------
+        // Check 72: Handoff targets and skill references
+        // ---------------------------------------------------------------------
+
+        #[test]
+        fn test_check_agent_prompt_references() {
+            let agent = "---\nname: a\ndescription: uses `ghost` skill\n---\n# A\n\n\
+                Use the `runs-index` skill, then the `openq-tools` skill.\n\n\
+                > Not the `quoted` skill.\n\n\
+                ## Skills\n\n- **runs-index**: indexing\n- **secrets-tools**: scans\n\n\
+                ## Handoff Targets\n\n- **b**: next\n- **ghost-agent**: gone\n\n\
+                See `.claude/skills/openq-tools/SKILL.md`.\n";
+            let fixture =
+                TestFixture::with_skills(&[("a", agent), ("b", "# B\n")], &[], &["runs-index"])
+                    .expect("Failed to create fixture");
+            let mut rep = test_reporter();
+
+            check_agent_prompt_references(&fixture.check_ctx(), &mut rep).expect("Check failed");
+            let headline = "Agent prompts reference agents or skills that do not exist";
+            assert_eq!(
+                findings(&rep),
+                [
+                    format!(
+                        "fail .claude/agents/a.md:19: {headline}: handoff target `ghost-agent` is not an agent in .claude/agents/"
+                    ),
+                    format!(
+                        "fail .claude/agents/a.md:7: {headline}: skill `openq-tools` is not in .claude/skills/"
+                    ),
+                    format!(
+                        "fail .claude/agents/a.md:14: {headline}: skill `secrets-tools` is not in .claude/skills/"
+                    ),
+                ]
+            );
+        }
     }
 }
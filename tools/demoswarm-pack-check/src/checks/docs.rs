@@ -0,0 +1,1137 @@
+//! Docs drift checks: `docs/reference` indexes and README/CLAUDE.md against the pack.
+//!
+//! These compare prose written by hand with what the pack contains, so they
+//! run only when a docs tree is given (`--docs-root`, `Ctx::docs_root`).
+//! Indexes are read from their tables (`| Agent | Description |`) and, for
+//! skills, from the `## skill-name` sections with a `**Description:**` line.
+//!
+//! Checks: 74, 75, 76
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{Context, bail};
+use regex::Regex;
+
+use crate::fix::FileFix;
+use crate::frontmatter::Fields;
+use crate::markdown::Scope;
+use crate::reporter::{Finding, Location, Reporter};
+use crate::topology::Flow;
+
+use super::graph::{EdgeKind, HandoffGraph};
+use super::{Category, CheckCtx, CheckSpec, control_plane};
+
+pub fn checks() -> Vec<CheckSpec> {
+    vec![
+        CheckSpec {
+            id: 74,
+            title: "Checking docs indexes list every agent and skill with its description...",
+            category: Category::Docs,
+            rationale: "Every agent and skill is listed in the docs indexes, with the\n\
+                description its frontmatter has.\n\
+                \n\
+                Agents must appear in `reference/agents-index.md` (any table with an\n\
+                `Agent` column); skills in `reference/skills-index.md` (a `Skill` table\n\
+                row or a `## skill-name` section). A missing entry fails. A description\n\
+                that neither starts with the frontmatter `description` nor is a prefix of\n\
+                it warns: the index has drifted from the file it summarizes.",
+            run: check_indexes_cover_pack,
+        },
+        CheckSpec {
+            id: 75,
+            title: "Checking docs indexes do not list deleted agents or skills...",
+            category: Category::Docs,
+            rationale: "Docs indexes name only agents and skills that exist.\n\
+                \n\
+                The `Agent` column of every table in `agents-index.md`, `agent-matrix.md`\n\
+                and `routing-table.md`, and the skills in `skills-index.md`, must resolve\n\
+                to `.claude/agents/<name>.md` and `.claude/skills/<name>/`. A stale row\n\
+                sends readers (and agents reading the docs) to something deleted. Cells\n\
+                that are not a single name (totals, \"Requesting agent\") are skipped.",
+            run: check_indexes_have_no_stale_entries,
+        },
+        CheckSpec {
+            id: 76,
+            title: "Checking flow counts in README.md and CLAUDE.md...",
+            category: Category::Docs,
+            rationale: "Flow counts stated in README.md and CLAUDE.md match the pack's\n\
+                flow commands.\n\
+                \n\
+                Every \"N flows\" (digits or a number word, headings included) outside code\n\
+                blocks and quotes must equal the number of distinct flows among the\n\
+                `flow-N-*.md` commands. Adding or retiring a flow without updating the\n\
+                front-page docs fails here.",
+            run: check_flow_counts,
+        },
+    ]
+}
+
+const AGENTS_INDEX: &str = "agents-index.md";
+const SKILLS_INDEX: &str = "skills-index.md";
+/// Indexes whose `Agent` tables may only name existing agents (check 75).
+const AGENT_TABLES: [&str; 3] = [AGENTS_INDEX, "agent-matrix.md", "routing-table.md"];
+
+/// One row (or skill section) of an index.
+struct Entry {
+    name: String,
+    description: Option<String>,
+    path: PathBuf,
+    line: usize,
+}
+
+impl Entry {
+    fn finding(&self, cx: &CheckCtx, text: String) -> Finding {
+        Finding::new(
+            Location::file(cx.ctx.rel(&self.path)).with_line(self.line),
+            text,
+        )
+    }
+}
+
+/// `<docs-root>/reference/<name>`, if a docs root is set.
+fn reference(cx: &CheckCtx, name: &str) -> Option<PathBuf> {
+    Some(cx.ctx.docs_root.as_ref()?.join("reference").join(name))
+}
+
+/// Rows of every table in `path` with a `column` header (`Agent`, `Skill`),
+/// with the `Description` cell if the table has one.
+fn table_entries(cx: &CheckCtx, path: &Path, column: &str) -> anyhow::Result<Vec<Entry>> {
+    let md = cx.ctx.markdown(path)?;
+    let mut out = Vec::new();
+
+    for table in md.tables() {
+        let Some(col) = table.column(column) else {
+            continue;
+        };
+        let desc = table.column("Description");
+        for (line, cells) in &table.rows {
+            let Some(name) = cells.get(col).and_then(|c| entry_name(c)) else {
+                continue;
+            };
+            out.push(Entry {
+                name,
+                description: desc.and_then(|d| cells.get(d)).cloned(),
+                path: path.to_path_buf(),
+                line: *line,
+            });
+        }
+    }
+
+    Ok(out)
+}
+
+/// `## skill-name` sections of the skills index and their `**Description:**`.
+fn skill_sections(cx: &CheckCtx, path: &Path) -> anyhow::Result<Vec<Entry>> {
+    let md = cx.ctx.markdown(path)?;
+    let mut out = Vec::new();
+
+    for section in md.sections() {
+        let heading = &md.headings[section.heading];
+        if heading.level != 2 || !is_name(&heading.text) {
+            continue;
+        }
+        let description = md
+            .lines(Scope::Prose)
+            .filter(|(n, _)| (section.start..=section.end).contains(n))
+            .find_map(|(_, l)| l.trim().strip_prefix("**Description:**"))
+            .map(|d| d.trim().to_string());
+        out.push(Entry {
+            name: heading.text.clone(),
+            description,
+            path: path.to_path_buf(),
+            line: heading.line,
+        });
+    }
+
+    Ok(out)
+}
+
+/// The agent or skill a table cell names: `` `name` ``, `[name](#anchor)` or
+/// `name`. `None` for cells that are not a single name (totals, prose).
+fn entry_name(cell: &str) -> Option<String> {
+    let cell = cell.trim().trim_matches('`');
+    let cell = match cell.strip_prefix('[') {
+        Some(rest) => rest.split(']').next()?.trim_matches('`'),
+        None => cell,
+    };
+    is_name(cell).then(|| cell.to_string())
+}
+
+fn is_name(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_lowercase())
+        && text
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// The frontmatter `description` of an agent or SKILL.md file.
+fn frontmatter_description(cx: &CheckCtx, path: &Path) -> anyhow::Result<Option<String>> {
+    let md = cx.ctx.markdown(path)?;
+    Ok(md
+        .frontmatter
+        .as_ref()
+        .and_then(|fm| Fields::parse(fm).ok())
+        .and_then(|f| f.get("description")?.text()))
+}
+
+/// Descriptions match when one starts with the other, ignoring case,
+/// whitespace, markdown emphasis and the index's `→ outputs` suffix.
+fn descriptions_match(index: &str, frontmatter: &str) -> bool {
+    let normalize = |s: &str| {
+        let s = s.split(['→']).next().unwrap_or(s);
+        let s = s.split(" -> ").next().unwrap_or(s);
+        let s: String = s.chars().filter(|c| !matches!(c, '`' | '*')).collect();
+        s.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_end_matches(['.', ':', ' '])
+            .to_lowercase()
+    };
+    let (a, b) = (normalize(index), normalize(frontmatter));
+    !a.is_empty() && (a.starts_with(&b) || b.starts_with(&a))
+}
+
+/// The pack's agents and skills, by name, with the file holding their frontmatter.
+pub(crate) fn pack_entries(cx: &CheckCtx) -> [(&'static str, BTreeMap<String, PathBuf>); 2] {
+    let agents = cx
+        .inv
+        .agents_by_stem
+        .iter()
+        .map(|(name, path)| (name.clone(), path.clone()))
+        .collect();
+    let skills = cx
+        .inv
+        .skill_md_files
+        .iter()
+        .filter_map(|path| {
+            let dir = path.parent()?.file_name()?.to_str()?;
+            Some((dir.to_string(), path.clone()))
+        })
+        .collect();
+    [("agent", agents), ("skill", skills)]
+}
+
+/// Check 74: Every agent and skill is listed in the docs indexes, with the
+/// description its frontmatter has.
+fn check_indexes_cover_pack(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let (Some(agents_index), Some(skills_index)) =
+        (reference(cx, AGENTS_INDEX), reference(cx, SKILLS_INDEX))
+    else {
+        rep.pass("No docs root given; docs indexes not checked");
+        return Ok(());
+    };
+
+    let mut missing = Vec::new();
+    let mut drifted = Vec::new();
+
+    for ((kind, pack), index) in pack_entries(cx)
+        .into_iter()
+        .zip([agents_index, skills_index])
+    {
+        if !cx.ctx.is_file(&index) {
+            missing.push(Finding::new(
+                Location::file(cx.ctx.rel(&index)),
+                format!("{kind} index does not exist"),
+            ));
+            continue;
+        }
+        let mut entries = table_entries(cx, &index, kind)?;
+        if kind == "skill" {
+            entries.extend(skill_sections(cx, &index)?);
+        }
+
+        for (name, file) in pack {
+            let listed: Vec<&Entry> = entries.iter().filter(|e| e.name == name).collect();
+            if listed.is_empty() {
+                missing.push(Finding::new(
+                    Location::file(cx.ctx.rel(&index)),
+                    format!("{kind} `{name}` ({}) is not listed", cx.ctx.rel(&file)),
+                ));
+                continue;
+            }
+
+            let Some(actual) = frontmatter_description(cx, &file)? else {
+                continue;
+            };
+            let described: Vec<&&Entry> =
+                listed.iter().filter(|e| e.description.is_some()).collect();
+            let matches = described
+                .iter()
+                .any(|e| descriptions_match(e.description.as_deref().unwrap_or(""), &actual));
+            if let (Some(first), false) = (described.first(), matches) {
+                drifted.push(first.finding(
+                    cx,
+                    format!(
+                        "`{name}`: \"{}\" does not match its frontmatter description \"{actual}\"",
+                        first.description.as_deref().unwrap_or("")
+                    ),
+                ));
+            }
+        }
+    }
+
+    if missing.is_empty() && drifted.is_empty() {
+        rep.pass("Docs indexes list every agent and skill with its description");
+    }
+    if !missing.is_empty() {
+        rep.fail_findings("Docs indexes are missing pack entries:", missing);
+    }
+    if !drifted.is_empty() {
+        rep.warn_findings("Docs index descriptions drifted from the pack:", drifted);
+    }
+
+    Ok(())
+}
+
+/// Check 75: Docs indexes name only agents and skills that exist.
+fn check_indexes_have_no_stale_entries(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    if cx.ctx.docs_root.is_none() {
+        rep.pass("No docs root given; docs indexes not checked");
+        return Ok(());
+    }
+
+    let [(_, agents), (_, skills)] = pack_entries(cx);
+    let mut stale = Vec::new();
+
+    for name in AGENT_TABLES {
+        let Some(path) = reference(cx, name).filter(|p| cx.ctx.is_file(p)) else {
+            continue;
+        };
+        for entry in table_entries(cx, &path, "Agent")? {
+            if !agents.contains_key(&entry.name) {
+                let text = format!("`{}` is not an agent in .claude/agents/", entry.name);
+                stale.push(entry.finding(cx, text));
+            }
+        }
+    }
+
+    if let Some(path) = reference(cx, SKILLS_INDEX).filter(|p| cx.ctx.is_file(p)) {
+        let mut entries = table_entries(cx, &path, "Skill")?;
+        entries.extend(skill_sections(cx, &path)?);
+        entries.sort_by_key(|e| e.line);
+        for entry in entries {
+            if !skills.contains_key(&entry.name) {
+                let text = format!("`{}` is not a skill in .claude/skills/", entry.name);
+                stale.push(entry.finding(cx, text));
+            }
+        }
+    }
+
+    if stale.is_empty() {
+        rep.pass("Docs indexes name only existing agents and skills");
+    } else {
+        rep.fail_findings(
+            "Docs indexes list agents or skills that do not exist:",
+            stale,
+        );
+    }
+
+    Ok(())
+}
+
+/// "seven flows", "7 flows", "The Seven Flows"
+static FLOW_COUNT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(one|two|three|four|five|six|seven|eight|nine|ten|eleven|twelve|[0-9]+)\s+flows\b",
+    )
+    .expect("valid regex")
+});
+
+const NUMBER_WORDS: [&str; 12] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+    "twelve",
+];
+
+/// Check 76: Flow counts stated in README.md and CLAUDE.md match the pack's
+/// flow commands.
+fn check_flow_counts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    if cx.ctx.docs_root.is_none() {
+        rep.pass("No docs root given; flow counts not checked");
+        return Ok(());
+    }
+
+    let numbers: BTreeSet<u32> = cx.flows().flows.iter().map(|f| f.number).collect();
+    let actual = numbers.len();
+
+    let mut wrong = Vec::new();
+    for name in ["README.md", "CLAUDE.md"] {
+        let path = cx.ctx.repo_root.join(name);
+        if !cx.ctx.is_file(&path) {
+            continue;
+        }
+        let md = cx.ctx.markdown(&path)?;
+        for (line, text) in md.lines(Scope::Prose) {
+            for caps in FLOW_COUNT.captures_iter(text) {
+                let stated = caps[1].to_lowercase();
+                let count = NUMBER_WORDS
+                    .iter()
+                    .position(|w| *w == stated)
+                    .map(|i| i + 1)
+                    .or_else(|| stated.parse().ok());
+                if count != Some(actual) {
+                    let m = caps.get(0).expect("whole match");
+                    wrong.push(Finding::new(
+                        Location::file(cx.ctx.rel(&path))
+                            .with_line(line)
+                            .with_column(text[..m.start()].chars().count() + 1),
+                        format!(
+                            "\"{}\", but the pack has {actual} flow commands",
+                            m.as_str()
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    if wrong.is_empty() {
+        rep.pass(format!(
+            "Flow counts in README.md and CLAUDE.md match the {actual} flow commands"
+        ));
+    } else {
+        rep.fail_findings("Stated flow counts do not match the pack:", wrong);
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// GENERATED DOCS (pack-check docs generate)
+// =============================================================================
+//
+// The inverse of checks 74-76: render the reference tables from the pack and
+// write them between markers, so the tables are never edited by hand:
+//
+//   <!-- pack-check-generated-begin agents -->
+//   ...replaced on every run...
+//   <!-- pack-check-generated-end agents -->
+//
+// Prose outside the markers is left alone. The tables use the same columns
+// the checks read (`Agent`, `Skill`, `Description`), so generated docs pass them.
+
+static GENERATED_MARKER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*<!--\s*pack-check-generated-(begin|end)\s+([a-z-]+)\s*-->\s*$")
+        .expect("valid regex")
+});
+
+/// `(section, file under reference/, title for a new file)`
+const GENERATED: [(&str, &str, &str); 4] = [
+    ("agents", AGENTS_INDEX, "Agents Index"),
+    ("skills", SKILLS_INDEX, "Skills Index"),
+    ("agent-matrix", "agent-matrix.md", "Agent Capability Matrix"),
+    ("routing", "routing-table.md", "Routing Table"),
+];
+
+/// The rewrites that bring `<docs_root>/reference/` up to date with the pack;
+/// empty when every generated section is current.
+///
+/// A missing file is created with just its generated section. An existing
+/// file without the section's markers is an error: the section has to be
+/// placed by hand once, so it doesn't duplicate a hand-written table.
+pub fn generate_docs(cx: &CheckCtx, docs_root: &Path) -> anyhow::Result<Vec<FileFix>> {
+    let graph = HandoffGraph::build(cx).context("build handoff graph")?;
+    let pack = PackDocs::load(cx, &graph)?;
+
+    let mut fixes = Vec::new();
+    for (section, file, title) in GENERATED {
+        let body = match section {
+            "agents" => pack.agents_table(),
+            "skills" => pack.skills_table(),
+            "agent-matrix" => pack.flow_matrix(),
+            _ => pack.routing_tables(),
+        };
+
+        let path = docs_root.join("reference").join(file);
+        let rel = cx.ctx.rel(&path);
+        let original = if cx.ctx.is_file(&path) {
+            cx.ctx.read_utf8(&path)?.to_string()
+        } else {
+            String::new()
+        };
+        let fixed = if original.is_empty() {
+            format!(
+                "# {title}\n\n{}\n",
+                splice_section("", section, &body)?.unwrap_or_default()
+            )
+        } else {
+            splice_section(&original, section, &body)
+                .with_context(|| format!("update {rel}"))?
+                .with_context(|| {
+                    format!(
+                        "{rel} has no generated section; add \
+                         `<!-- pack-check-generated-begin {section} -->` and \
+                         `<!-- pack-check-generated-end {section} -->` where it belongs"
+                    )
+                })?
+        };
+
+        if fixed != original {
+            fixes.push(FileFix {
+                path,
+                rel,
+                original,
+                fixed,
+                edits: Vec::new(),
+            });
+        }
+    }
+
+    Ok(fixes)
+}
+
+/// `content` with the lines between `section`'s markers replaced by `body`;
+/// `None` if it has no begin marker. An empty `content` yields just the
+/// markers around `body`.
+fn splice_section(content: &str, section: &str, body: &str) -> anyhow::Result<Option<String>> {
+    let begin = format!("<!-- pack-check-generated-begin {section} -->");
+    let end = format!("<!-- pack-check-generated-end {section} -->");
+    if content.is_empty() {
+        return Ok(Some(format!("{begin}\n\n{body}\n{end}")));
+    }
+
+    let marker = |line: &str, kind: &str| {
+        GENERATED_MARKER
+            .captures(line)
+            .is_some_and(|c| &c[1] == kind && &c[2] == section)
+    };
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let Some(start) = lines.iter().position(|l| marker(l, "begin")) else {
+        return Ok(None);
+    };
+    let Some(stop) = lines[start..].iter().position(|l| marker(l, "end")) else {
+        bail!("`{begin}` at line {} is never closed by `{end}`", start + 1);
+    };
+
+    let mut out: String = lines[..=start].concat();
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push('\n');
+    out.push_str(body);
+    out.push('\n');
+    out.push_str(&lines[start + stop..].concat());
+    Ok(Some(out))
+}
+
+/// What the generated tables are rendered from.
+struct PackDocs<'a> {
+    cx: &'a CheckCtx<'a>,
+    graph: &'a HandoffGraph,
+    flows: Vec<Flow>,
+    /// Agent name → frontmatter (description, model, color).
+    agents: BTreeMap<String, [Option<String>; 3]>,
+    /// Skill name → frontmatter description.
+    skills: BTreeMap<String, Option<String>>,
+    /// Skill name → agents whose prompts reference it.
+    skill_users: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl<'a> PackDocs<'a> {
+    fn load(cx: &'a CheckCtx<'a>, graph: &'a HandoffGraph) -> anyhow::Result<Self> {
+        let [(_, agent_files), (_, skill_files)] = pack_entries(cx);
+
+        let mut agents = BTreeMap::new();
+        let mut skill_users: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for (name, path) in &agent_files {
+            let md = cx.ctx.markdown(path)?;
+            let fields = md
+                .frontmatter
+                .as_ref()
+                .and_then(|fm| Fields::parse(fm).ok());
+            let field = |key| fields.as_ref().and_then(|f| f.get(key)?.text());
+            agents.insert(
+                name.clone(),
+                [field("description"), field("model"), field("color")],
+            );
+            for (_, skill) in control_plane::skill_references(&md) {
+                skill_users.entry(skill).or_default().insert(name.clone());
+            }
+        }
+
+        let mut skills = BTreeMap::new();
+        for (name, path) in &skill_files {
+            skills.insert(name.clone(), frontmatter_description(cx, path)?);
+        }
+
+        Ok(Self {
+            cx,
+            graph,
+            flows: cx.flows().flows,
+            agents,
+            skills,
+            skill_users,
+        })
+    }
+
+    /// The agents `flow` invokes, in the order its command first calls them.
+    fn sequence(&self, flow: &Flow) -> Vec<&str> {
+        self.graph.invoked_by(&flow.stem())
+    }
+
+    /// Contract roles: `critic`, `gate`, `cleanup`, `gh`.
+    fn roles(&self, agent: &str) -> Vec<&'static str> {
+        let c = self.cx.c;
+        let has = |list: &[String]| list.iter().any(|a| a == agent);
+        let mut roles = Vec::new();
+        if has(&c.critics) {
+            roles.push("critic");
+        }
+        if has(&c.gate_agents) {
+            roles.push("gate");
+        }
+        if c.cleanup_agents.iter().any(|(a, _)| a == agent) {
+            roles.push("cleanup");
+        }
+        if has(&c.gh_agents) {
+            roles.push("gh");
+        }
+        roles
+    }
+
+    fn agents_table(&self) -> String {
+        let mut out = format!(
+            "{} agent{} in `.claude/agents/`.\n\n\
+             | Agent | Description | Model | Color | Role | Flows |\n\
+             |-------|-------------|-------|-------|------|-------|\n",
+            self.agents.len(),
+            if self.agents.len() == 1 { "" } else { "s" }
+        );
+        for (name, [description, model, color]) in &self.agents {
+            let flows: Vec<String> = self
+                .flows
+                .iter()
+                .filter(|f| self.sequence(f).contains(&name.as_str()))
+                .map(|f| f.number.to_string())
+                .collect();
+            out.push_str(&format!(
+                "| `{name}` | {} | {} | {} | {} | {} |\n",
+                cell(description.as_deref().unwrap_or("")),
+                cell(model.as_deref().unwrap_or("")),
+                cell(color.as_deref().unwrap_or("")),
+                self.roles(name).join(", "),
+                flows.join(", "),
+            ));
+        }
+        out
+    }
+
+    fn skills_table(&self) -> String {
+        let mut out = format!(
+            "{} skill{} in `.claude/skills/`.\n\n\
+             | Skill | Description | Used by |\n\
+             |-------|-------------|---------|\n",
+            self.skills.len(),
+            if self.skills.len() == 1 { "" } else { "s" }
+        );
+        for (name, description) in &self.skills {
+            let users: Vec<String> = self
+                .skill_users
+                .get(name)
+                .into_iter()
+                .flatten()
+                .map(|a| format!("`{a}`"))
+                .collect();
+            out.push_str(&format!(
+                "| `{name}` | {} | {} |\n",
+                cell(description.as_deref().unwrap_or("")),
+                users.join(", "),
+            ));
+        }
+        out
+    }
+
+    /// Agent × flow table (cells are the agent's step in the flow), then each
+    /// flow's sequence.
+    fn flow_matrix(&self) -> String {
+        let sequences: Vec<(&Flow, Vec<&str>)> =
+            self.flows.iter().map(|f| (f, self.sequence(f))).collect();
+
+        let mut rows: Vec<&str> = Vec::new();
+        for (_, seq) in &sequences {
+            for agent in seq {
+                if !rows.contains(agent) {
+                    rows.push(agent);
+                }
+            }
+        }
+
+        let mut out = String::from("| Agent |");
+        for (flow, _) in &sequences {
+            out.push_str(&format!(" {} {} |", flow.number, flow.name));
+        }
+        out.push_str("\n|-------|");
+        out.push_str(&"---|".repeat(sequences.len()));
+        out.push('\n');
+        for agent in &rows {
+            out.push_str(&format!("| `{agent}` |"));
+            for (_, seq) in &sequences {
+                match seq.iter().position(|a| a == agent) {
+                    Some(step) => out.push_str(&format!(" {} |", step + 1)),
+                    None => out.push_str(" |"),
+                }
+            }
+            out.push('\n');
+        }
+
+        out.push_str("\nFlow sequences:\n\n");
+        for (flow, seq) in &sequences {
+            let agents: Vec<String> = seq.iter().map(|a| format!("`{a}`")).collect();
+            out.push_str(&format!(
+                "{}. **{}** (`{}`): {}\n",
+                flow.number,
+                flow.name,
+                flow.stem(),
+                agents.join(" → ")
+            ));
+        }
+
+        let idle: Vec<String> = self
+            .agents
+            .keys()
+            .filter(|a| !rows.contains(&a.as_str()))
+            .map(|a| format!("`{a}`"))
+            .collect();
+        if !idle.is_empty() {
+            out.push_str(&format!(
+                "\nNot invoked by a flow command: {}.\n",
+                idle.join(", ")
+            ));
+        }
+        out
+    }
+
+    /// Each agent's routing targets, then each flow's cleanup agent and receipt.
+    fn routing_tables(&self) -> String {
+        let mut routes: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for edge in self
+            .graph
+            .edges
+            .iter()
+            .filter(|e| e.kind == EdgeKind::Routes)
+        {
+            let target = edge.to.trim_start_matches("agent:");
+            let missing = self.graph.get(&edge.to).is_some_and(|n| n.missing);
+            routes
+                .entry(edge.from.trim_start_matches("agent:"))
+                .or_default()
+                .push(if missing {
+                    format!("`{target}` (not an agent)")
+                } else {
+                    format!("`{target}`")
+                });
+        }
+
+        let mut out = String::from("| Agent | Routes to |\n|-------|-----------|\n");
+        for (agent, targets) in &routes {
+            out.push_str(&format!("| `{agent}` | {} |\n", targets.join(", ")));
+        }
+
+        out.push_str("\n| Flow | Cleanup agent | Receipt |\n|------|---------------|---------|\n");
+        for flow in &self.flows {
+            let (agent, receipt) = match &flow.cleanup {
+                Some(c) => (format!("`{}`", c.agent), format!("`{}`", c.receipt)),
+                None => (String::new(), String::new()),
+            };
+            out.push_str(&format!(
+                "| {} {} | {agent} | {receipt} |\n",
+                flow.number, flow.name
+            ));
+        }
+        out
+    }
+}
+
+/// Text as one table cell: a single line, pipes escaped.
+fn cell(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    /// A pack with agents `code-critic` and `test-author`, skill `test-runner`,
+    /// two flow commands and indexes that match it.
+    fn create_pack() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            ".claude/agents/code-critic.md",
+            "---\nname: code-critic\ndescription: Harsh review of code vs design.\n---\n\n# Code Critic\n",
+        );
+        write(
+            root,
+            ".claude/agents/test-author.md",
+            "---\nname: test-author\ndescription: Write tests for the change.\n---\n\n# Test Author\n",
+        );
+        write(
+            root,
+            ".claude/skills/test-runner/SKILL.md",
+            "---\nname: test-runner\ndescription: Run the test suite.\n---\n\n# Test Runner\n",
+        );
+        write(root, ".claude/commands/flow-1-signal.md", "# Flow 1\n");
+        write(root, ".claude/commands/flow-2-plan.md", "# Flow 2\n");
+        write(
+            root,
+            "docs/reference/agents-index.md",
+            "# Agents\n\n\
+             | Agent | Description |\n\
+             |-------|-------------|\n\
+             | `code-critic` | Harsh review of code vs design → `code_critique.md` |\n\
+             | `test-author` | Write tests for the change |\n\
+             | **Total** | 2 agents |\n",
+        );
+        write(
+            root,
+            "docs/reference/skills-index.md",
+            "# Skills\n\n\
+             | Skill | Used by |\n\
+             |-------|---------|\n\
+             | [test-runner](#test-runner) | test-author |\n\n\
+             ## test-runner\n\n\
+             **Description:** Run the test suite.\n",
+        );
+        write(
+            root,
+            "README.md",
+            "# Pack\n\nTwo flows take work from signal to plan.\n",
+        );
+        dir
+    }
+
+    fn with_cx<T>(root: &Path, f: impl FnOnce(&CheckCtx) -> T) -> T {
+        use crate::contracts::Contracts;
+        use crate::contracts::test_utils::REGEXES;
+        use crate::ctx::Ctx;
+        use crate::inventory::Inventory;
+
+        let mut ctx = Ctx::discover(Some(root.to_path_buf())).unwrap();
+        ctx.docs_root = Some(root.join("docs"));
+        let inv = Inventory::from_ctx(&ctx).unwrap();
+        let contracts = Contracts::default();
+        f(&CheckCtx {
+            ctx: &ctx,
+            inv: &inv,
+            re: &REGEXES,
+            c: &contracts,
+        })
+    }
+
+    fn run(root: &Path, check: fn(&CheckCtx, &mut Reporter) -> anyhow::Result<()>) -> Vec<String> {
+        use crate::reporter::Level;
+
+        let mut rep = Reporter::silent();
+        with_cx(root, |cx| check(cx, &mut rep)).unwrap();
+        rep.diagnostics()
+            .iter()
+            .filter(|d| d.level != Level::Pass)
+            .map(|d| {
+                let level = if d.level == Level::Fail {
+                    "fail"
+                } else {
+                    "warn"
+                };
+                let loc = d.location.as_ref().unwrap();
+                match loc.line {
+                    Some(line) => format!("{level} {}:{line}: {}", loc.path, d.message),
+                    None => format!("{level} {}: {}", loc.path, d.message),
+                }
+            })
+            .collect()
+    }
+
+    /// Test: Indexes that match the pack pass all three checks.
+    #[test]
+    fn test_matching_docs_pass() {
+        let dir = create_pack();
+        for check in checks() {
+            assert_eq!(
+                run(dir.path(), check.run),
+                Vec::<String>::new(),
+                "check {}",
+                check.id
+            );
+        }
+    }
+
+    /// Test: An unlisted agent fails; a drifted description warns at its row.
+    #[test]
+    fn test_indexes_cover_pack() {
+        let dir = create_pack();
+        write(
+            dir.path(),
+            ".claude/agents/new-agent.md",
+            "---\nname: new-agent\ndescription: Brand new.\n---\n",
+        );
+        write(
+            dir.path(),
+            ".claude/agents/test-author.md",
+            "---\nname: test-author\ndescription: Author BDD scenarios.\n---\n",
+        );
+
+        assert_eq!(
+            run(dir.path(), check_indexes_cover_pack),
+            vec![
+                "fail docs/reference/agents-index.md: Docs indexes are missing pack entries: \
+                 agent `new-agent` (.claude/agents/new-agent.md) is not listed",
+                "warn docs/reference/agents-index.md:6: Docs index descriptions drifted from the pack: \
+                 `test-author`: \"Write tests for the change\" does not match its frontmatter \
+                 description \"Author BDD scenarios.\"",
+            ]
+        );
+    }
+
+    /// Test: A missing index file fails rather than passing vacuously.
+    #[test]
+    fn test_missing_index_fails() {
+        let dir = create_pack();
+        std::fs::remove_file(dir.path().join("docs/reference/skills-index.md")).unwrap();
+
+        assert_eq!(
+            run(dir.path(), check_indexes_cover_pack),
+            vec![
+                "fail docs/reference/skills-index.md: Docs indexes are missing pack entries: \
+                 skill index does not exist"
+            ]
+        );
+    }
+
+    /// Test: Deleted agents and skills fail at every index row naming them.
+    #[test]
+    fn test_stale_entries() {
+        let dir = create_pack();
+        std::fs::remove_file(dir.path().join(".claude/agents/code-critic.md")).unwrap();
+        std::fs::remove_dir_all(dir.path().join(".claude/skills/test-runner")).unwrap();
+        write(
+            dir.path(),
+            "docs/reference/routing-table.md",
+            "| Agent | Routes to |\n|---|---|\n| code-critic | test-author |\n| Requesting agent | any |\n",
+        );
+
+        assert_eq!(
+            run(dir.path(), check_indexes_have_no_stale_entries),
+            vec![
+                "fail docs/reference/agents-index.md:5: Docs indexes list agents or skills that \
+                 do not exist: `code-critic` is not an agent in .claude/agents/",
+                "fail docs/reference/routing-table.md:3: Docs indexes list agents or skills that \
+                 do not exist: `code-critic` is not an agent in .claude/agents/",
+                "fail docs/reference/skills-index.md:5: Docs indexes list agents or skills that \
+                 do not exist: `test-runner` is not a skill in .claude/skills/",
+                "fail docs/reference/skills-index.md:7: Docs indexes list agents or skills that \
+                 do not exist: `test-runner` is not a skill in .claude/skills/",
+            ]
+        );
+    }
+
+    /// Test: Stated flow counts must equal the number of flows; code is ignored.
+    #[test]
+    fn test_flow_counts() {
+        let dir = create_pack();
+        write(
+            dir.path(),
+            "CLAUDE.md",
+            "## The 7 Flows\n\n```\nthree flows\n```\n\nBoth of the two flows.\n",
+        );
+
+        assert_eq!(
+            run(dir.path(), check_flow_counts),
+            vec![
+                "fail CLAUDE.md:1: Stated flow counts do not match the pack: \
+                 \"7 Flows\", but the pack has 2 flow commands"
+            ]
+        );
+    }
+
+    /// Test: Index descriptions match on prefix, ignoring case, emphasis and outputs.
+    #[test]
+    fn test_descriptions_match() {
+        assert!(descriptions_match(
+            "Run the **tests** → `out.md`",
+            "run the tests."
+        ));
+        assert!(descriptions_match(
+            "Harsh review",
+            "Harsh review of code vs design."
+        ));
+        assert!(!descriptions_match("Write code", "Write tests"));
+        assert!(!descriptions_match("", "Write tests"));
+    }
+
+    /// `create_pack` with flow commands that call its agents and a route.
+    fn create_flowing_pack() -> TempDir {
+        let dir = create_pack();
+        let root = dir.path();
+        write(
+            root,
+            ".claude/commands/flow-1-signal.md",
+            "# Flow 1\n\n- [ ] test-author writes tests\n- [ ] code-critic reviews them\n",
+        );
+        write(
+            root,
+            ".claude/commands/flow-2-plan.md",
+            "# Flow 2\n\n1) code-critic\n",
+        );
+        write(
+            root,
+            ".claude/agents/code-critic.md",
+            "---\nname: code-critic\ndescription: Harsh review of code vs design.\nmodel: inherit\n\
+             color: red\n---\n\n# Code Critic\n\nUse the `test-runner` skill.\n\n\
+             route_to_agent: test-author\n",
+        );
+        dir
+    }
+
+    fn generated(root: &Path) -> BTreeMap<String, String> {
+        let fixes = with_cx(root, |cx| generate_docs(cx, &root.join("docs"))).unwrap();
+        fixes.into_iter().map(|f| (f.rel, f.fixed)).collect()
+    }
+
+    /// The text between `section`'s markers.
+    fn section(content: &str, section: &str) -> String {
+        let begin = format!("<!-- pack-check-generated-begin {section} -->\n\n");
+        let start = content.find(&begin).unwrap() + begin.len();
+        let end = content
+            .find(&format!("\n<!-- pack-check-generated-end {section} -->"))
+            .unwrap();
+        content[start..end].to_string()
+    }
+
+    /// Test: Generated tables come from frontmatter, flow sequences, routes
+    /// and the cleanup contract.
+    #[test]
+    fn test_generate_docs_tables() {
+        let dir = create_flowing_pack();
+        std::fs::remove_dir_all(dir.path().join("docs")).unwrap();
+        let docs = generated(dir.path());
+
+        assert_eq!(
+            section(&docs["docs/reference/agents-index.md"], "agents"),
+            "2 agents in `.claude/agents/`.\n\n\
+             | Agent | Description | Model | Color | Role | Flows |\n\
+             |-------|-------------|-------|-------|------|-------|\n\
+             | `code-critic` | Harsh review of code vs design. | inherit | red | critic | 1, 2 |\n\
+             | `test-author` | Write tests for the change. |  |  |  | 1 |\n"
+        );
+        assert_eq!(
+            section(&docs["docs/reference/skills-index.md"], "skills"),
+            "1 skill in `.claude/skills/`.\n\n\
+             | Skill | Description | Used by |\n\
+             |-------|-------------|---------|\n\
+             | `test-runner` | Run the test suite. | `code-critic` |\n"
+        );
+        assert_eq!(
+            section(&docs["docs/reference/agent-matrix.md"], "agent-matrix"),
+            "| Agent | 1 signal | 2 plan |\n\
+             |-------|---|---|\n\
+             | `test-author` | 1 | |\n\
+             | `code-critic` | 2 | 1 |\n\
+             \n\
+             Flow sequences:\n\n\
+             1. **signal** (`flow-1-signal`): `test-author` → `code-critic`\n\
+             2. **plan** (`flow-2-plan`): `code-critic`\n"
+        );
+        assert_eq!(
+            section(&docs["docs/reference/routing-table.md"], "routing"),
+            "| Agent | Routes to |\n\
+             |-------|-----------|\n\
+             | `code-critic` | `test-author` |\n\
+             \n\
+             | Flow | Cleanup agent | Receipt |\n\
+             |------|---------------|---------|\n\
+             | 1 signal | `signal-cleanup` | `signal_receipt.json` |\n\
+             | 2 plan | `plan-cleanup` | `plan_receipt.json` |\n"
+        );
+    }
+
+    /// Test: Only the marked section is rewritten; generated docs pass the
+    /// docs checks, and a second run has nothing to change.
+    #[test]
+    fn test_generate_docs_round_trip() {
+        let dir = create_flowing_pack();
+        let root = dir.path();
+        write(
+            root,
+            "docs/reference/agents-index.md",
+            "# Agents\n\nHand-written intro.\n\n\
+             <!-- pack-check-generated-begin agents -->\nstale\n<!-- pack-check-generated-end agents -->\n\n\
+             ## See Also\n",
+        );
+        std::fs::remove_file(root.join("docs/reference/skills-index.md")).unwrap();
+
+        for (rel, fixed) in generated(root) {
+            write(root, &rel, &fixed);
+        }
+
+        let index = std::fs::read_to_string(root.join("docs/reference/agents-index.md")).unwrap();
+        assert!(index.starts_with("# Agents\n\nHand-written intro.\n\n<!-- pack-check-generated-begin agents -->\n\n2 agents"));
+        assert!(index.ends_with("<!-- pack-check-generated-end agents -->\n\n## See Also\n"));
+        let skills = std::fs::read_to_string(root.join("docs/reference/skills-index.md")).unwrap();
+        assert!(
+            skills.starts_with("# Skills Index\n\n<!-- pack-check-generated-begin skills -->\n")
+        );
+
+        assert!(generated(root).is_empty());
+        for check in checks() {
+            assert_eq!(
+                run(root, check.run),
+                Vec::<String>::new(),
+                "check {}",
+                check.id
+            );
+        }
+    }
+
+    /// Test: Existing docs without markers, or with an unclosed one, are errors.
+    #[test]
+    fn test_generate_docs_needs_markers() {
+        let dir = create_flowing_pack();
+        let root = dir.path();
+        let err = with_cx(root, |cx| generate_docs(cx, &root.join("docs"))).unwrap_err();
+        assert!(
+            format!("{err:#}").contains(
+                "docs/reference/agents-index.md has no generated section; add \
+                 `<!-- pack-check-generated-begin agents -->`"
+            ),
+            "{err:#}"
+        );
+
+        let unclosed = splice_section(
+            "a\n<!-- pack-check-generated-begin agents -->\nb\n",
+            "agents",
+            "x",
+        );
+        assert_eq!(
+            format!("{:#}", unclosed.unwrap_err()),
+            "`<!-- pack-check-generated-begin agents -->` at line 2 is never closed by \
+             `<!-- pack-check-generated-end agents -->`"
+        );
+    }
+
+    /// Test: This repo's own reference docs carry every generated section.
+    #[test]
+    fn test_repo_docs_have_markers() {
+        let reference = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../docs/reference");
+        for (section, file, _) in GENERATED {
+            let content = std::fs::read_to_string(reference.join(file)).unwrap();
+            let spliced = splice_section(&content, section, "x\n").unwrap();
+            assert!(spliced.is_some(), "{file} has no {section} section");
+        }
+    }
+}
@@ -1,116 +1,277 @@
 //! Drift checks: banned patterns, old taxonomy, raw git, shim enforcement, GH hygiene.
 //!
-//! Checks: 7, 8, 14, 23, 30, 38, 39, 40, 42, 45, 46, 47, 48, 49, 50, 52, 53
+//! Checks: 7, 8, 14, 23, 30, 38, 39, 40, 42, 45, 46, 47, 48, 50, 52, 53, 62, 64, 73
 
 use std::path::PathBuf;
+use std::sync::LazyLock;
 
-use crate::reporter::Reporter;
-use crate::util::{LineMatch, find_files_containing_recursive, find_matches_regex_recursive};
+use regex::Regex;
 
-use super::{CheckCtx, CheckSpec};
+use crate::contracts_file;
+use crate::markdown::Scope;
+use crate::reporter::{Finding, Location, Reporter};
+use crate::shim;
+use crate::util::{
+    LineMatch, find_lines_containing_recursive, find_lines_containing_scoped,
+    find_matches_regex_recursive, find_matches_regex_scoped, line_col_at, list_files_recursive,
+};
+
+use super::{Category, CheckCtx, CheckSpec, FixSpec, Rewrite};
 
 pub fn checks() -> Vec<CheckSpec> {
     vec![
         CheckSpec {
             id: 7,
             title: "Checking for old taxonomy patterns...",
+            category: Category::Drift,
+            rationale: "No old taxonomy (FR-* instead of REQ-*).",
             run: check_old_taxonomy,
         },
         CheckSpec {
             id: 8,
             title: "Checking for removed/deprecated concepts...",
+            category: Category::Drift,
+            rationale: "No references to removed concepts.\n\
+                \n\
+                Prose and code both count; quoted counter-examples (`> ...`) and frontmatter don't.",
             run: check_banned_patterns,
         },
         CheckSpec {
             id: 14,
             title: "Checking for RUN_BASE alias (should use explicit paths)...",
+            category: Category::Drift,
+            rationale: "No RUN_BASE alias in flows/agents.",
             run: check_run_base_alias,
         },
         CheckSpec {
             id: 23,
             title: "Checking typed NFR ID contract...",
+            category: Category::Drift,
+            rationale: "Typed NFR ID contract.",
             run: check_typed_nfr,
         },
         CheckSpec {
             id: 30,
             title: "Checking for flow-specific action enum drift...",
+            category: Category::Drift,
+            rationale: "No flow-specific action enums.",
             run: check_flow_specific_actions,
         },
         CheckSpec {
             id: 38,
             title: "Checking for 'operation: ensure_branch' drift in flow commands...",
+            category: Category::Drift,
+            rationale: "Reject operation: ensure_branch in flow commands.",
             run: check_ensure_branch_op,
         },
         CheckSpec {
             id: 39,
             title: "Checking for raw git commands in flow commands...",
+            category: Category::Drift,
+            rationale: "No raw git commands in flow commands.\n\
+                \n\
+                Only shell blocks are commands the orchestrator would run; prose that names\n\
+                a git command (e.g. to explain what repo-operator does) is not a violation.",
             run: check_raw_git,
         },
         CheckSpec {
             id: 40,
             title: "Checking for 'See CLAUDE.md > ...' behavioral substitution...",
+            category: Category::Drift,
+            rationale: "No CLAUDE.md behavioral substitution.\n\
+                \n\
+                The substitution is an instruction, so only prose counts.",
             run: check_claude_substitution,
         },
         CheckSpec {
             id: 42,
             title: "Checking Flow 6 does not introduce alternate issue draft filenames...",
+            category: Category::Drift,
+            rationale: "No alternate issue draft filenames.",
             run: check_issue_drafts,
         },
         CheckSpec {
             id: 45,
             title: "Checking cleanup agents use demoswarm shim (no bespoke pipelines)...",
+            category: Category::Drift,
+            rationale: "Cleanup agents must use demoswarm shim, not bespoke pipelines.\n\
+                \n\
+                This enforces the pack contract that cleanup agents use the standardized\n\
+                `demoswarm.sh` shim for mechanical counts and extraction, not inline\n\
+                `grep|sed|awk|jq` pipelines.",
             run: check_cleanup_uses_demoswarm_shim,
         },
         CheckSpec {
             id: 46,
             title: "Checking skill ownership boundaries...",
+            category: Category::Drift,
+            rationale: "Skill ownership boundaries.\n\
+                \n\
+                Enforces that restricted skill commands are only used by allowed agents:\n\
+                - `index upsert-status` → cleanup + run-prep agents only\n\
+                - `secrets scan|redact` → secrets-sanitizer only\n\
+                - `openq next-id|append` → clarifier only",
             run: check_skill_ownership,
         },
         CheckSpec {
             id: 47,
             title: "Checking shim line-continuation bypass...",
+            category: Category::Drift,
+            rationale: "No line-continuation bypass after demoswarm.sh.\n\
+                \n\
+                Prevents clever/accidental line-wrap from bypassing ownership checks:\n\
+                ```text\n\
+                bash .claude/scripts/demoswarm.sh \\\n\
+                \x20\x20secrets scan ...  # This splits the command, hiding the subcommand\n\
+                ```",
             run: check_shim_line_continuation,
         },
         CheckSpec {
             id: 48,
             title: "Checking direct demoswarm invocation (must use shim)...",
+            category: Category::Drift,
+            rationale: "No direct demoswarm invocation (must use shim).\n\
+                \n\
+                Ensures `demoswarm` is not invoked directly (bypassing the shim):\n\
+                ```text\n\
+                demoswarm count ...        # BAD: bypasses shim\n\
+                bash .claude/scripts/demoswarm.sh count ...  # GOOD: uses shim\n\
+                ```\n\
+                \n\
+                Exceptions:\n\
+                - `tools/` directory (the CLI source itself)\n\
+                - `docs/reference/` (documentation about the CLI)",
             run: check_direct_demoswarm_invocation,
         },
         CheckSpec {
             id: 50,
             title: "Checking GH agents use heredoc (not --body-file) and no forbidden patterns...",
+            category: Category::Drift,
+            rationale: "GH agents must use heredoc pattern (not --body-file) and avoid forbidden patterns.\n\
+                \n\
+                This prevents the class of failures where:\n\
+                - Windows paths in --body-file are misinterpreted\n\
+                - Temp file paths leak into commands\n\
+                - Absolute paths or placeholders get posted\n\
+                \n\
+                Requirements for GH posting agents:\n\
+                1. Must NOT use --body-file (fails on Windows paths)\n\
+                2. Must use heredoc pattern: -f body=\"$(cat <<'EOF' ... EOF)\"\n\
+                3. Must NOT contain forbidden patterns (absolute paths, temp dirs, placeholders)\n\
+                \n\
+                Forbidden patterns are only looked for in prose; code blocks are examples.",
             run: check_gh_body_hygiene,
         },
         CheckSpec {
             id: 52,
             title: "Checking flow commands do not contain demoswarm.sh or skill CLI syntax...",
+            category: Category::Drift,
+            rationale: "Flow boundary enforcement - no demoswarm.sh or skill CLI syntax in flow commands.\n\
+                \n\
+                Enforces the three-tier ownership model:\n\
+                - Flow commands -> delegate to agents\n\
+                - Agent docs -> use skills\n\
+                - Skill docs -> implement CLI operations\n\
+                \n\
+                Flow commands should NOT contain:\n\
+                - \"demoswarm.sh\" (direct shim invocation)\n\
+                - Skill CLI subcommands (count, ms, yaml, index, receipt, receipts, openapi, line, inv, time, openq, secrets)\n\
+                \n\
+                This prevents flow commands from bypassing the agent layer and directly using skill-layer CLI.",
             run: check_flow_boundary_enforcement,
         },
         CheckSpec {
             id: 53,
             title: "Checking OpenQ QID patterns use canonical flow codes...",
+            category: Category::Drift,
+            rationale: "OpenQ prefix validation - QIDs must use canonical flow codes.\n\
+                \n\
+                Validates that QID patterns in `.runs/**/open_questions.md` files follow\n\
+                the canonical format: OQ-<FLOW>-<NNN>\n\
+                \n\
+                Where:\n\
+                - <FLOW> is one of: SIG, PLAN, BUILD, REVIEW, GATE, DEPLOY, WISDOM (full names preferred)\n\
+                - <NNN> is a three-digit zero-padded number (001-999)\n\
+                \n\
+                Reports warnings for:\n\
+                - Non-canonical flow codes (e.g., PLN instead of PLAN, BLD instead of BUILD)\n\
+                - Invalid numeric suffixes (not three digits)",
             run: check_openq_prefix_validation,
         },
         CheckSpec {
-            id: 54,
+            id: 62,
             title: "Checking agents using demoswarm.sh have Skills section...",
+            category: Category::Drift,
+            rationale: "Agents using demoswarm.sh must have a Skills section.\n\
+                \n\
+                Agents that invoke demoswarm.sh are expected to document which skills\n\
+                they use in a ## Skills (or ## Skill) section. This ensures that:\n\
+                - Skill dependencies are discoverable\n\
+                - Agents explicitly declare their tool usage",
             run: check_skills_section_required,
         },
+        CheckSpec {
+            id: 64,
+            title: "Checking demoswarm.sh invocations against the CLI grammar...",
+            category: Category::Drift,
+            rationale: "Every demoswarm.sh invocation parses with the real CLI grammar.\n\
+                \n\
+                Each `bash .claude/scripts/demoswarm.sh ...` call in agents and skills\n\
+                (continuation lines joined) is parsed with the `demoswarm` clap command\n\
+                tree, so unknown subcommands, misspelled or missing required flags, and\n\
+                removed options fail here instead of at runtime. See `shim` for how\n\
+                placeholders and usage synopses are handled.",
+            run: check_shim_invocation_grammar,
+        },
+        CheckSpec {
+            id: 73,
+            title: "Checking .claude/ is portable (no harness-specific references)...",
+            category: Category::Drift,
+            rationale: "`.claude/` holds no harness-specific references.\n\
+                \n\
+                The pack is copied into other repos as-is, so every file under `.claude/`\n\
+                (any extension, every line) is scanned for the `harness_patterns`\n\
+                contract and `swarm/` path segments. Lines carrying a\n\
+                `harness_exceptions` entry (example FR ids) are exempt, and so is the\n\
+                contracts file, which lists the patterns.",
+            run: check_portable_pack,
+        },
     ]
 }
 
-fn format_line_matches(cx: &CheckCtx, matches: Vec<LineMatch>) -> Vec<String> {
-    matches
-        .into_iter()
-        .map(|m| {
-            format!(
-                "{}:{}:{}",
-                cx.ctx.rel(&m.path),
-                m.line_no,
-                m.line.trim_end()
-            )
-        })
-        .collect()
+/// Rewrites for the drift checks whose correct form is known (`--fix`).
+pub fn fixers() -> Vec<FixSpec> {
+    vec![
+        FixSpec {
+            check_id: 7,
+            files: taxonomy_files,
+            rewrite: Rewrite::Line(fix_old_taxonomy),
+        },
+        FixSpec {
+            check_id: 47,
+            files: shim_files,
+            rewrite: Rewrite::JoinNext(is_shim_line_continuation),
+        },
+        FixSpec {
+            check_id: 48,
+            files: shim_files,
+            rewrite: Rewrite::Line(fix_direct_demoswarm_invocation),
+        },
+        FixSpec {
+            check_id: 53,
+            files: open_questions_files,
+            rewrite: Rewrite::Line(fix_openq_prefix),
+        },
+    ]
+}
+
+fn format_line_matches(cx: &CheckCtx, matches: Vec<LineMatch>) -> Vec<Finding> {
+    matches.into_iter().map(|m| m.finding(cx.ctx)).collect()
+}
+
+/// A finding for one line of a file (column unknown).
+fn line_finding(cx: &CheckCtx, path: &std::path::Path, line_no: usize, text: String) -> Finding {
+    Finding::new(Location::file(cx.ctx.rel(path)).with_line(line_no), text)
 }
 
 /// Check 7: No old taxonomy (FR-* instead of REQ-*).
@@ -125,8 +286,10 @@ fn check_old_taxonomy(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
         .collect();
 
     if !fr_hits_filtered.is_empty() {
-        rep.fail("Found old FR-* taxonomy (use REQ-* / typed NFR-*):");
-        rep.indent_lines(format_line_matches(cx, fr_hits_filtered));
+        rep.fail_findings(
+            "Found old FR-* taxonomy (use REQ-* / typed NFR-*):",
+            format_line_matches(cx, fr_hits_filtered),
+        );
     } else {
         rep.pass("No old FR-* taxonomy found");
     }
@@ -134,8 +297,10 @@ fn check_old_taxonomy(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let old_bdd_hits =
         find_matches_regex_recursive(cx.ctx, &roots, &cx.re.old_bdd_tag, &["pack-check.sh"])?;
     if !old_bdd_hits.is_empty() {
-        rep.fail("Found old @FR- tags in pack files");
-        rep.indent_lines(format_line_matches(cx, old_bdd_hits));
+        rep.fail_findings(
+            "Found old @FR- tags in pack files",
+            format_line_matches(cx, old_bdd_hits),
+        );
     } else {
         rep.pass("No old @FR- tags found");
     }
@@ -143,22 +308,44 @@ fn check_old_taxonomy(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn taxonomy_files(cx: &CheckCtx) -> Vec<PathBuf> {
+    let roots = [cx.ctx.agents_dir.clone(), cx.ctx.commands_dir.clone()];
+    list_files_recursive(cx.ctx, &roots, &["pack-check.sh"])
+}
+
+/// Fix for check 7: FR-NNN -> REQ-NNN, @FR- -> @REQ-. Lines mentioning NFR-
+/// keep their FR ids, as the check ignores them.
+fn fix_old_taxonomy(cx: &CheckCtx, line: &str) -> Option<String> {
+    let mut fixed = line.to_string();
+    if !line.contains("NFR-") {
+        fixed = cx
+            .re
+            .old_fr_id_rewrite
+            .replace_all(&fixed, "${1}REQ-${2}")
+            .into_owned();
+    }
+    fixed = fixed.replace("@FR-", "@REQ-");
+    (fixed != line).then_some(fixed)
+}
+
+/// Prose and code both count; quoted counter-examples (`> ...`) and frontmatter don't.
+const BANNED_PATTERNS_SCOPE: Scope = Scope::Both;
+
 /// Check 8: No references to removed concepts.
 fn check_banned_patterns(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
-    for pattern in cx.c.banned_patterns {
-        let matches = find_files_containing_recursive(
+    for pattern in &cx.c.banned_patterns {
+        let matches = find_lines_containing_scoped(
             cx.ctx,
             &cx.ctx.claude_dir,
             pattern,
             &["pack-check.sh"],
+            BANNED_PATTERNS_SCOPE,
         )?;
         if !matches.is_empty() {
-            rep.fail(format!("Found banned pattern '{pattern}' in:"));
-            let rels = matches
-                .into_iter()
-                .map(|p| cx.ctx.rel(&p))
-                .collect::<Vec<_>>();
-            rep.indent_lines(rels);
+            rep.fail_findings(
+                format!("Found banned pattern '{pattern}' in:"),
+                format_line_matches(cx, matches),
+            );
         } else {
             rep.pass(format!("No references to '{pattern}'"));
         }
@@ -169,13 +356,13 @@ fn check_banned_patterns(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()
 
 /// Check 14: No RUN_BASE alias in flows/agents.
 fn check_run_base_alias(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
-    let mut hits = find_files_containing_recursive(
+    let mut hits = find_lines_containing_recursive(
         cx.ctx,
         &cx.ctx.commands_dir,
         "RUN_BASE",
         &["pack-check.sh"],
     )?;
-    hits.extend(find_files_containing_recursive(
+    hits.extend(find_lines_containing_recursive(
         cx.ctx,
         &cx.ctx.agents_dir,
         "RUN_BASE",
@@ -183,9 +370,10 @@ fn check_run_base_alias(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()>
     )?);
 
     if !hits.is_empty() {
-        rep.fail("Found 'RUN_BASE' alias (use explicit .runs/<run-id>/ paths) in:");
-        let rels = hits.into_iter().map(|p| cx.ctx.rel(&p)).collect::<Vec<_>>();
-        rep.indent_lines(rels);
+        rep.fail_findings(
+            "Found 'RUN_BASE' alias (use explicit .runs/<run-id>/ paths) in:",
+            format_line_matches(cx, hits),
+        );
     } else {
         rep.pass("No RUN_BASE alias in flows/agents");
     }
@@ -200,19 +388,21 @@ fn check_typed_nfr(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let bare_nfr_matches =
         find_matches_regex_recursive(cx.ctx, &roots, &cx.re.bare_nfr_id, &["pack-check.sh"])?;
     if !bare_nfr_matches.is_empty() {
-        rep.fail("Found bare NFR-### patterns (should be NFR-<DOMAIN>-###) in:");
-        rep.indent_lines(format_line_matches(cx, bare_nfr_matches));
+        rep.fail_findings(
+            "Found bare NFR-### patterns (should be NFR-<DOMAIN>-###) in:",
+            format_line_matches(cx, bare_nfr_matches),
+        );
     } else {
         rep.pass("No bare NFR-### patterns found (all NFRs are typed)");
     }
 
-    let nfr_scale_hits = find_files_containing_recursive(
+    let nfr_scale_hits = find_lines_containing_recursive(
         cx.ctx,
         &cx.ctx.agents_dir,
         "NFR-SCALE-",
         &["pack-check.sh"],
     )?;
-    let mut nfr_scale_hits2 = find_files_containing_recursive(
+    let mut nfr_scale_hits2 = find_lines_containing_recursive(
         cx.ctx,
         &cx.ctx.commands_dir,
         "NFR-SCALE-",
@@ -222,12 +412,10 @@ fn check_typed_nfr(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     scale_all.append(&mut nfr_scale_hits2);
 
     if !scale_all.is_empty() {
-        rep.fail("Found deprecated NFR-SCALE-* (use NFR-PERF-* instead) in:");
-        let rels = scale_all
-            .into_iter()
-            .map(|p| cx.ctx.rel(&p))
-            .collect::<Vec<_>>();
-        rep.indent_lines(rels);
+        rep.fail_findings(
+            "Found deprecated NFR-SCALE-* (use NFR-PERF-* instead) in:",
+            format_line_matches(cx, scale_all),
+        );
     } else {
         rep.pass("No deprecated NFR-SCALE-* patterns found");
     }
@@ -239,20 +427,21 @@ fn check_typed_nfr(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
 fn check_flow_specific_actions(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let roots: [PathBuf; 2] = [cx.ctx.agents_dir.clone(), cx.ctx.commands_dir.clone()];
 
-    for pattern in cx.c.flow_specific_patterns {
+    for pattern in &cx.c.flow_specific_patterns {
         let matches =
-            find_files_containing_recursive(cx.ctx, &roots[0], pattern, &["pack-check.sh"])?;
+            find_lines_containing_recursive(cx.ctx, &roots[0], pattern, &["pack-check.sh"])?;
         let mut matches2 =
-            find_files_containing_recursive(cx.ctx, &roots[1], pattern, &["pack-check.sh"])?;
+            find_lines_containing_recursive(cx.ctx, &roots[1], pattern, &["pack-check.sh"])?;
         let mut all = matches;
         all.append(&mut matches2);
 
         if !all.is_empty() {
-            rep.fail(format!(
-                "Found flow-specific action '{pattern}' (use closed enum + route_to_flow) in:"
-            ));
-            let rels = all.into_iter().map(|p| cx.ctx.rel(&p)).collect::<Vec<_>>();
-            rep.indent_lines(rels);
+            rep.fail_findings(
+                format!(
+                    "Found flow-specific action '{pattern}' (use closed enum + route_to_flow) in:"
+                ),
+                format_line_matches(cx, all),
+            );
         } else {
             rep.pass(format!("No flow-specific '{pattern}'"));
         }
@@ -261,8 +450,10 @@ fn check_flow_specific_actions(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Res
     let domain_verdict_hits =
         find_matches_regex_recursive(cx.ctx, &roots, &cx.re.domain_verdict, &["pack-check.sh"])?;
     if !domain_verdict_hits.is_empty() {
-        rep.fail("Found domain verdict keywords in recommended_action (must stay closed enum):");
-        rep.indent_lines(format_line_matches(cx, domain_verdict_hits));
+        rep.fail_findings(
+            "Found domain verdict keywords in recommended_action (must stay closed enum):",
+            format_line_matches(cx, domain_verdict_hits),
+        );
     } else {
         rep.pass("No domain verdict keywords in recommended_action");
     }
@@ -280,8 +471,10 @@ fn check_ensure_branch_op(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<(
     )?;
 
     if !ensure_hits.is_empty() {
-        rep.fail("Found 'operation: ensure_branch' in flow commands (use task phrasing):");
-        rep.indent_lines(format_line_matches(cx, ensure_hits));
+        rep.fail_findings(
+            "Found 'operation: ensure_branch' in flow commands (use task phrasing):",
+            format_line_matches(cx, ensure_hits),
+        );
     } else {
         rep.pass("No 'operation: ensure_branch' in flow commands");
     }
@@ -289,35 +482,41 @@ fn check_ensure_branch_op(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<(
     Ok(())
 }
 
+/// Only shell blocks are commands the orchestrator would run; prose that names
+/// a git command (e.g. to explain what repo-operator does) is not a violation.
+const RAW_GIT_SCOPE: Scope = Scope::Shell;
+
 /// Check 39: No raw git commands in flow commands.
 fn check_raw_git(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let mut raw_git_found = 0usize;
 
-    for (needle, display) in cx.c.raw_git_patterns {
+    for (needle, display) in &cx.c.raw_git_patterns {
         let mut non_allowed = Vec::new();
 
         for cmd in &cx.inv.flow_cmd_files {
-            let content = cx.ctx.read_utf8(cmd)?;
-            for (idx, line) in content.lines().enumerate() {
-                if line.contains(needle)
+            let md = cx.ctx.markdown(cmd)?;
+            for (line_no, line) in md.lines(RAW_GIT_SCOPE) {
+                if let Some(col) = line.find(needle)
                     && !line.contains("repo-operator")
                     && !line.contains("(not `")
                 {
-                    non_allowed.push(format!(
-                        "{}:{}:{}",
-                        cx.ctx.rel(cmd),
-                        idx + 1,
-                        line.trim_end()
+                    non_allowed.push(Finding::new(
+                        Location::file(cx.ctx.rel(cmd))
+                            .with_line(line_no)
+                            .with_column(line[..col].chars().count() + 1),
+                        line.trim_end(),
                     ));
                 }
             }
         }
 
         if !non_allowed.is_empty() {
-            rep.fail(format!(
-                "Raw git pattern '{display}' found in flow commands (pack bug: flows must delegate to repo-operator):"
-            ));
-            rep.indent_lines(non_allowed.into_iter().take(3));
+            rep.fail_findings(
+                format!(
+                    "Raw git pattern '{display}' found in flow commands (pack bug: flows must delegate to repo-operator):"
+                ),
+                non_allowed.into_iter().take(3),
+            );
             raw_git_found += 1;
         }
     }
@@ -329,18 +528,24 @@ fn check_raw_git(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// The substitution is an instruction, so only prose counts.
+const CLAUDE_SUBSTITUTION_SCOPE: Scope = Scope::Prose;
+
 /// Check 40: No CLAUDE.md behavioral substitution.
 fn check_claude_substitution(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
-    let sub_hits = find_matches_regex_recursive(
+    let sub_hits = find_matches_regex_scoped(
         cx.ctx,
         std::slice::from_ref(&cx.ctx.commands_dir),
         &cx.re.claude_sub,
         &["pack-check.sh"],
+        CLAUDE_SUBSTITUTION_SCOPE,
     )?;
 
     if !sub_hits.is_empty() {
-        rep.fail("Found 'See CLAUDE.md > ...' substitution in flow commands (inline the rule):");
-        rep.indent_lines(format_line_matches(cx, sub_hits));
+        rep.fail_findings(
+            "Found 'See CLAUDE.md > ...' substitution in flow commands (inline the rule):",
+            format_line_matches(cx, sub_hits),
+        );
     } else {
         rep.pass("No CLAUDE.md behavioral substitution in flow commands");
     }
@@ -358,8 +563,10 @@ fn check_issue_drafts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     )?;
 
     if !issue_drafts_refs.is_empty() {
-        rep.fail("Found 'issue_drafts.md' reference (standardize on feedback_actions.md):");
-        rep.indent_lines(format_line_matches(cx, issue_drafts_refs));
+        rep.fail_findings(
+            "Found 'issue_drafts.md' reference (standardize on feedback_actions.md):",
+            format_line_matches(cx, issue_drafts_refs),
+        );
     } else {
         rep.pass("No issue_drafts.md references (feedback_actions.md is the standard)");
     }
@@ -368,10 +575,6 @@ fn check_issue_drafts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
 }
 
 /// Check 45: Cleanup agents must use demoswarm shim, not bespoke pipelines.
-///
-/// This enforces the pack contract that cleanup agents use the standardized
-/// `demoswarm.sh` shim for mechanical counts and extraction, not inline
-/// `grep|sed|awk|jq` pipelines.
 fn check_cleanup_uses_demoswarm_shim(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     // Only check cleanup agents
     let cleanup_files: Vec<PathBuf> = cx
@@ -420,22 +623,22 @@ fn check_cleanup_uses_demoswarm_shim(cx: &CheckCtx, rep: &mut Reporter) -> anyho
             }
 
             // Check for bespoke pipeline patterns
-            if cx.re.bespoke_pipeline.is_match(line) {
-                violations.push(format!(
-                    "{}:{}:{}",
-                    cx.ctx.rel(path),
-                    idx + 1,
-                    line.trim_end()
+            if let Some(m) = cx.re.bespoke_pipeline.find(line) {
+                violations.push(Finding::new(
+                    Location::file(cx.ctx.rel(path))
+                        .with_line(idx + 1)
+                        .with_column(line[..m.start()].chars().count() + 1),
+                    line.trim_end(),
                 ));
             }
         }
     }
 
     if !violations.is_empty() {
-        rep.fail(
+        rep.fail_findings(
             "Found bespoke grep/sed/awk/jq pipelines in cleanup agents (use demoswarm.sh shim):",
+            violations.into_iter().take(10),
         );
-        rep.indent_lines(violations.into_iter().take(10));
     } else {
         rep.pass("Cleanup agents use demoswarm.sh shim (no bespoke pipelines)");
     }
@@ -444,11 +647,6 @@ fn check_cleanup_uses_demoswarm_shim(cx: &CheckCtx, rep: &mut Reporter) -> anyho
 }
 
 /// Check 46: Skill ownership boundaries.
-///
-/// Enforces that restricted skill commands are only used by allowed agents:
-/// - `index upsert-status` → cleanup + run-prep agents only
-/// - `secrets scan|redact` → secrets-sanitizer only
-/// - `openq next-id|append` → clarifier only
 fn check_skill_ownership(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let mut violations = Vec::new();
 
@@ -466,43 +664,56 @@ fn check_skill_ownership(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()
         for (idx, line) in content.lines().enumerate() {
             // Check index upsert-status
             if cx.re.index_upsert_cmd.is_match(line)
-                && !cx.c.index_allowed_agents.contains(&agent_name)
+                && !cx.c.index_allowed_agents.iter().any(|a| a == agent_name)
             {
-                violations.push(format!(
-                    "{}:{}:{} (only cleanup/run-prep agents may use index upsert-status)",
-                    cx.ctx.rel(agent_file),
+                violations.push(line_finding(
+                    cx,
+                    agent_file,
                     idx + 1,
-                    line.trim_end()
+                    format!(
+                        "{} (only cleanup/run-prep agents may use index upsert-status)",
+                        line.trim_end()
+                    ),
                 ));
             }
 
             // Check secrets scan|redact
             if cx.re.secrets_cmd.is_match(line)
-                && !cx.c.secrets_allowed_agents.contains(&agent_name)
+                && !cx.c.secrets_allowed_agents.iter().any(|a| a == agent_name)
             {
-                violations.push(format!(
-                    "{}:{}:{} (only secrets-sanitizer may use secrets scan/redact)",
-                    cx.ctx.rel(agent_file),
+                violations.push(line_finding(
+                    cx,
+                    agent_file,
                     idx + 1,
-                    line.trim_end()
+                    format!(
+                        "{} (only secrets-sanitizer may use secrets scan/redact)",
+                        line.trim_end()
+                    ),
                 ));
             }
 
             // Check openq next-id|append
-            if cx.re.openq_cmd.is_match(line) && !cx.c.openq_allowed_agents.contains(&agent_name) {
-                violations.push(format!(
-                    "{}:{}:{} (only clarifier may use openq next-id/append)",
-                    cx.ctx.rel(agent_file),
+            if cx.re.openq_cmd.is_match(line)
+                && !cx.c.openq_allowed_agents.iter().any(|a| a == agent_name)
+            {
+                violations.push(line_finding(
+                    cx,
+                    agent_file,
                     idx + 1,
-                    line.trim_end()
+                    format!(
+                        "{} (only clarifier may use openq next-id/append)",
+                        line.trim_end()
+                    ),
                 ));
             }
         }
     }
 
     if !violations.is_empty() {
-        rep.fail("Found skill ownership violations:");
-        rep.indent_lines(violations.into_iter().take(10));
+        rep.fail_findings(
+            "Found skill ownership violations:",
+            violations.into_iter().take(10),
+        );
     } else {
         rep.pass("Skill ownership boundaries enforced");
     }
@@ -511,22 +722,16 @@ fn check_skill_ownership(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()
 }
 
 /// Check 47: No line-continuation bypass after demoswarm.sh.
-///
-/// Prevents clever/accidental line-wrap from bypassing ownership checks:
-/// ```text
-/// bash .claude/scripts/demoswarm.sh \
-///   secrets scan ...  # This splits the command, hiding the subcommand
-/// ```
 fn check_shim_line_continuation(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let roots = [cx.ctx.agents_dir.clone(), cx.ctx.commands_dir.clone()];
 
     let matches = find_matches_regex_recursive(cx.ctx, &roots, &cx.re.shim_line_continuation, &[])?;
 
     if !matches.is_empty() {
-        rep.fail(
+        rep.fail_findings(
             "Found demoswarm.sh followed by line continuation (subcommand must be on same line):",
+            format_line_matches(cx, matches).into_iter().take(5),
         );
-        rep.indent_lines(format_line_matches(cx, matches).into_iter().take(5));
     } else {
         rep.pass("No shim line-continuation bypass patterns");
     }
@@ -534,17 +739,17 @@ fn check_shim_line_continuation(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Re
     Ok(())
 }
 
+fn shim_files(cx: &CheckCtx) -> Vec<PathBuf> {
+    let roots = [cx.ctx.agents_dir.clone(), cx.ctx.commands_dir.clone()];
+    list_files_recursive(cx.ctx, &roots, &[])
+}
+
+/// Fix for check 47: pull the subcommand line up onto the `demoswarm.sh` line.
+fn is_shim_line_continuation(cx: &CheckCtx, line: &str) -> bool {
+    cx.re.shim_line_continuation.is_match(line)
+}
+
 /// Check 48: No direct demoswarm invocation (must use shim).
-///
-/// Ensures `demoswarm` is not invoked directly (bypassing the shim):
-/// ```text
-/// demoswarm count ...        # BAD: bypasses shim
-/// bash .claude/scripts/demoswarm.sh count ...  # GOOD: uses shim
-/// ```
-///
-/// Exceptions:
-/// - `tools/` directory (the CLI source itself)
-/// - `docs/reference/` (documentation about the CLI)
 fn check_direct_demoswarm_invocation(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let roots = [cx.ctx.agents_dir.clone(), cx.ctx.commands_dir.clone()];
 
@@ -558,8 +763,10 @@ fn check_direct_demoswarm_invocation(cx: &CheckCtx, rep: &mut Reporter) -> anyho
         .collect();
 
     if !violations.is_empty() {
-        rep.fail("Found direct demoswarm invocation (must use bash .claude/scripts/demoswarm.sh):");
-        rep.indent_lines(format_line_matches(cx, violations).into_iter().take(5));
+        rep.fail_findings(
+            "Found direct demoswarm invocation (must use bash .claude/scripts/demoswarm.sh):",
+            format_line_matches(cx, violations).into_iter().take(5),
+        );
     } else {
         rep.pass("No direct demoswarm invocations (all use shim)");
     }
@@ -567,48 +774,41 @@ fn check_direct_demoswarm_invocation(cx: &CheckCtx, rep: &mut Reporter) -> anyho
     Ok(())
 }
 
+/// Fix for check 48: route direct invocations through the shim.
+fn fix_direct_demoswarm_invocation(cx: &CheckCtx, line: &str) -> Option<String> {
+    if line.contains("demoswarm.sh") || !cx.re.direct_demoswarm_invocation.is_match(line) {
+        return None;
+    }
+    Some(
+        cx.re
+            .direct_demoswarm_invocation
+            .replace_all(line, "${1}bash .claude/scripts/demoswarm.sh ${2}")
+            .into_owned(),
+    )
+}
+
+/// Forbidden patterns are only looked for in prose; code blocks are examples.
+const GH_FORBIDDEN_SCOPE: Scope = Scope::Prose;
+
 /// Check 50: GH agents must use heredoc pattern (not --body-file) and avoid forbidden patterns.
-///
-/// This prevents the class of failures where:
-/// - Windows paths in --body-file are misinterpreted
-/// - Temp file paths leak into commands
-/// - Absolute paths or placeholders get posted
-///
-/// Requirements for GH posting agents:
-/// 1. Must NOT use --body-file (fails on Windows paths)
-/// 2. Must use heredoc pattern: -f body="$(cat <<'EOF' ... EOF)"
-/// 3. Must NOT contain forbidden patterns (absolute paths, temp dirs, placeholders)
 fn check_gh_body_hygiene(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let mut violations = Vec::new();
     let mut missing_heredoc = Vec::new();
 
-    for agent_name in cx.c.gh_posting_agents {
+    for agent_name in &cx.c.gh_posting_agents {
         let Some(agent_file) = cx.inv.agent(agent_name) else {
             continue;
         };
 
-        let content = match cx.ctx.read_utf8(agent_file) {
-            Ok(c) => c,
+        let md = match cx.ctx.markdown(agent_file) {
+            Ok(md) => md,
             Err(_) => continue,
         };
-
-        // Track if we're inside a code block (fenced with ```)
-        let mut in_code_block = false;
+        let content = md.content();
 
         // Check for forbidden patterns
-        for (idx, line) in content.lines().enumerate() {
-            // Track code block boundaries
-            if line.trim().starts_with("```") {
-                in_code_block = !in_code_block;
-                continue;
-            }
-
-            // Skip content inside code blocks (examples/documentation)
-            if in_code_block {
-                continue;
-            }
-
-            for pattern in cx.c.gh_body_forbidden_patterns {
+        for (line_no, line) in md.lines(GH_FORBIDDEN_SCOPE) {
+            for pattern in &cx.c.gh_body_forbidden_patterns {
                 // Skip patterns that appear in "do NOT" or "don't" documentation
                 let line_lower = line.to_lowercase();
                 if line_lower.contains("do not")
@@ -619,13 +819,12 @@ fn check_gh_body_hygiene(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()
                 }
 
                 // Check if the pattern appears outside of documentation context
-                if line.contains(pattern) {
-                    violations.push(format!(
-                        "{}:{}:{} (forbidden: '{}')",
-                        cx.ctx.rel(agent_file),
-                        idx + 1,
-                        line.trim_end(),
-                        pattern
+                if let Some(col) = line.find(pattern) {
+                    violations.push(Finding::new(
+                        Location::file(cx.ctx.rel(agent_file))
+                            .with_line(line_no)
+                            .with_column(line[..col].chars().count() + 1),
+                        format!("{} (forbidden: '{}')", line.trim_end(), pattern),
                     ));
                 }
             }
@@ -633,25 +832,28 @@ fn check_gh_body_hygiene(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()
 
         // Check for heredoc pattern (if agent posts to GH, should have heredoc example)
         if (content.contains("gh api") || content.contains("gh issue"))
-            && !cx.re.gh_heredoc_pattern.is_match(&content)
+            && !cx.re.gh_heredoc_pattern.is_match(content)
         {
-            missing_heredoc.push(format!(
-                "{} uses gh commands but missing heredoc pattern (-f body=\"$(cat <<'EOF'...)",
-                agent_name
+            missing_heredoc.push(Finding::new(
+                Location::file(cx.ctx.rel(agent_file)),
+                format!(
+                    "({agent_name} uses gh commands but missing heredoc pattern (-f body=\"$(cat <<'EOF'...))"
+                ),
             ));
         }
     }
 
     if !violations.is_empty() {
-        rep.fail("Found forbidden patterns in GH agents (will cause failures):");
-        rep.indent_lines(violations.into_iter().take(10));
+        rep.fail_findings(
+            "Found forbidden patterns in GH agents (will cause failures):",
+            violations.into_iter().take(10),
+        );
     } else {
         rep.pass("No forbidden patterns in GH agents");
     }
 
     if !missing_heredoc.is_empty() {
-        rep.fail("GH agents missing heredoc pattern:");
-        rep.indent_lines(missing_heredoc);
+        rep.fail_findings("GH agents missing heredoc pattern:", missing_heredoc);
     } else {
         rep.pass("All GH agents use heredoc pattern for body passing");
     }
@@ -660,17 +862,6 @@ fn check_gh_body_hygiene(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()
 }
 
 /// Check 52: Flow boundary enforcement - no demoswarm.sh or skill CLI syntax in flow commands.
-///
-/// Enforces the three-tier ownership model:
-/// - Flow commands -> delegate to agents
-/// - Agent docs -> use skills
-/// - Skill docs -> implement CLI operations
-///
-/// Flow commands should NOT contain:
-/// - "demoswarm.sh" (direct shim invocation)
-/// - Skill CLI subcommands (count, ms, yaml, index, receipt, receipts, openapi, line, inv, time, openq, secrets)
-///
-/// This prevents flow commands from bypassing the agent layer and directly using skill-layer CLI.
 fn check_flow_boundary_enforcement(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let mut shim_violations = Vec::new();
     let mut subcommand_violations = Vec::new();
@@ -683,12 +874,12 @@ fn check_flow_boundary_enforcement(cx: &CheckCtx, rep: &mut Reporter) -> anyhow:
 
         for (idx, line) in content.lines().enumerate() {
             // Check for demoswarm.sh invocation
-            if line.contains("demoswarm.sh") {
-                shim_violations.push(format!(
-                    "{}:{}:{}",
-                    cx.ctx.rel(flow_cmd),
-                    idx + 1,
-                    line.trim_end()
+            if let Some(col) = line.find("demoswarm.sh") {
+                shim_violations.push(Finding::new(
+                    Location::file(cx.ctx.rel(flow_cmd))
+                        .with_line(idx + 1)
+                        .with_column(line[..col].chars().count() + 1),
+                    line.trim_end(),
                 ));
             }
 
@@ -696,7 +887,7 @@ fn check_flow_boundary_enforcement(cx: &CheckCtx, rep: &mut Reporter) -> anyhow:
             // We want to flag: "demoswarm count", "ms get", "yaml get", etc.
             // But NOT prose mentions like "the count of items" or "the index of"
             // Strategy: look for patterns that suggest CLI invocation context
-            for subcommand in cx.c.skill_cli_subcommands {
+            for subcommand in &cx.c.skill_cli_subcommands {
                 // Look for the subcommand in CLI-like contexts:
                 // - After "demoswarm" (e.g., "demoswarm count")
                 // - After "bash" or in backticks/code blocks
@@ -709,13 +900,12 @@ fn check_flow_boundary_enforcement(cx: &CheckCtx, rep: &mut Reporter) -> anyhow:
                 ];
 
                 for pattern in &patterns {
-                    if line.contains(pattern) {
-                        subcommand_violations.push(format!(
-                            "{}:{}:{} (skill subcommand: '{}')",
-                            cx.ctx.rel(flow_cmd),
-                            idx + 1,
-                            line.trim_end(),
-                            subcommand
+                    if let Some(col) = line.find(pattern.as_str()) {
+                        subcommand_violations.push(Finding::new(
+                            Location::file(cx.ctx.rel(flow_cmd))
+                                .with_line(idx + 1)
+                                .with_column(line[..col].chars().count() + 1),
+                            format!("{} (skill subcommand: '{}')", line.trim_end(), subcommand),
                         ));
                         break;
                     }
@@ -725,15 +915,19 @@ fn check_flow_boundary_enforcement(cx: &CheckCtx, rep: &mut Reporter) -> anyhow:
     }
 
     if !shim_violations.is_empty() {
-        rep.warn("Found demoswarm.sh in flow commands (flows should delegate to agents):");
-        rep.indent_lines(shim_violations.into_iter().take(10));
+        rep.warn_findings(
+            "Found demoswarm.sh in flow commands (flows should delegate to agents):",
+            shim_violations.into_iter().take(10),
+        );
     } else {
         rep.pass("No demoswarm.sh in flow commands");
     }
 
     if !subcommand_violations.is_empty() {
-        rep.warn("Found skill CLI subcommands in flow commands (flows should delegate to agents):");
-        rep.indent_lines(subcommand_violations.into_iter().take(10));
+        rep.warn_findings(
+            "Found skill CLI subcommands in flow commands (flows should delegate to agents):",
+            subcommand_violations.into_iter().take(10),
+        );
     } else {
         rep.pass("No skill CLI subcommands in flow commands");
     }
@@ -742,22 +936,9 @@ fn check_flow_boundary_enforcement(cx: &CheckCtx, rep: &mut Reporter) -> anyhow:
 }
 
 /// Check 53: OpenQ prefix validation - QIDs must use canonical flow codes.
-///
-/// Validates that QID patterns in `.runs/**/open_questions.md` files follow
-/// the canonical format: OQ-<FLOW>-<NNN>
-///
-/// Where:
-/// - <FLOW> is one of: SIG, PLAN, BUILD, REVIEW, GATE, DEPLOY, WISDOM (full names preferred)
-/// - <NNN> is a three-digit zero-padded number (001-999)
-///
-/// Reports warnings for:
-/// - Non-canonical flow codes (e.g., PLN instead of PLAN, BLD instead of BUILD)
-/// - Invalid numeric suffixes (not three digits)
 fn check_openq_prefix_validation(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
-    use walkdir::WalkDir;
-
     let runs_dir = cx.ctx.repo_root.join(".runs");
-    if !runs_dir.exists() {
+    if !cx.ctx.is_dir(&runs_dir) {
         rep.pass("No .runs directory found (skipping OpenQ validation)");
         return Ok(());
     }
@@ -766,23 +947,13 @@ fn check_openq_prefix_validation(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::R
     let mut invalid_format = Vec::new();
 
     // Find all open_questions.md files under .runs/
-    for entry in WalkDir::new(&runs_dir).follow_links(false) {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        let path = entry.path();
-        if !path.is_file() {
-            continue;
-        }
-
+    for path in cx.ctx.files_under(&runs_dir) {
         let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
         if file_name != "open_questions.md" {
             continue;
         }
 
-        let content = match std::fs::read_to_string(path) {
+        let content = match cx.ctx.read_utf8(&path) {
             Ok(c) => c,
             Err(_) => continue,
         };
@@ -790,7 +961,14 @@ fn check_openq_prefix_validation(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::R
         // Look for QID patterns: OQ-XXX-NNN or similar
         // Valid: OQ-SIG-001, OQ-PLAN-002, OQ-BUILD-003, etc.
         // Invalid: OQ-PLN-001, OQ-BLD-002, OQ-SIG-1, etc.
+        let rel = cx.ctx.rel(&path);
         for (idx, line) in content.lines().enumerate() {
+            let qid_location = |line_no: usize, byte_col: usize| {
+                Location::file(rel.clone())
+                    .with_line(line_no)
+                    .with_column(line[..byte_col].chars().count() + 1)
+            };
+
             // Look for QID: or - QID: patterns
             if !line.contains("QID:") && !line.contains("OQ-") {
                 continue;
@@ -816,7 +994,7 @@ fn check_openq_prefix_validation(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::R
 
                     // Validate flow code
                     if !flow_code.is_empty() {
-                        let is_canonical = cx.c.openq_flow_codes.contains(&flow_code);
+                        let is_canonical = cx.c.openq_flow_codes.iter().any(|c| c == flow_code);
 
                         if !is_canonical {
                             // Check if it's a known non-canonical code (3-letter abbreviations)
@@ -825,34 +1003,37 @@ fn check_openq_prefix_validation(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::R
                             let is_known_wrong = non_canonical_codes.contains(&flow_code);
 
                             if is_known_wrong {
-                                invalid_codes.push(format!(
-                                    "{}:{}:{} (use {} instead of {})",
-                                    cx.ctx.rel(path),
-                                    idx + 1,
-                                    line.trim_end(),
-                                    suggest_canonical_code(flow_code),
-                                    flow_code
+                                invalid_codes.push(Finding::new(
+                                    qid_location(idx + 1, abs_start),
+                                    format!(
+                                        "{} (use {} instead of {})",
+                                        line.trim_end(),
+                                        suggest_canonical_code(flow_code),
+                                        flow_code
+                                    ),
                                 ));
                             } else if flow_code.chars().all(|c| c.is_ascii_uppercase()) {
                                 // Unknown uppercase code
-                                invalid_codes.push(format!(
-                                    "{}:{}:{} (unknown flow code: {})",
-                                    cx.ctx.rel(path),
-                                    idx + 1,
-                                    line.trim_end(),
-                                    flow_code
+                                invalid_codes.push(Finding::new(
+                                    qid_location(idx + 1, abs_start),
+                                    format!(
+                                        "{} (unknown flow code: {})",
+                                        line.trim_end(),
+                                        flow_code
+                                    ),
                                 ));
                             }
                         }
 
                         // Validate numeric suffix (should be 3 digits, zero-padded)
                         if !num_part.is_empty() && num_part.len() != 3 {
-                            invalid_format.push(format!(
-                                "{}:{}:{} (numeric suffix should be 3 digits: {})",
-                                cx.ctx.rel(path),
-                                idx + 1,
-                                line.trim_end(),
-                                num_part
+                            invalid_format.push(Finding::new(
+                                qid_location(idx + 1, abs_start),
+                                format!(
+                                    "{} (numeric suffix should be 3 digits: {})",
+                                    line.trim_end(),
+                                    num_part
+                                ),
                             ));
                         }
                     }
@@ -864,21 +1045,25 @@ fn check_openq_prefix_validation(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::R
     }
 
     // Deduplicate violations (same line may match multiple patterns)
-    invalid_codes.sort();
+    invalid_codes.sort_by_key(|f| f.to_string());
     invalid_codes.dedup();
-    invalid_format.sort();
+    invalid_format.sort_by_key(|f| f.to_string());
     invalid_format.dedup();
 
     if !invalid_codes.is_empty() {
-        rep.warn("Found non-canonical OpenQ flow codes:");
-        rep.indent_lines(invalid_codes.into_iter().take(10));
+        rep.warn_findings(
+            "Found non-canonical OpenQ flow codes:",
+            invalid_codes.into_iter().take(10),
+        );
     } else {
         rep.pass("All OpenQ QIDs use canonical flow codes");
     }
 
     if !invalid_format.is_empty() {
-        rep.warn("Found OpenQ QIDs with invalid format:");
-        rep.indent_lines(invalid_format.into_iter().take(10));
+        rep.warn_findings(
+            "Found OpenQ QIDs with invalid format:",
+            invalid_format.into_iter().take(10),
+        );
     } else {
         rep.pass("All OpenQ QIDs use valid format (OQ-<FLOW>-<NNN>)");
     }
@@ -886,12 +1071,31 @@ fn check_openq_prefix_validation(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::R
     Ok(())
 }
 
-/// Check 54: Agents using demoswarm.sh must have a Skills section.
-///
-/// Agents that invoke demoswarm.sh are expected to document which skills
-/// they use in a ## Skills (or ## Skill) section. This ensures that:
-/// - Skill dependencies are discoverable
-/// - Agents explicitly declare their tool usage
+fn open_questions_files(cx: &CheckCtx) -> Vec<PathBuf> {
+    list_files_recursive(cx.ctx, &[cx.ctx.repo_root.join(".runs")], &[])
+        .into_iter()
+        .filter(|p| p.file_name().is_some_and(|n| n == "open_questions.md"))
+        .collect()
+}
+
+/// Fix for check 53: replace known non-canonical flow codes (OQ-PLN- -> OQ-PLAN-),
+/// unless the pack's contracts declare the code canonical.
+fn fix_openq_prefix(cx: &CheckCtx, line: &str) -> Option<String> {
+    let fixed = cx
+        .re
+        .openq_non_canonical_prefix
+        .replace_all(line, |caps: &regex::Captures| {
+            let code = &caps[1];
+            if cx.c.openq_flow_codes.iter().any(|c| c == code) {
+                caps[0].to_string()
+            } else {
+                format!("OQ-{}-", suggest_canonical_code(code))
+            }
+        });
+    (fixed != line).then(|| fixed.into_owned())
+}
+
+/// Check 62: Agents using demoswarm.sh must have a Skills section.
 fn check_skills_section_required(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let mut violations = Vec::new();
 
@@ -902,12 +1106,10 @@ fn check_skills_section_required(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::R
         };
 
         // Check if agent uses demoswarm.sh
-        let uses_demoswarm = content.contains("demoswarm.sh");
-
-        if !uses_demoswarm {
+        let Some(first_use) = content.find("demoswarm.sh") else {
             // Agent doesn't use demoswarm, no requirement for Skills section
             continue;
-        }
+        };
 
         // Check for Skills or Skill section (## Skills or ## Skill)
         let has_skills_section = content.lines().any(|line| {
@@ -916,16 +1118,21 @@ fn check_skills_section_required(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::R
         });
 
         if !has_skills_section {
-            violations.push(format!(
-                "{} (uses demoswarm.sh but missing ## Skills section)",
-                cx.ctx.rel(agent_file),
+            let (line, column) = line_col_at(&content, first_use);
+            violations.push(Finding::new(
+                Location::file(cx.ctx.rel(agent_file))
+                    .with_line(line)
+                    .with_column(column),
+                "(uses demoswarm.sh but missing ## Skills section)",
             ));
         }
     }
 
     if !violations.is_empty() {
-        rep.fail("Found agents using demoswarm.sh without Skills section:");
-        rep.indent_lines(violations.into_iter().take(10));
+        rep.fail_findings(
+            "Found agents using demoswarm.sh without Skills section:",
+            violations.into_iter().take(10),
+        );
     } else {
         rep.pass("All agents using demoswarm.sh have Skills section");
     }
@@ -933,6 +1140,100 @@ fn check_skills_section_required(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::R
     Ok(())
 }
 
+/// Only shell blocks hold invocations; prose mentions of the shim are references.
+const SHIM_INVOCATION_SCOPE: Scope = Scope::Shell;
+
+/// Check 64: Every demoswarm.sh invocation parses with the real CLI grammar.
+fn check_shim_invocation_grammar(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let roots = [cx.ctx.agents_dir.clone(), cx.ctx.skills_dir.clone()];
+    let mut violations = Vec::new();
+    let mut checked = 0usize;
+
+    for file in list_files_recursive(cx.ctx, &roots, &[]) {
+        if file.extension().is_none_or(|ext| ext != "md") {
+            continue;
+        }
+        let md = cx.ctx.markdown(&file)?;
+        for inv in shim::extract(&md, SHIM_INVOCATION_SCOPE) {
+            checked += 1;
+            if let Err(msg) = shim::validate(&inv.args) {
+                violations.push(Finding::new(
+                    Location::file(cx.ctx.rel(&file))
+                        .with_line(inv.line)
+                        .with_column(inv.column),
+                    format!("{} ({msg})", inv.display()),
+                ));
+            }
+        }
+    }
+
+    if !violations.is_empty() {
+        rep.fail_findings(
+            "Found demoswarm.sh invocations the CLI would reject:",
+            violations,
+        );
+    } else {
+        rep.pass(format!(
+            "All {checked} demoswarm.sh invocations parse against the CLI grammar"
+        ));
+    }
+
+    Ok(())
+}
+
+/// A `swarm/` path segment (harness layout), but not `demoswarm/` or `.demoswarm/`.
+static HARNESS_SWARM_PATH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(^|[^A-Za-z0-9])swarm[/\\]").expect("valid regex"));
+
+/// Check 73: `.claude/` holds no harness-specific references.
+fn check_portable_pack(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let roots = [cx.ctx.claude_dir.clone()];
+    let exempt = |m: &LineMatch| cx.c.harness_exceptions.iter().any(|e| m.line.contains(e));
+    let mut found = false;
+
+    for pattern in &cx.c.harness_patterns {
+        let matches: Vec<LineMatch> = find_lines_containing_recursive(
+            cx.ctx,
+            &cx.ctx.claude_dir,
+            pattern,
+            contracts_file::FILE_NAMES,
+        )?
+        .into_iter()
+        .filter(|m| !exempt(m))
+        .collect();
+        if !matches.is_empty() {
+            found = true;
+            rep.fail_findings(
+                format!("Found harness-specific reference '{pattern}' in:"),
+                format_line_matches(cx, matches),
+            );
+        }
+    }
+
+    let matches: Vec<LineMatch> = find_matches_regex_recursive(
+        cx.ctx,
+        &roots,
+        &HARNESS_SWARM_PATH,
+        contracts_file::FILE_NAMES,
+    )?
+    .into_iter()
+    .filter(|m| !exempt(m))
+    .collect();
+    if !matches.is_empty() {
+        found = true;
+        rep.fail_findings(
+            "Found harness-specific reference 'swarm/' in:",
+            format_line_matches(cx, matches),
+        );
+    }
+
+    if !found {
+        rep.pass("No harness-specific references in .claude/");
+    }
+
+    Ok(())
+}
+
 /// Suggest the canonical flow code for a non-canonical one.
 fn suggest_canonical_code(non_canonical: &str) -> &'static str {
     match non_canonical {
@@ -1072,13 +1373,16 @@ mod tests {
             let matches = vec![LineMatch {
                 path: test_file,
                 line_no: 42,
+                column: 1,
                 line: "  some content here  ".to_string(),
             }];
 
             let result = format_line_matches(&cx, matches);
             assert_eq!(result.len(), 1);
-            assert!(result[0].contains(":42:"));
-            assert!(result[0].contains("some content here"));
+            assert_eq!(result[0].location.line, Some(42));
+            assert_eq!(result[0].location.column, Some(1));
+            assert!(result[0].to_string().contains(":42:"));
+            assert!(result[0].to_string().contains("some content here"));
         }
 
         #[test]
@@ -1095,13 +1399,18 @@ mod tests {
             let matches = vec![LineMatch {
                 path: test_file,
                 line_no: 1,
+                column: 1,
                 line: "content with trailing spaces   \t  ".to_string(),
             }];
 
             let result = format_line_matches(&cx, matches);
             assert_eq!(result.len(), 1);
             // Should trim trailing whitespace
-            assert!(result[0].ends_with("content with trailing spaces"));
+            assert!(
+                result[0]
+                    .to_string()
+                    .ends_with("content with trailing spaces")
+            );
         }
 
         #[test]
@@ -1119,25 +1428,28 @@ mod tests {
                 LineMatch {
                     path: test_file.clone(),
                     line_no: 10,
+                    column: 1,
                     line: "first line".to_string(),
                 },
                 LineMatch {
                     path: test_file.clone(),
                     line_no: 20,
+                    column: 1,
                     line: "second line".to_string(),
                 },
                 LineMatch {
                     path: test_file,
                     line_no: 30,
+                    column: 1,
                     line: "third line".to_string(),
                 },
             ];
 
             let result = format_line_matches(&cx, matches);
             assert_eq!(result.len(), 3);
-            assert!(result[0].contains(":10:"));
-            assert!(result[1].contains(":20:"));
-            assert!(result[2].contains(":30:"));
+            assert!(result[0].to_string().contains(":10:"));
+            assert!(result[1].to_string().contains(":20:"));
+            assert!(result[2].to_string().contains(":30:"));
         }
     }
 
@@ -2495,4 +2807,181 @@ Run demoswarm ms get --file test.txt directly.
             assert!(rep.errors > 0);
         }
     }
+
+    // ==========================================================================
+    // Tests for markdown-scoped scans (checks 8, 39)
+    // ==========================================================================
+
+    mod scoped_scan_tests {
+        use super::*;
+        use crate::checks::CheckCtx;
+        use crate::cli::OutputFormat;
+        use crate::contracts::Contracts;
+        use crate::contracts::test_utils::REGEXES;
+        use crate::ctx::Ctx;
+        use crate::inventory::Inventory;
+        use crate::reporter::Reporter;
+        use tempfile::TempDir;
+
+        fn run_on_pack(
+            rel_path: &str,
+            content: &str,
+            check: fn(&CheckCtx, &mut Reporter) -> anyhow::Result<()>,
+        ) -> Reporter {
+            let temp = TempDir::new().unwrap();
+            let claude_dir = temp.path().join(".claude");
+            for dir in ["agents", "commands", "skills"] {
+                std::fs::create_dir_all(claude_dir.join(dir)).unwrap();
+            }
+            std::fs::write(claude_dir.join(rel_path), content).unwrap();
+
+            let ctx = Ctx::discover(Some(temp.path().to_path_buf())).unwrap();
+            let inv = Inventory::from_ctx(&ctx).unwrap();
+            let c = Contracts::default();
+            let cx = CheckCtx {
+                ctx: &ctx,
+                inv: &inv,
+                re: &REGEXES,
+                c: &c,
+            };
+            let mut rep = Reporter::new(OutputFormat::Json, false, false);
+            check(&cx, &mut rep).unwrap();
+            rep
+        }
+
+        #[test]
+        fn test_shim_invocation_grammar() {
+            let agent = "# Agent\n\n\
+                Call `bash .claude/scripts/demoswarm.sh` for counts.\n\n\
+                ```bash\n\
+                bash .claude/scripts/demoswarm.sh count pattern --file \"$F\" --regex '^x'\n\
+                bash .claude/scripts/demoswarm.sh count pattern \\\n  \
+                --file x --regx '^x'\n\
+                bash .claude/scripts/demoswarm.sh cuont bdd --dir features\n\
+                ```\n";
+            let rep = run_on_pack("agents/counter.md", agent, check_shim_invocation_grammar);
+            assert_eq!(rep.errors, 2);
+
+            let failures: Vec<_> = rep
+                .diagnostics()
+                .iter()
+                .filter(|d| d.level == crate::reporter::Level::Fail)
+                .collect();
+            assert_eq!(failures.len(), 2);
+            assert_eq!(failures[0].location.as_ref().unwrap().line, Some(7));
+            assert!(failures[0].message.contains("--regx"));
+            assert!(failures[1].message.contains("cuont"));
+        }
+
+        #[test]
+        fn test_raw_git_in_bash_block_fails() {
+            let rep = run_on_pack(
+                "commands/flow-3-build.md",
+                "# Build\n\n```bash\ngit add .\n```\n",
+                check_raw_git,
+            );
+            assert_eq!(rep.errors, 1);
+        }
+
+        #[test]
+        fn test_raw_git_in_untagged_block_fails() {
+            let rep = run_on_pack(
+                "commands/flow-3-build.md",
+                "# Build\n\n```\ngit add .\n```\n",
+                check_raw_git,
+            );
+            assert_eq!(rep.errors, 1);
+        }
+
+        #[test]
+        fn test_raw_git_in_prose_or_other_blocks_ignored() {
+            let rep = run_on_pack(
+                "commands/flow-3-build.md",
+                "# Build\n\nNever run git add . yourself.\n\n```text\ngit reset --hard\n```\n",
+                check_raw_git,
+            );
+            assert_eq!(rep.errors, 0);
+        }
+
+        #[test]
+        fn test_banned_pattern_in_blockquote_ignored() {
+            let rep = run_on_pack(
+                "agents/a.md",
+                "# A\n\n> Old docs said: run harness.py\n",
+                check_banned_patterns,
+            );
+            assert_eq!(rep.errors, 0);
+        }
+
+        #[test]
+        fn test_banned_pattern_in_prose_and_code_fails() {
+            let rep = run_on_pack(
+                "agents/a.md",
+                "# A\n\nUse harness.py\n\n```bash\npython harness.py\n```\n",
+                check_banned_patterns,
+            );
+            assert_eq!(rep.errors, 2);
+            let failures = rep
+                .diagnostics()
+                .iter()
+                .filter(|d| d.level == crate::reporter::Level::Fail)
+                .count();
+            assert_eq!(failures, 2);
+        }
+
+        #[test]
+        fn test_portable_pack() {
+            let rep = run_on_pack(
+                "agents/a.md",
+                "# A\n\nOpen localhost:5000 in Flow Studio.\n\n\
+                > Quoted swarm/ paths count too.\n\n\
+                Install to .demoswarm/bin and write FR-001 for flow-studio.\n",
+                check_portable_pack,
+            );
+            // (line, pattern) of each failure
+            let found: Vec<(usize, &str)> = rep
+                .diagnostics()
+                .iter()
+                .filter(|d| d.level == crate::reporter::Level::Fail)
+                .map(|d| {
+                    let line = d.location.as_ref().unwrap().line.unwrap();
+                    (line, d.message.split('\'').nth(1).unwrap())
+                })
+                .collect();
+            assert_eq!(
+                found,
+                [(3, "Flow Studio"), (3, "localhost:5000"), (5, "swarm/")]
+            );
+        }
+
+        #[test]
+        fn test_portable_pack_scans_frontmatter_and_quotes() {
+            let rep = run_on_pack(
+                "agents/a.md",
+                "---\nname: a\ndescription: Talks to Flow Studio\n---\n\n\
+                > Open Flow Studio first\n",
+                check_portable_pack,
+            );
+            let lines: Vec<usize> = rep
+                .diagnostics()
+                .iter()
+                .filter(|d| d.level == crate::reporter::Level::Fail)
+                .map(|d| d.location.as_ref().unwrap().line.unwrap())
+                .collect();
+            assert_eq!(lines, [3, 6]);
+        }
+
+        #[test]
+        fn test_skills_section_required() {
+            let agent = "# A\n\nRun `bash .claude/scripts/demoswarm.sh count bdd`.\n";
+            let rep = run_on_pack("agents/a.md", agent, check_skills_section_required);
+            assert_eq!(rep.errors, 1);
+            let loc = rep.diagnostics()[0].location.as_ref().unwrap();
+            assert_eq!((loc.line, loc.column), (Some(3), Some(27)));
+
+            let agent = format!("{agent}\n## Skill\n\n- **runs-derive**: counts\n");
+            let rep = run_on_pack("agents/a.md", &agent, check_skills_section_required);
+            assert_eq!(rep.errors, 0);
+        }
+    }
 }
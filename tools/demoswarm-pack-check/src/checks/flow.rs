@@ -1,93 +1,140 @@
 //! Flow command checks.
 //!
-//! Checks: 5, 11, 12, 13, 22, 25, 26, 43, 44, 45, 46, 47, 48, 49, 50
+//! Checks: 5, 12, 13, 22, 25, 26, 43, 44, 49, 56, 57, 59, 60, 61, 81
 
 use super::contracts::headings;
-use crate::reporter::Reporter;
-use crate::util::{contains_ignore_ascii_case, extract_frontmatter_name};
+use super::control_plane::INPUT_SECTIONS;
+use crate::markdown::{LineKind, Markdown, Scope};
+use crate::reporter::{Finding, Location, Reporter};
+use crate::shim;
+use crate::util::{contains_ignore_ascii_case, extract_frontmatter_name, line_col_at};
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use super::{CheckCtx, CheckSpec};
+use super::{Category, CheckCtx, CheckSpec};
 
 pub fn checks() -> Vec<CheckSpec> {
     vec![
         CheckSpec {
             id: 5,
             title: "Checking flows reference sealing sequence (cleanup → secrets → repo-op → GH ops)...",
+            category: Category::Flow,
+            rationale: "Flow commands have sealing sequence.",
             run: check_sealing_sequence,
         },
         CheckSpec {
-            id: 11,
+            id: 56,
             title: "Checking issue-first phrasing...",
+            category: Category::Flow,
+            rationale: "Issue-first phrasing.",
             run: check_issue_first,
         },
         CheckSpec {
             id: 12,
             title: "Checking CANNOT_PROCEED semantics in cleanup agents...",
+            category: Category::Flow,
+            rationale: "CANNOT_PROCEED semantics in cleanup agents.",
             run: check_cleanup_cannot_proceed,
         },
         CheckSpec {
             id: 13,
             title: "Checking reseal pattern in flow commands...",
+            category: Category::Flow,
+            rationale: "Reseal pattern in flow commands.",
             run: check_reseal_pattern,
         },
         CheckSpec {
             id: 22,
             title: "Checking decision spine marker contracts...",
+            category: Category::Flow,
+            rationale: "Decision spine marker contracts.",
             run: check_decision_spine,
         },
         CheckSpec {
             id: 25,
             title: "Checking decision spine extraction in plan-cleanup...",
+            category: Category::Flow,
+            rationale: "Decision spine extraction in plan-cleanup.",
             run: check_plan_cleanup_spine,
         },
         CheckSpec {
             id: 26,
             title: "Checking jq commands use single-line paths...",
+            category: Category::Flow,
+            rationale: "jq commands use single-line paths.\n\
+                \n\
+                Only shell blocks hold jq invocations; prose mentioning jq is not one.",
             run: check_jq_paths,
         },
         CheckSpec {
             id: 43,
             title: "Checking subtask bridge contract...",
+            category: Category::Flow,
+            rationale: "Subtask bridge contract.",
             run: check_subtask_bridge,
         },
         CheckSpec {
             id: 44,
             title: "Checking reseal non-convergence guard...",
+            category: Category::Flow,
+            rationale: "Reseal non-convergence guard.",
             run: check_reseal_guard,
         },
         CheckSpec {
-            id: 45,
+            id: 57,
             title: "Checking flow skill plumbing boundary...",
+            category: Category::Flow,
+            rationale: "Flow Skill Plumbing boundary.\n\
+                \n\
+                Flow commands should not reference skill names directly or mention demoswarm.sh.\n\
+                Skills are agent-level implementation details, not flow-level concepts.",
             run: check_flow_skill_plumbing,
         },
         CheckSpec {
-            id: 46,
-            title: "Checking agent docs have Skills section when using demoswarm.sh...",
-            run: check_missing_skills_section,
-        },
-        CheckSpec {
-            id: 47,
+            id: 59,
             title: "Checking flow output path patterns (advisory)...",
+            category: Category::Flow,
+            rationale: "Flow output path patterns (advisory).\n\
+                \n\
+                Scans flow commands for patterns like \"agent -> .runs/\" or \"agent -> file\"\n\
+                which may indicate documentation of agent file outputs (informational only).",
             run: check_flow_output_paths,
         },
         CheckSpec {
-            id: 48,
+            id: 60,
             title: "Checking ms get keys are documented by producers...",
+            category: Category::Flow,
+            rationale: "Every `demoswarm.sh ms get --key X` used by a consumer must be documented\n\
+                in the producing agent's `## Machine Summary` schema for that artifact.",
             run: check_ms_get_key_contracts,
         },
         CheckSpec {
             id: 49,
             title: "Checking inv get marker contracts (consumer vs producer)...",
+            category: Category::Flow,
+            rationale: "Every `demoswarm.sh inv get --marker X` used by a consumer must match a\n\
+                documented marker in the producing agent's contract for that artifact.",
             run: check_inv_marker_contracts,
         },
         CheckSpec {
-            id: 50,
+            id: 61,
             title: "Checking command docs reference declared agent names...",
+            category: Category::Flow,
+            rationale: "Command docs only reference declared agent names (frontmatter `name:`).",
             run: check_flow_agent_name_resolution,
         },
+        CheckSpec {
+            id: 81,
+            title: "Checking receipt get keys are documented by the cleanup agent writing the receipt...",
+            category: Category::Flow,
+            rationale: "Every `demoswarm.sh receipt get --file F --key K` must read a key the\n\
+                cleanup agent writing receipt `F` (per the `cleanup_agents` contract) documents\n\
+                in its receipt schema: a ```` ```json ```` block where `K`'s segments are\n\
+                nested keys (`counts.passed` is `\"passed\"` inside `\"counts\"`).",
+            run: check_receipt_get_key_contracts,
+        },
     ]
 }
 
@@ -101,33 +148,36 @@ fn check_sealing_sequence(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<(
         let content = cx.ctx.read_utf8(cmd)?;
 
         let mut missing = Vec::new();
-        for marker in cx.c.sealing_markers {
+        for marker in &cx.c.sealing_markers {
             if !contains_ignore_ascii_case(&content, marker) {
-                missing.push(*marker);
+                missing.push(marker.as_str());
             }
         }
 
         if missing.is_empty() {
             rep.pass(format!("{flow_name} references sealing sequence"));
         } else {
-            rep.warn(format!(
-                "{flow_name} missing references: {}",
-                missing.join(" ")
-            ));
+            rep.warn_at(
+                Location::file(cx.ctx.rel(cmd)),
+                format!("{flow_name} missing references: {}", missing.join(" ")),
+            );
         }
     }
 
     Ok(())
 }
 
-/// Check 11: Issue-first phrasing.
+/// Check 56: Issue-first phrasing.
 fn check_issue_first(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     if let Some(gh_reporter) = cx.inv.agent("gh-reporter") {
         let content = cx.ctx.read_utf8(gh_reporter)?;
         if content.contains(headings::ISSUE_FIRST_INVARIANT) {
             rep.pass("gh-reporter has issue-first invariant");
         } else {
-            rep.fail("gh-reporter MISSING issue-first invariant section");
+            rep.fail_at(
+                Location::file(cx.ctx.rel(gh_reporter)),
+                "gh-reporter MISSING issue-first invariant section",
+            );
         }
     }
 
@@ -138,18 +188,25 @@ fn check_issue_first(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
             .unwrap_or("<unknown>");
         let content = cx.ctx.read_utf8(cmd)?;
 
-        let mut flagged = false;
-        for line in content.lines() {
-            if cx.re.pr_first.is_match(line) && !line.contains("(not PR)") {
-                flagged = true;
+        let mut flagged = None;
+        for (idx, line) in content.lines().enumerate() {
+            if let Some(m) = cx.re.pr_first.find(line)
+                && !line.contains("(not PR)")
+            {
+                flagged = Some(
+                    Location::file(cx.ctx.rel(cmd))
+                        .with_line(idx + 1)
+                        .with_column(line[..m.start()].chars().count() + 1),
+                );
                 break;
             }
         }
 
-        if flagged {
-            rep.fail(format!(
-                "{flow_name} has PR-first or ambiguous posting target"
-            ));
+        if let Some(loc) = flagged {
+            rep.fail_at(
+                loc,
+                format!("{flow_name} has PR-first or ambiguous posting target"),
+            );
         } else {
             rep.pass(format!("{flow_name} uses issue-first posting"));
         }
@@ -177,14 +234,16 @@ fn check_cleanup_cannot_proceed(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Re
                     "{cleanup_name} documents CANNOT_PROCEED as mechanical + missing_required"
                 ));
             } else {
-                rep.warn(format!(
-                    "{cleanup_name} may be missing 'missing_required' documentation"
-                ));
+                rep.warn_at(
+                    Location::file(cx.ctx.rel(path)),
+                    format!("{cleanup_name} may be missing 'missing_required' documentation"),
+                );
             }
         } else {
-            rep.warn(format!(
-                "{cleanup_name} may have unclear CANNOT_PROCEED semantics"
-            ));
+            rep.warn_at(
+                Location::file(cx.ctx.rel(path)),
+                format!("{cleanup_name} may have unclear CANNOT_PROCEED semantics"),
+            );
         }
     }
 
@@ -203,7 +262,10 @@ fn check_reseal_pattern(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()>
         if cx.re.reseal_pattern.is_match(&content) {
             rep.pass(format!("{flow_name} references reseal-if-modified"));
         } else {
-            rep.warn(format!("{flow_name} may be missing reseal-if-modified"));
+            rep.warn_at(
+                Location::file(cx.ctx.rel(cmd)),
+                format!("{flow_name} may be missing reseal-if-modified"),
+            );
         }
     }
 
@@ -226,10 +288,10 @@ fn check_decision_spine(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()>
         if missing.is_empty() {
             rep.pass("design-optioneer.md has marker-level schema hints");
         } else {
-            rep.warn(format!(
-                "design-optioneer.md may be missing: {}",
-                missing.join(" ")
-            ));
+            rep.warn_at(
+                Location::file(cx.ctx.rel(design_optioneer)),
+                format!("design-optioneer.md may be missing: {}", missing.join(" ")),
+            );
         }
     } else {
         rep.fail("design-optioneer.md MISSING");
@@ -256,7 +318,10 @@ fn check_decision_spine(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()>
         if issues.is_empty() {
             rep.pass("adr-author.md matches marker-based ADR contract");
         } else {
-            rep.fail(format!("adr-author.md drift: {}", issues.join(" ")));
+            rep.fail_at(
+                Location::file(cx.ctx.rel(adr_author)),
+                format!("adr-author.md drift: {}", issues.join(" ")),
+            );
         }
     } else {
         rep.fail("adr-author.md MISSING");
@@ -283,7 +348,10 @@ fn check_decision_spine(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()>
         if issues.is_empty() {
             rep.pass("design-critic.md matches updated handshake contract (semantic + markers)");
         } else {
-            rep.fail(format!("design-critic.md drift: {}", issues.join(" ")));
+            rep.fail_at(
+                Location::file(cx.ctx.rel(design_critic)),
+                format!("design-critic.md drift: {}", issues.join(" ")),
+            );
         }
     } else {
         rep.fail("design-critic.md MISSING");
@@ -311,53 +379,50 @@ fn check_plan_cleanup_spine(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result
         if missing.is_empty() {
             rep.pass("plan-cleanup.md has marker-based decision spine extraction");
         } else {
-            rep.warn(format!(
-                "plan-cleanup.md may be missing: {}",
-                missing.join(" ")
-            ));
+            rep.warn_at(
+                Location::file(cx.ctx.rel(plan_cleanup)),
+                format!("plan-cleanup.md may be missing: {}", missing.join(" ")),
+            );
         }
     }
 
     Ok(())
 }
 
+/// Only shell blocks hold jq invocations; prose mentioning jq is not one.
+const JQ_SCOPE: Scope = Scope::Shell;
+
 /// Check 26: jq commands use single-line paths.
 fn check_jq_paths(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let mut split_jq_files = Vec::new();
     for cmd in &cx.inv.flow_cmd_files {
-        let content = cx.ctx.read_utf8(cmd)?;
+        let md = cx.ctx.markdown(cmd)?;
 
-        let mut any_jq_runs = false;
-        for line in content.lines() {
-            if cx.re.jq_has_runs.is_match(line) {
-                any_jq_runs = true;
-                break;
-            }
-        }
+        let any_jq_runs = md
+            .lines(JQ_SCOPE)
+            .any(|(_, line)| cx.re.jq_has_runs.is_match(line));
         if !any_jq_runs {
             continue;
         }
 
-        let mut flagged = false;
-        for line in content.lines() {
+        for (line_no, line) in md.lines(JQ_SCOPE) {
             if cx.re.jq_quote.is_match(line) && !line.contains(".runs/") {
-                flagged = true;
+                split_jq_files.push(Finding::new(
+                    Location::file(cx.ctx.rel(cmd)).with_line(line_no),
+                    "(may have split jq path)",
+                ));
                 break;
             }
         }
-
-        if flagged {
-            split_jq_files.push(cx.ctx.rel(cmd));
-        }
     }
 
     if split_jq_files.is_empty() {
         rep.pass("jq commands appear to use single-line paths");
     } else {
-        for f in &split_jq_files {
-            rep.indent_lines([format!("{f} may have split jq path")]);
-        }
-        rep.warn("Some jq commands may have multiline path issues");
+        rep.warn_findings(
+            "Some jq commands may have multiline path issues",
+            split_jq_files,
+        );
     }
 
     Ok(())
@@ -371,7 +436,8 @@ fn check_subtask_bridge(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()>
         if content.contains("subtasks.yaml") {
             rep.pass("work-planner.md mentions subtasks.yaml output");
         } else {
-            rep.fail(
+            rep.fail_at(
+                Location::file(cx.ctx.rel(work_planner)),
                 "work-planner.md does NOT mention subtasks.yaml (required for codetask bridge)",
             );
         }
@@ -379,13 +445,19 @@ fn check_subtask_bridge(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()>
         if cx.re.status_enum.is_match(&content) {
             rep.pass("work-planner.md documents subtask status enum (TODO | DOING | DONE)");
         } else {
-            rep.fail("work-planner.md missing subtask status enum (TODO | DOING | DONE)");
+            rep.fail_at(
+                Location::file(cx.ctx.rel(work_planner)),
+                "work-planner.md missing subtask status enum (TODO | DOING | DONE)",
+            );
         }
 
         if content.contains("allow_new_files_under") {
             rep.pass("work-planner.md documents allow_new_files_under scope hint");
         } else {
-            rep.fail("work-planner.md missing allow_new_files_under scope hint");
+            rep.fail_at(
+                Location::file(cx.ctx.rel(work_planner)),
+                "work-planner.md missing allow_new_files_under scope hint",
+            );
         }
     }
 
@@ -395,13 +467,17 @@ fn check_subtask_bridge(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()>
         if content.contains("subtasks.yaml") {
             rep.pass("context-loader.md references subtasks.yaml");
         } else {
-            rep.fail("context-loader.md does NOT reference subtasks.yaml");
+            rep.fail_at(
+                Location::file(cx.ctx.rel(context_loader)),
+                "context-loader.md does NOT reference subtasks.yaml",
+            );
         }
 
         if cx.re.precedence.is_match(&content) && cx.re.todo_status.is_match(&content) {
             rep.pass("context-loader.md documents subtask selection precedence");
         } else {
-            rep.fail(
+            rep.fail_at(
+                Location::file(cx.ctx.rel(context_loader)),
                 "context-loader.md missing selection precedence (explicit id → next TODO → fallback)",
             );
         }
@@ -409,7 +485,10 @@ fn check_subtask_bridge(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()>
         if content.contains("allow_new_files_under") {
             rep.pass("context-loader.md manifest includes allow_new_files_under");
         } else {
-            rep.fail("context-loader.md manifest missing allow_new_files_under");
+            rep.fail_at(
+                Location::file(cx.ctx.rel(context_loader)),
+                "context-loader.md manifest missing allow_new_files_under",
+            );
         }
     }
 
@@ -439,10 +518,7 @@ fn check_reseal_guard(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Check 45: Flow Skill Plumbing boundary.
-///
-/// Flow commands should not reference skill names directly or mention demoswarm.sh.
-/// Skills are agent-level implementation details, not flow-level concepts.
+/// Check 57: Flow Skill Plumbing boundary.
 fn check_flow_skill_plumbing(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let mut flagged_files = Vec::new();
 
@@ -454,74 +530,41 @@ fn check_flow_skill_plumbing(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Resul
         let content = cx.ctx.read_utf8(cmd)?;
 
         let mut issues = Vec::new();
+        let mut first_offset: Option<usize> = None;
 
         // Check for skill names in prose
-        if cx.re.skill_names_in_prose.is_match(&content) {
+        if let Some(m) = cx.re.skill_names_in_prose.find(&content) {
             issues.push("skill name references");
+            first_offset = Some(m.start());
         }
 
         // Check for demoswarm.sh references
-        if cx.re.demoswarm_shim_ref.is_match(&content) {
+        if let Some(m) = cx.re.demoswarm_shim_ref.find(&content) {
             issues.push("demoswarm.sh references");
+            first_offset = Some(first_offset.map_or(m.start(), |o| o.min(m.start())));
         }
 
-        if !issues.is_empty() {
-            flagged_files.push(format!("{flow_name}: {}", issues.join(", ")));
+        if let Some(offset) = first_offset {
+            flagged_files.push(Finding::new(
+                offset_location(cx, cmd, &content, offset),
+                format!("({flow_name}: {})", issues.join(", ")),
+            ));
         }
     }
 
     if flagged_files.is_empty() {
         rep.pass("Flow commands do not leak skill plumbing");
     } else {
-        for f in &flagged_files {
-            rep.indent_lines([f.clone()]);
-        }
-        rep.fail("Flow commands contain skill plumbing (should be agent-level)");
-    }
-
-    Ok(())
-}
-
-/// Check 46: Missing Skills section in agent docs.
-///
-/// If an agent doc references demoswarm.sh, it should have a ## Skills section.
-fn check_missing_skills_section(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
-    let mut missing_section = Vec::new();
-
-    for path in &cx.inv.agent_md_files {
-        let agent = path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("<unknown>");
-        let content = cx.ctx.read_utf8(path)?;
-
-        // Check if agent uses demoswarm.sh
-        if cx.re.demoswarm_shim_ref.is_match(&content) {
-            // Check if it has a Skills section
-            if !content.contains("## Skills") {
-                missing_section.push(agent.to_string());
-            }
-        }
-    }
-
-    if missing_section.is_empty() {
-        rep.pass("Agents using demoswarm.sh have Skills section");
-    } else {
-        for agent in &missing_section {
-            rep.indent_lines([format!(
-                "{agent} uses demoswarm.sh but lacks ## Skills section"
-            )]);
-        }
-        rep.warn("Some agents using demoswarm.sh lack Skills section");
+        rep.fail_findings(
+            "Flow commands contain skill plumbing (should be agent-level)",
+            flagged_files,
+        );
     }
 
     Ok(())
 }
 
-/// Check 47: Flow output path patterns (advisory).
-///
-/// Scans flow commands for patterns like "agent -> .runs/" or "agent -> file"
-/// which may indicate documentation of agent file outputs (informational only).
+/// Check 59: Flow output path patterns (advisory).
 fn check_flow_output_paths(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let mut found_patterns = Vec::new();
 
@@ -532,28 +575,32 @@ fn check_flow_output_paths(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<
             .unwrap_or("<unknown>");
         let content = cx.ctx.read_utf8(cmd)?;
 
-        if cx.re.flow_output_arrow.is_match(&content) {
-            found_patterns.push(flow_name.to_string());
+        if let Some(m) = cx.re.flow_output_arrow.find(&content) {
+            found_patterns.push(Finding::new(
+                offset_location(cx, cmd, &content, m.start()),
+                format!("({flow_name} has output arrow patterns)"),
+            ));
         }
     }
 
     if found_patterns.is_empty() {
         rep.pass("No flow output arrow patterns detected");
     } else {
-        for flow in &found_patterns {
-            rep.indent_lines([format!("{flow} has output arrow patterns")]);
-        }
-        rep.warn("Flow commands have output arrow patterns (advisory: consider if this leaks implementation)");
+        rep.warn_findings(
+            "Flow commands have output arrow patterns (advisory: consider if this leaks implementation)",
+            found_patterns,
+        );
     }
 
     Ok(())
 }
 
-/// Check 48: Every `demoswarm.sh ms get --key X` used by a consumer must be documented
+/// Check 60: Every `demoswarm.sh ms get --key X` used by a consumer must be documented
 /// in the producing agent's `## Machine Summary` schema for that artifact.
 fn check_ms_get_key_contracts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let agent_docs = load_agent_docs(cx)?;
-    let outputs_to_agents = map_outputs_to_agents(&agent_docs)?;
+    let agent_paths = load_agent_paths(cx)?;
+    let outputs_to_agents = map_outputs_to_agents(&agent_docs, &cx.flows().names())?;
 
     let mut seen = HashSet::<(String, String)>::new();
     let mut unresolved = Vec::new();
@@ -566,10 +613,19 @@ fn check_ms_get_key_contracts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Resu
             if !seen.insert((file.clone(), key.clone())) {
                 continue;
             }
+            let loc = span_location(
+                cx,
+                agent_paths.get(consumer_name),
+                consumer_content,
+                cap.get(0).unwrap(),
+            );
 
             let Some(producers) = outputs_to_agents.get(&file) else {
-                unresolved.push(format!(
-                    "Cannot map `{file}` to a producer (ms get expects key `{key}`; referenced in {consumer_name})"
+                unresolved.push((
+                    loc,
+                    format!(
+                        "Cannot map `{file}` to a producer (ms get expects key `{key}`; referenced in {consumer_name})"
+                    ),
                 ));
                 continue;
             };
@@ -586,13 +642,16 @@ fn check_ms_get_key_contracts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Resu
             }
 
             if !ok {
-                drift.push(format!(
-                    "`{file}`: consumer expects Machine Summary key `{key}`, but producer(s) {} do not document it",
-                    producers
-                        .iter()
-                        .map(|p| format!("`{p}`"))
-                        .collect::<Vec<_>>()
-                        .join(", ")
+                drift.push((
+                    loc,
+                    format!(
+                        "`{file}`: consumer expects Machine Summary key `{key}`, but producer(s) {} do not document it",
+                        producers
+                            .iter()
+                            .map(|p| format!("`{p}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
                 ));
             }
         }
@@ -601,13 +660,19 @@ fn check_ms_get_key_contracts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Resu
     if drift.is_empty() {
         rep.pass("ms get keys appear documented by producers");
     } else {
-        for msg in drift {
-            rep.fail(msg);
+        for (loc, msg) in drift {
+            match loc {
+                Some(loc) => rep.fail_at(loc, msg),
+                None => rep.fail(msg),
+            }
         }
     }
 
-    for msg in unresolved {
-        rep.warn(msg);
+    for (loc, msg) in unresolved {
+        match loc {
+            Some(loc) => rep.warn_at(loc, msg),
+            None => rep.warn(msg),
+        }
     }
 
     Ok(())
@@ -617,7 +682,8 @@ fn check_ms_get_key_contracts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Resu
 /// documented marker in the producing agent's contract for that artifact.
 fn check_inv_marker_contracts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let agent_docs = load_agent_docs(cx)?;
-    let outputs_to_agents = map_outputs_to_agents(&agent_docs)?;
+    let agent_paths = load_agent_paths(cx)?;
+    let outputs_to_agents = map_outputs_to_agents(&agent_docs, &cx.flows().names())?;
 
     let mut seen = HashSet::<(String, String)>::new();
     let mut unresolved = Vec::new();
@@ -630,10 +696,19 @@ fn check_inv_marker_contracts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Resu
             if !seen.insert((file.clone(), marker.clone())) {
                 continue;
             }
+            let loc = span_location(
+                cx,
+                agent_paths.get(consumer_name),
+                consumer_content,
+                cap.get(0).unwrap(),
+            );
 
             let Some(producers) = outputs_to_agents.get(&file) else {
-                unresolved.push(format!(
-                    "Cannot map `{file}` to a producer (inv get expects marker `{marker}`; referenced in {consumer_name})"
+                unresolved.push((
+                    loc,
+                    format!(
+                        "Cannot map `{file}` to a producer (inv get expects marker `{marker}`; referenced in {consumer_name})"
+                    ),
                 ));
                 continue;
             };
@@ -651,13 +726,16 @@ fn check_inv_marker_contracts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Resu
             }
 
             if !ok {
-                drift.push(format!(
-                    "`{file}`: consumer expects inventory marker `{marker}`, but producer(s) {} do not document `{needle}`",
-                    producers
-                        .iter()
-                        .map(|p| format!("`{p}`"))
-                        .collect::<Vec<_>>()
-                        .join(", ")
+                drift.push((
+                    loc,
+                    format!(
+                        "`{file}`: consumer expects inventory marker `{marker}`, but producer(s) {} do not document `{needle}`",
+                        producers
+                            .iter()
+                            .map(|p| format!("`{p}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
                 ));
             }
         }
@@ -666,19 +744,109 @@ fn check_inv_marker_contracts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Resu
     if drift.is_empty() {
         rep.pass("inv get markers appear documented by producers");
     } else {
-        for msg in drift {
-            rep.fail(msg);
+        for (loc, msg) in drift {
+            match loc {
+                Some(loc) => rep.fail_at(loc, msg),
+                None => rep.fail(msg),
+            }
+        }
+    }
+
+    for (loc, msg) in unresolved {
+        match loc {
+            Some(loc) => rep.warn_at(loc, msg),
+            None => rep.warn(msg),
+        }
+    }
+
+    Ok(())
+}
+
+/// Check 81: Every `demoswarm.sh receipt get --file F --key K` must read a key the
+/// cleanup agent writing receipt `F` documents in its receipt schema.
+fn check_receipt_get_key_contracts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let agent_docs = load_agent_docs(cx)?;
+    let agent_paths = load_agent_paths(cx)?;
+
+    let mut schemas: HashMap<&str, HashSet<String>> = HashMap::new();
+    let mut unresolved = Vec::new();
+    let mut drift = Vec::new();
+
+    let mut consumers: Vec<&String> = agent_docs.keys().collect();
+    consumers.sort();
+    for consumer_name in consumers {
+        let consumer_content = &agent_docs[consumer_name];
+        // Each consumer is reported on its own, at its first read of a key.
+        let mut seen = HashSet::<(String, String)>::new();
+        for cap in cx.re.receipt_get_invocation.captures_iter(consumer_content) {
+            let file = cap.get(1).unwrap().as_str().to_string();
+            let key = cap.get(2).unwrap().as_str().to_string();
+            if !seen.insert((file.clone(), key.clone())) {
+                continue;
+            }
+            let loc = span_location(
+                cx,
+                agent_paths.get(consumer_name),
+                consumer_content,
+                cap.get(0).unwrap(),
+            );
+
+            let receipt = file.rsplit('/').next().unwrap_or(&file);
+            let Some((producer, _)) = cx.c.cleanup_agents.iter().find(|(_, r)| r == receipt) else {
+                unresolved.push((
+                    loc,
+                    format!(
+                        "Cannot map `{file}` to a cleanup agent (receipt get expects key `{key}`; referenced in {consumer_name})"
+                    ),
+                ));
+                continue;
+            };
+            let Some(producer_content) = agent_docs.get(producer) else {
+                unresolved.push((
+                    loc,
+                    format!(
+                        "`{receipt}` is written by `{producer}`, which is not in the pack (receipt get expects key `{key}`; referenced in {consumer_name})"
+                    ),
+                ));
+                continue;
+            };
+
+            let schema = schemas
+                .entry(producer.as_str())
+                .or_insert_with(|| receipt_schema_keys(producer_content));
+            if !schema.contains(&key) {
+                drift.push((
+                    loc,
+                    format!(
+                        "`{file}`: consumer expects receipt key `{key}`, but `{producer}` does not document it in its receipt schema"
+                    ),
+                ));
+            }
+        }
+    }
+
+    if drift.is_empty() {
+        rep.pass("receipt get keys appear documented by cleanup agents");
+    } else {
+        for (loc, msg) in drift {
+            match loc {
+                Some(loc) => rep.fail_at(loc, msg),
+                None => rep.fail(msg),
+            }
         }
     }
 
-    for msg in unresolved {
-        rep.warn(msg);
+    for (loc, msg) in unresolved {
+        match loc {
+            Some(loc) => rep.warn_at(loc, msg),
+            None => rep.warn(msg),
+        }
     }
 
     Ok(())
 }
 
-/// Check 50: Command docs only reference declared agent names (frontmatter `name:`).
+/// Check 61: Command docs only reference declared agent names (frontmatter `name:`).
 fn check_flow_agent_name_resolution(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let agent_docs = load_agent_docs(cx)?;
     let declared: HashSet<String> = agent_docs.keys().cloned().collect();
@@ -769,14 +937,10 @@ fn check_flow_agent_name_resolution(cx: &CheckCtx, rep: &mut Reporter) -> anyhow
 
         let mut missing_names: Vec<String> = missing.keys().cloned().collect();
         missing_names.sort();
-        rep.fail(format!(
-            "{rel} references missing agent name(s): {}",
-            missing_names.join(", ")
-        ));
 
         let mut details = Vec::new();
-        for name in missing_names {
-            if let Some(lines) = missing.get(&name) {
+        for name in &missing_names {
+            if let Some(lines) = missing.get(name) {
                 let mut uniq = lines.clone();
                 uniq.sort();
                 uniq.dedup();
@@ -785,10 +949,19 @@ fn check_flow_agent_name_resolution(cx: &CheckCtx, rep: &mut Reporter) -> anyhow
                     .map(|n| n.to_string())
                     .collect::<Vec<_>>()
                     .join(", ");
-                details.push(format!("`{name}` at line(s): {line_list}"));
+                details.push(Finding::new(
+                    Location::file(rel.clone()).with_line(uniq[0]),
+                    format!("`{name}` at line(s): {line_list}"),
+                ));
             }
         }
-        rep.indent_lines(details);
+        rep.fail_findings(
+            format!(
+                "{rel} references missing agent name(s): {}",
+                missing_names.join(", ")
+            ),
+            details,
+        );
     }
 
     Ok(())
@@ -916,7 +1089,7 @@ fn parse_agent_sequence_prefix(s: &str, declared: &HashSet<String>) -> Vec<Strin
     out
 }
 
-fn is_agent_token_candidate(token: &str, declared: &HashSet<String>) -> bool {
+pub(super) fn is_agent_token_candidate(token: &str, declared: &HashSet<String>) -> bool {
     if declared.contains(token) {
         return true;
     }
@@ -964,7 +1137,7 @@ fn is_agent_char(b: u8) -> bool {
     b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-'
 }
 
-fn load_agent_docs(cx: &CheckCtx) -> anyhow::Result<HashMap<String, Arc<str>>> {
+pub(super) fn load_agent_docs(cx: &CheckCtx) -> anyhow::Result<HashMap<String, Arc<str>>> {
     let mut out: HashMap<String, Arc<str>> = HashMap::new();
     for path in &cx.inv.agent_md_files {
         let content = cx.ctx.read_utf8(path)?;
@@ -979,15 +1152,52 @@ fn load_agent_docs(cx: &CheckCtx) -> anyhow::Result<HashMap<String, Arc<str>>> {
     Ok(out)
 }
 
+/// Agent file paths keyed the same way as `load_agent_docs`.
+pub(super) fn load_agent_paths(cx: &CheckCtx) -> anyhow::Result<HashMap<String, PathBuf>> {
+    let mut out: HashMap<String, PathBuf> = HashMap::new();
+    for path in &cx.inv.agent_md_files {
+        let content = cx.ctx.read_utf8(path)?;
+        let name = extract_frontmatter_name(&content).unwrap_or_else(|| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("<unknown>")
+                .to_string()
+        });
+        out.insert(name, path.clone());
+    }
+    Ok(out)
+}
+
+/// Location of a byte offset within a file's content.
+fn offset_location(cx: &CheckCtx, path: &Path, content: &str, offset: usize) -> Location {
+    let (line, column) = line_col_at(content, offset);
+    Location::file(cx.ctx.rel(path))
+        .with_line(line)
+        .with_column(column)
+}
+
+/// Location spanning a (possibly multi-line) regex match.
+fn span_location(
+    cx: &CheckCtx,
+    path: Option<&PathBuf>,
+    content: &str,
+    m: regex::Match<'_>,
+) -> Option<Location> {
+    let path = path?;
+    let (end_line, _) = line_col_at(content, m.end());
+    Some(offset_location(cx, path, content, m.start()).with_end_line(end_line))
+}
+
 fn map_outputs_to_agents(
     agent_docs: &HashMap<String, Arc<str>>,
+    flows: &[String],
 ) -> anyhow::Result<HashMap<String, Vec<String>>> {
     let runs_re = Regex::new(r"\.runs/<run-id>/[A-Za-z0-9_./<>-]+")?;
 
     let mut out: HashMap<String, Vec<String>> = HashMap::new();
     for (agent_name, content) in agent_docs {
         for output_file in extract_output_files(&runs_re, content) {
-            for expanded in expand_flow_placeholders(&output_file) {
+            for expanded in expand_flow_placeholders(&output_file, flows) {
                 out.entry(expanded).or_default().push(agent_name.clone());
             }
         }
@@ -995,17 +1205,16 @@ fn map_outputs_to_agents(
     Ok(out)
 }
 
-fn expand_flow_placeholders(path: &str) -> Vec<String> {
-    const FLOWS: [&str; 6] = ["signal", "plan", "build", "gate", "deploy", "wisdom"];
-
+/// Expand `<flow>` / `<current-flow>` into one path per flow in the pack.
+fn expand_flow_placeholders(path: &str, flows: &[String]) -> Vec<String> {
     if path.contains("/<flow>/") {
-        return FLOWS
+        return flows
             .iter()
             .map(|flow| path.replace("/<flow>/", &format!("/{flow}/")))
             .collect();
     }
     if path.contains("/<current-flow>/") {
-        return FLOWS
+        return flows
             .iter()
             .map(|flow| path.replace("/<current-flow>/", &format!("/{flow}/")))
             .collect();
@@ -1013,7 +1222,7 @@ fn expand_flow_placeholders(path: &str) -> Vec<String> {
     vec![path.to_string()]
 }
 
-fn extract_output_files(runs_re: &Regex, content: &str) -> Vec<String> {
+pub(super) fn extract_output_files(runs_re: &Regex, content: &str) -> Vec<String> {
     let mut out = Vec::new();
     let mut seen = HashSet::<String>::new();
 
@@ -1082,6 +1291,92 @@ fn extract_output_files(runs_re: &Regex, content: &str) -> Vec<String> {
     out
 }
 
+/// Shim subcommands that read the file named by `--file`. Writers (`openq
+/// append`, `secrets redact`) take one too, so `--file` alone is not a read.
+const SHIM_FILE_READERS: [(&str, &str); 8] = [
+    ("count", "pattern"),
+    ("inv", "get"),
+    ("line", "get"),
+    ("ms", "get"),
+    ("openapi", "count-paths"),
+    ("receipt", "get"),
+    ("yaml", "count-items"),
+    ("yaml", "get"),
+];
+
+/// The `--file` a shim invocation reads, if it is a reading subcommand.
+fn shim_read_file(args: &[String]) -> Option<&str> {
+    let [tool, sub, rest @ ..] = args else {
+        return None;
+    };
+    if !SHIM_FILE_READERS.contains(&(tool.as_str(), sub.as_str())) {
+        return None;
+    }
+    rest.iter().enumerate().find_map(|(i, arg)| match arg.strip_prefix("--file") {
+        Some("") => rest.get(i + 1).map(String::as_str),
+        Some(value) => value.strip_prefix('='),
+        None => None,
+    })
+}
+
+/// The `.runs/<run-id>/...` files an agent or command reads: paths in its
+/// Inputs section (the `##` headings check 71 accepts), the `--file` of shim
+/// subcommands that read (`demoswarm.sh ms get --file ...`), and lines that
+/// say they read a path.
+pub(super) fn extract_input_files(runs_re: &Regex, content: &str) -> Vec<String> {
+    let md = Markdown::parse(content);
+    let inputs: Vec<(usize, usize)> = md
+        .sections()
+        .into_iter()
+        .filter(|s| {
+            let heading = &md.headings[s.heading];
+            let text = heading.text.to_lowercase();
+            heading.level == 2 && INPUT_SECTIONS.iter().any(|p| text.starts_with(p))
+        })
+        .map(|s| (s.start, s.end))
+        .collect();
+    let mut shim_reads: HashMap<usize, Vec<String>> = HashMap::new();
+    for inv in shim::extract(&md, Scope::Both) {
+        if let Some(file) = shim_read_file(&inv.args) {
+            shim_reads
+                .entry(inv.line)
+                .or_default()
+                .push(file.to_string());
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut seen = HashSet::<String>::new();
+    let mut push = |text: &str| {
+        for m in runs_re.find_iter(text) {
+            let path = m.as_str().trim_end_matches('.');
+            if !path.ends_with('/') && seen.insert(path.to_string()) {
+                out.push(path.to_string());
+            }
+        }
+    };
+
+    for (line_no, line) in md.lines(Scope::All) {
+        for file in shim_reads.get(&line_no).into_iter().flatten() {
+            push(file);
+        }
+        let in_inputs = inputs
+            .iter()
+            .any(|&(start, end)| line_no > start && line_no <= end);
+        let says_read = line.split(|c: char| !c.is_ascii_alphabetic()).any(|w| {
+            matches!(
+                w.to_ascii_lowercase().as_str(),
+                "read" | "reads" | "reading"
+            )
+        });
+        if in_inputs || says_read {
+            push(line);
+        }
+    }
+
+    out
+}
+
 fn producer_machine_summary_has_key(content: &str, key: &str) -> bool {
     let segments: Vec<&str> = key.split('.').collect();
     for block in extract_machine_summary_blocks(content) {
@@ -1102,7 +1397,72 @@ fn producer_machine_summary_has_key(content: &str, key: &str) -> bool {
     false
 }
 
-fn extract_machine_summary_blocks(content: &str) -> Vec<String> {
+/// Dotted paths (`tests.passed`) of every key in the doc's ```` ```json ````
+/// blocks, where a producer documents its receipt schema.
+fn receipt_schema_keys(content: &str) -> HashSet<String> {
+    let md = Markdown::parse(content);
+    let mut blocks: Vec<(usize, String)> = Vec::new();
+    for (line_no, line) in md.lines(Scope::Code(&["json"])) {
+        let Some(LineKind::Code(idx)) = md.kind(line_no) else {
+            continue;
+        };
+        match blocks.last_mut() {
+            Some((fence, text)) if *fence == idx => {
+                text.push('\n');
+                text.push_str(line);
+            }
+            _ => blocks.push((idx, line.to_string())),
+        }
+    }
+    blocks
+        .iter()
+        .flat_map(|(_, block)| json_key_paths(block))
+        .collect()
+}
+
+/// Dotted paths of the object keys in a JSON-like block: `{"a": {"b": 1}}` has
+/// `a` and `a.b`. Schemas use placeholders (`<int>`, `PASS | FAIL`), so values
+/// are skipped rather than parsed; array elements keep the array's path.
+fn json_key_paths(block: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    // Open objects/arrays, each with the key whose value it is.
+    let mut open: Vec<Option<String>> = Vec::new();
+    let mut key: Option<String> = None;
+
+    let mut chars = block.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let mut s = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => s.extend(chars.next()),
+                        '"' => break,
+                        c => s.push(c),
+                    }
+                }
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                if chars.next_if_eq(&':').is_some() {
+                    let mut path: Vec<&str> = open.iter().flatten().map(String::as_str).collect();
+                    path.push(&s);
+                    paths.push(path.join("."));
+                    key = Some(s);
+                }
+            }
+            '{' | '[' => open.push(key.take()),
+            '}' | ']' => {
+                open.pop();
+                key = None;
+            }
+            ',' => key = None,
+            _ => {}
+        }
+    }
+
+    paths
+}
+
+pub(crate) fn extract_machine_summary_blocks(content: &str) -> Vec<String> {
     let lines: Vec<&str> = content.lines().collect();
     let mut blocks = Vec::new();
 
@@ -1367,14 +1727,23 @@ mod tests {
     // Tests for expand_flow_placeholders
     // =========================================================================
 
+    fn seven_flows() -> Vec<String> {
+        [
+            "signal", "plan", "build", "review", "gate", "deploy", "wisdom",
+        ]
+        .map(String::from)
+        .to_vec()
+    }
+
     #[test]
     fn test_expand_flow_placeholders_with_flow_placeholder() {
-        let result = expand_flow_placeholders(".runs/<run-id>/<flow>/receipt.json");
+        let result = expand_flow_placeholders(".runs/<run-id>/<flow>/receipt.json", &seven_flows());
 
-        assert_eq!(result.len(), 6);
+        assert_eq!(result.len(), 7);
         assert!(result.contains(&".runs/<run-id>/signal/receipt.json".to_string()));
         assert!(result.contains(&".runs/<run-id>/plan/receipt.json".to_string()));
         assert!(result.contains(&".runs/<run-id>/build/receipt.json".to_string()));
+        assert!(result.contains(&".runs/<run-id>/review/receipt.json".to_string()));
         assert!(result.contains(&".runs/<run-id>/gate/receipt.json".to_string()));
         assert!(result.contains(&".runs/<run-id>/deploy/receipt.json".to_string()));
         assert!(result.contains(&".runs/<run-id>/wisdom/receipt.json".to_string()));
@@ -1382,16 +1751,17 @@ mod tests {
 
     #[test]
     fn test_expand_flow_placeholders_with_current_flow_placeholder() {
-        let result = expand_flow_placeholders(".runs/<run-id>/<current-flow>/status.md");
+        let result =
+            expand_flow_placeholders(".runs/<run-id>/<current-flow>/status.md", &seven_flows());
 
-        assert_eq!(result.len(), 6);
+        assert_eq!(result.len(), 7);
         assert!(result.contains(&".runs/<run-id>/signal/status.md".to_string()));
         assert!(result.contains(&".runs/<run-id>/wisdom/status.md".to_string()));
     }
 
     #[test]
     fn test_expand_flow_placeholders_no_placeholder() {
-        let result = expand_flow_placeholders(".runs/<run-id>/build/receipt.json");
+        let result = expand_flow_placeholders(".runs/<run-id>/build/receipt.json", &seven_flows());
 
         assert_eq!(result.len(), 1);
         assert_eq!(result[0], ".runs/<run-id>/build/receipt.json");
@@ -1399,7 +1769,7 @@ mod tests {
 
     #[test]
     fn test_expand_flow_placeholders_empty_path() {
-        let result = expand_flow_placeholders("");
+        let result = expand_flow_placeholders("", &seven_flows());
 
         assert_eq!(result.len(), 1);
         assert_eq!(result[0], "");
@@ -1659,7 +2029,7 @@ status_code: 200
     fn test_map_outputs_to_agents_empty_docs() {
         let agent_docs: HashMap<String, Arc<str>> = HashMap::new();
 
-        let result = map_outputs_to_agents(&agent_docs).unwrap();
+        let result = map_outputs_to_agents(&agent_docs, &seven_flows()).unwrap();
 
         assert!(result.is_empty());
     }
@@ -1672,7 +2042,7 @@ status_code: 200
             Arc::from("# Agent doc with no output paths"),
         );
 
-        let result = map_outputs_to_agents(&agent_docs).unwrap();
+        let result = map_outputs_to_agents(&agent_docs, &seven_flows()).unwrap();
 
         assert!(result.is_empty());
     }
@@ -1689,7 +2059,7 @@ status_code: 200
             Arc::from("output_file: .runs/<run-id>/build/receipt.json"),
         );
 
-        let result = map_outputs_to_agents(&agent_docs).unwrap();
+        let result = map_outputs_to_agents(&agent_docs, &seven_flows()).unwrap();
 
         let producers = result.get(".runs/<run-id>/build/receipt.json").unwrap();
         assert_eq!(producers.len(), 2);
@@ -1704,7 +2074,7 @@ status_code: 200
     #[test]
     fn test_flow_placeholder_preserves_other_placeholders() {
         // Ensures <run-id> is preserved while <flow> is expanded
-        let result = expand_flow_placeholders(".runs/<run-id>/<flow>/file.txt");
+        let result = expand_flow_placeholders(".runs/<run-id>/<flow>/file.txt", &seven_flows());
 
         for path in &result {
             assert!(
@@ -1796,6 +2166,101 @@ status_code: 200
         assert!(result.contains("test-critic"));
     }
 
+    // =========================================================================
+    // Tests for receipt get key contracts (check 81)
+    // =========================================================================
+
+    #[test]
+    fn test_receipt_schema_keys() {
+        let content = "Set `\"verdict\": x` later.\n\n\
+            ```json\n{\n  \"merge_verdict\": \"PASS | FAIL\",\n  \"tests\" : { \"passed\": <int> },\n\
+            \"findings\": [{ \"id\": null }],\n  \"failed\": 0\n}\n```\n\n\
+            ```bash\necho '{\"skipped\": 1}'\n```\n";
+        let mut keys: Vec<String> = receipt_schema_keys(content).into_iter().collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            [
+                "failed",
+                "findings",
+                "findings.id",
+                "merge_verdict",
+                "tests",
+                "tests.passed"
+            ]
+        );
+        // Segments must nest in order, not merely appear.
+        assert!(!keys.contains(&"tests.failed".to_string()));
+        assert!(!keys.contains(&"passed".to_string()));
+    }
+
+    #[test]
+    fn test_check_receipt_get_key_contracts() {
+        use crate::checker::PackChecker;
+        use crate::checks::Selection;
+        use crate::files::MemoryFiles;
+
+        let read = |file: &str, key: &str| {
+            format!(
+                "```bash\nbash .claude/scripts/demoswarm.sh receipt get \\\n  --file \"{file}\" \\\n  --key \"{key}\"\n```\n"
+            )
+        };
+        let files = MemoryFiles::new()
+            .with_file(
+                "pack/.claude/agents/gate-cleanup.md",
+                "---\nname: gate-cleanup\n---\n\n```json\n{ \"merge_verdict\": null, \"counts\": { \"security_findings\": null } }\n```\n",
+            )
+            .with_file(
+                "pack/.claude/agents/deploy-decider.md",
+                format!(
+                    "---\nname: deploy-decider\n---\n\n{}{}{}{}",
+                    read(".runs/<run-id>/gate/gate_receipt.json", "merge_verdict"),
+                    read(".runs/<run-id>/gate/gate_receipt.json", "counts.security_findings"),
+                    read(".runs/<run-id>/gate/gate_receipt.json", "counts.policy_violations"),
+                    read(".runs/<run-id>/build/build_receipt.json", "tests.passed"),
+                ),
+            )
+            .with_file(
+                "pack/.claude/agents/wisdom-analyst.md",
+                format!(
+                    "---\nname: wisdom-analyst\n---\n\n{}{}",
+                    read(".runs/<run-id>/notes.json", "x"),
+                    read(".runs/<run-id>/gate/gate_receipt.json", "counts.policy_violations"),
+                ),
+            );
+        let report = PackChecker::new("pack")
+            .files(files)
+            .selection(Selection {
+                only: vec![81],
+                ..Default::default()
+            })
+            .run()
+            .unwrap();
+
+        let found: Vec<String> = report
+            .diagnostics
+            .iter()
+            .map(|d| {
+                let line = d.location.as_ref().and_then(|l| l.line).unwrap_or(0);
+                format!("{:?} {line}: {}", d.level, d.message)
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                "Fail 16: `.runs/<run-id>/gate/gate_receipt.json`: consumer expects receipt key \
+                 `counts.policy_violations`, but `gate-cleanup` does not document it in its receipt schema",
+                // Another consumer of the same key is reported too.
+                "Fail 11: `.runs/<run-id>/gate/gate_receipt.json`: consumer expects receipt key \
+                 `counts.policy_violations`, but `gate-cleanup` does not document it in its receipt schema",
+                "Warn 21: `build_receipt.json` is written by `build-cleanup`, which is not in the pack \
+                 (receipt get expects key `tests.passed`; referenced in deploy-decider)",
+                "Warn 6: Cannot map `.runs/<run-id>/notes.json` to a cleanup agent \
+                 (receipt get expects key `x`; referenced in wisdom-analyst)",
+            ]
+        );
+    }
+
     // =========================================================================
     // Tests for missing file handling (simulated via empty collections)
     // =========================================================================
@@ -1806,7 +2271,7 @@ status_code: 200
         // The actual load_agent_docs requires a CheckCtx, but we can test
         // that map_outputs_to_agents handles empty input gracefully
         let empty_docs: HashMap<String, Arc<str>> = HashMap::new();
-        let result = map_outputs_to_agents(&empty_docs).unwrap();
+        let result = map_outputs_to_agents(&empty_docs, &seven_flows()).unwrap();
         assert!(result.is_empty());
     }
 
@@ -1888,6 +2353,30 @@ output_file: .runs/<run-id>/valid/path.md
         assert!(result.contains(&".runs/<run-id>/valid/path.md".to_string()));
     }
 
+    #[test]
+    fn test_extract_input_files() {
+        let runs_re = Regex::new(r"\.runs/<run-id>/[A-Za-z0-9_./<>-]+").unwrap();
+        let content = "## Required Inputs\n\n- `.runs/<run-id>/plan/adr.md`\n- `.runs/<run-id>/plan/`\n\
+                       ```md\n## Not a heading\n.runs/<run-id>/plan/fenced.md\n```\n\n\
+                       ## Behavior\n\nRead .runs/<run-id>/build/lint_report.md.\n\
+                       Already done: .runs/<run-id>/build/ignored.md\n\
+                       ```bash\n\
+                       bash .claude/scripts/demoswarm.sh ms get \\\n  --file \".runs/<run-id>/gate/merge_decision.md\"\n\
+                       bash .claude/scripts/demoswarm.sh openq append --file \".runs/<run-id>/signal/open_questions.md\"\n\
+                       ```\n\
+                       Write .runs/<run-id>/build/out.md\n";
+
+        assert_eq!(
+            extract_input_files(&runs_re, content),
+            [
+                ".runs/<run-id>/plan/adr.md",
+                ".runs/<run-id>/plan/fenced.md",
+                ".runs/<run-id>/build/lint_report.md",
+                ".runs/<run-id>/gate/merge_decision.md",
+            ]
+        );
+    }
+
     #[test]
     fn test_extract_output_files_handles_trailing_characters() {
         let runs_re = Regex::new(r"\.runs/<run-id>/[A-Za-z0-9_./<>-]+").unwrap();
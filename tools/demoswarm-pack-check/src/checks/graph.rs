@@ -0,0 +1,1046 @@
+//! Agent handoff graph: commands → agents → routed agents → `.runs/` artifacts.
+//!
+//! Built from the same sources the flow checks read: task lines and
+//! "Agents to use" sections in commands (as in check 61), `route_to_agent:`
+//! values and "route to `agent`" prose in agent docs, declared output files
+//! (as in checks 49 and 60), and the files agents and commands read. Exported with
+//! `--graph dot|mermaid|json`.
+//!
+//! Checks: 65, 66, 67, 82, 83
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::Write as _;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::flow::{
+    extract_input_files, extract_output_files, is_agent_token_candidate, load_agent_docs,
+    load_agent_paths,
+};
+use super::{Category, CheckCtx, CheckSpec};
+use crate::cli::GraphFormat;
+use crate::contracts::headings;
+use crate::reporter::{Finding, Location, Reporter};
+
+pub fn checks() -> Vec<CheckSpec> {
+    vec![
+        CheckSpec {
+            id: 65,
+            title: "Checking every agent is reachable from a command...",
+            category: Category::Flow,
+            rationale: "Every declared agent is reachable from some command.",
+            run: check_unreachable_agents,
+        },
+        CheckSpec {
+            id: 66,
+            title: "Checking agent routing targets exist...",
+            category: Category::Flow,
+            rationale: "`route_to_agent` values and \"route to `agent`\" prose name declared agents.",
+            run: check_route_targets_exist,
+        },
+        CheckSpec {
+            id: 67,
+            title: "Checking agent routing cycles have an iteration guard...",
+            category: Category::Flow,
+            rationale: "Agents that route to each other in a loop bound the loop.\n\
+                \n\
+                A routing cycle is guarded when at least one agent in it has an\n\
+                `## Iteration Control` section, `max_iterations`, or\n\
+                `can_further_iteration_help`. Command-level sequences (reseal loops,\n\
+                microloops) are orchestrated by the command and are not considered.",
+            run: check_routing_cycles_guarded,
+        },
+        CheckSpec {
+            id: 82,
+            title: "Checking every artifact read is produced by an agent...",
+            category: Category::Flow,
+            rationale: "Every `.runs/` artifact an agent or command reads is produced by\n\
+                some agent (its declared outputs, as in checks 49 and 60).",
+            run: check_inputs_produced,
+        },
+        CheckSpec {
+            id: 83,
+            title: "Checking every artifact produced is read by an agent or command...",
+            category: Category::Flow,
+            rationale: "Every `.runs/` artifact an agent produces is read by some agent or\n\
+                command. Advisory: an unread output is dead work, or a reader that lost its\n\
+                input when the artifact was renamed.",
+            run: check_outputs_consumed,
+        },
+    ]
+}
+
+static TASK_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?:-\s*\[\s*[xX ]\s*\]|\d+\))\s*(.+)$").expect("valid task regex")
+});
+static AGENT_TOKEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[a-z][a-z0-9-]+").expect("valid token regex"));
+static ROUTE_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"route_to_agent"?:\s*["'`]?([a-z][a-z0-9-]*)"#).expect("valid route regex")
+});
+static ROUTE_PROSE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:route|hand\s*off|bounce)\s+(?:back\s+)?to\s+\**`([a-z][a-z0-9-]+)`")
+        .expect("valid route prose regex")
+});
+static RUNS_PATH: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\.runs/<run-id>/[A-Za-z0-9_./<>-]+").expect("valid runs path regex")
+});
+static PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<[^>/]*>").expect("valid placeholder regex"));
+
+/// Agent doc markers that bound a routing loop.
+const ITERATION_GUARDS: [&str; 3] = [
+    headings::ITERATION_CONTROL_H2,
+    "max_iterations",
+    "can_further_iteration_help",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    /// A slash command in `.claude/commands/` (flows and utility commands alike).
+    Command,
+    Agent,
+    /// A `.runs/<run-id>/...` file an agent declares as output.
+    Artifact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    /// Command → agent it calls.
+    Invokes,
+    /// Agent → agent called after it in the same command.
+    Next,
+    /// Agent → agent it hands work to (`route_to_agent`).
+    Routes,
+    /// Agent → artifact it writes.
+    Produces,
+    /// Artifact → agent or command that reads it.
+    Reads,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Node {
+    pub id: String,
+    pub kind: NodeKind,
+    pub label: String,
+    /// Defining file (commands and agents).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Routed to, but no agent declares this name.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub missing: bool,
+    /// Agent doc bounds its loops (Iteration Control, max_iterations, ...).
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub iteration_guard: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+    /// Where the edge was read from.
+    pub file: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct HandoffGraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    #[serde(skip)]
+    index: HashMap<String, usize>,
+}
+
+impl HandoffGraph {
+    pub fn build(cx: &CheckCtx) -> anyhow::Result<Self> {
+        let agent_docs = load_agent_docs(cx)?;
+        let agent_paths = load_agent_paths(cx)?;
+        let declared: HashSet<String> = agent_docs.keys().cloned().collect();
+
+        let mut names: Vec<&String> = agent_docs.keys().collect();
+        names.sort();
+
+        let mut g = Self::default();
+        for name in &names {
+            let content = &agent_docs[*name];
+            let node = g.node(NodeKind::Agent, name);
+            node.file = agent_paths.get(*name).map(|p| cx.ctx.rel(p));
+            node.iteration_guard = ITERATION_GUARDS.iter().any(|m| content.contains(m));
+        }
+
+        for cmd in &cx.inv.command_md_files {
+            let rel = cx.ctx.rel(cmd);
+            let stem = cmd
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("<unknown>");
+            let node = g.node(NodeKind::Command, stem);
+            node.file = Some(rel.clone());
+            let from = node.id.clone();
+
+            let content = cx.ctx.read_utf8(cmd)?;
+            g.reads(&from, &content, &rel);
+            let mut prev: Option<String> = None;
+            let mut in_agents_section = false;
+            for (idx, line) in content.lines().enumerate() {
+                let line_no = idx + 1;
+                if let Some(heading) = line.trim_end().strip_prefix("## ") {
+                    let h = heading.trim().to_ascii_lowercase();
+                    in_agents_section = h == "agents to use" || h == "subagents to use";
+                }
+                let task = TASK_LINE.captures(line).map(|c| c.get(1).unwrap().as_str());
+                let Some(text) = task.or(in_agents_section.then_some(line)) else {
+                    continue;
+                };
+
+                for token in AGENT_TOKEN.find_iter(text).map(|m| m.as_str()) {
+                    if !declared.contains(token) {
+                        continue;
+                    }
+                    let to = agent_id(token);
+                    g.edge(&from, &to, EdgeKind::Invokes, &rel, line_no);
+                    if task.is_some() {
+                        if let Some(p) = prev.as_ref().filter(|p| **p != to) {
+                            g.edge(p, &to, EdgeKind::Next, &rel, line_no);
+                        }
+                        prev = Some(to);
+                    }
+                }
+            }
+        }
+
+        for name in &names {
+            let content = &agent_docs[*name];
+            let from = agent_id(name);
+            let rel = agent_paths
+                .get(*name)
+                .map(|p| cx.ctx.rel(p))
+                .unwrap_or_default();
+
+            for (idx, line) in content.lines().enumerate() {
+                let field = ROUTE_FIELD.captures_iter(line).map(|c| (c, true));
+                let prose = ROUTE_PROSE.captures_iter(line).map(|c| (c, false));
+                for (caps, explicit) in field.chain(prose) {
+                    let target = caps.get(1).unwrap().as_str();
+                    if matches!(target, "null" | "none") {
+                        continue;
+                    }
+                    // Prose may route to a flow or a step; only agent-shaped names count.
+                    if !explicit && !is_agent_token_candidate(target, &declared) {
+                        continue;
+                    }
+                    if !declared.contains(target) {
+                        g.node(NodeKind::Agent, target).missing = true;
+                    }
+                    g.edge(&from, &agent_id(target), EdgeKind::Routes, &rel, idx + 1);
+                }
+            }
+
+            for path in extract_output_files(&RUNS_PATH, content) {
+                let line = content
+                    .lines()
+                    .position(|l| l.contains(&path))
+                    .map_or(0, |i| i + 1);
+                let to = g.node(NodeKind::Artifact, &path).id.clone();
+                g.edge(&from, &to, EdgeKind::Produces, &rel, line);
+            }
+            g.reads(&from, content, &rel);
+        }
+
+        g.nodes
+            .sort_by(|a, b| (a.kind, &a.id).cmp(&(b.kind, &b.id)));
+        g.index = g
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.clone(), i))
+            .collect();
+        g.edges
+            .sort_by(|a, b| (a.kind, &a.from, &a.to).cmp(&(b.kind, &b.from, &b.to)));
+        Ok(g)
+    }
+
+    fn node(&mut self, kind: NodeKind, label: &str) -> &mut Node {
+        let id = node_id(kind, label);
+        let idx = match self.index.get(&id) {
+            Some(&idx) => idx,
+            None => {
+                self.index.insert(id.clone(), self.nodes.len());
+                self.nodes.push(Node {
+                    id,
+                    kind,
+                    label: label.to_string(),
+                    file: None,
+                    missing: false,
+                    iteration_guard: false,
+                });
+                self.nodes.len() - 1
+            }
+        };
+        &mut self.nodes[idx]
+    }
+
+    /// Adds a reads edge to `reader` from each artifact its doc reads.
+    fn reads(&mut self, reader: &str, content: &str, file: &str) {
+        for path in extract_input_files(&RUNS_PATH, content) {
+            let line = content
+                .lines()
+                .position(|l| l.contains(&path))
+                .map_or(0, |i| i + 1);
+            let from = self.node(NodeKind::Artifact, &path).id.clone();
+            self.edge(&from, reader, EdgeKind::Reads, file, line);
+        }
+    }
+
+    /// Adds an edge; the first occurrence of (from, to, kind) keeps its location.
+    fn edge(&mut self, from: &str, to: &str, kind: EdgeKind, file: &str, line: usize) {
+        if self
+            .edges
+            .iter()
+            .any(|e| e.from == from && e.to == to && e.kind == kind)
+        {
+            return;
+        }
+        self.edges.push(Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind,
+            file: file.to_string(),
+            line,
+        });
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Node> {
+        self.index.get(id).map(|&i| &self.nodes[i])
+    }
+
+    /// The agents `command` (a command file stem) invokes, in the order it
+    /// first calls them.
+    pub fn invoked_by(&self, command: &str) -> Vec<&str> {
+        let command = node_id(NodeKind::Command, command);
+        let mut calls: Vec<(usize, &str)> = self
+            .edges
+            .iter()
+            .filter(|e| e.kind == EdgeKind::Invokes && e.from == command)
+            .map(|e| (e.line, e.to.trim_start_matches("agent:")))
+            .collect();
+        calls.sort();
+        calls.into_iter().map(|(_, agent)| agent).collect()
+    }
+
+    /// Declared agents no command reaches through invokes, next or routes edges.
+    pub fn unreachable_agents(&self) -> Vec<&Node> {
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<&str> = self
+            .nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Command)
+            .map(|n| n.id.as_str())
+            .collect();
+        while let Some(id) = queue.pop_front() {
+            if !seen.insert(id) {
+                continue;
+            }
+            for e in &self.edges {
+                if e.from == id && e.kind != EdgeKind::Produces {
+                    queue.push_back(&e.to);
+                }
+            }
+        }
+
+        self.nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Agent && !n.missing && !seen.contains(n.id.as_str()))
+            .collect()
+    }
+
+    /// Reads of an artifact no agent produces. A `<placeholder>` in either
+    /// path matches any name (`<flow>/x.md` is produced as `build/x.md`).
+    pub fn orphan_inputs(&self) -> Vec<&Edge> {
+        self.unmatched(EdgeKind::Reads, EdgeKind::Produces)
+    }
+
+    /// Writes of an artifact no agent or command reads.
+    pub fn unused_outputs(&self) -> Vec<&Edge> {
+        self.unmatched(EdgeKind::Produces, EdgeKind::Reads)
+    }
+
+    /// `kind` edges whose artifact has no `other` edge to a matching path.
+    fn unmatched(&self, kind: EdgeKind, other: EdgeKind) -> Vec<&Edge> {
+        let artifact = |e: &Edge| {
+            let id = match e.kind {
+                EdgeKind::Reads => &e.from,
+                _ => &e.to,
+            };
+            id.trim_start_matches("artifact:")
+                .trim_end_matches('.')
+                .to_string()
+        };
+        let others: Vec<PathPattern> = self
+            .edges
+            .iter()
+            .filter(|e| e.kind == other)
+            .map(|e| PathPattern::new(&artifact(e)))
+            .collect();
+        self.edges
+            .iter()
+            .filter(|e| e.kind == kind)
+            .filter(|e| {
+                let path = PathPattern::new(&artifact(e));
+                !others.iter().any(|o| o.matches(&path))
+            })
+            .collect()
+    }
+
+    /// Routing edges whose target no agent declares.
+    pub fn missing_route_targets(&self) -> Vec<&Edge> {
+        self.edges
+            .iter()
+            .filter(|e| e.kind == EdgeKind::Routes)
+            .filter(|e| self.get(&e.to).is_some_and(|n| n.missing))
+            .collect()
+    }
+
+    /// Routing cycles (strongly connected agents) where no member has an
+    /// iteration guard. Each cycle is its agent node IDs, sorted.
+    pub fn unguarded_cycles(&self) -> Vec<Vec<&str>> {
+        let routes: Vec<&Edge> = self
+            .edges
+            .iter()
+            .filter(|e| e.kind == EdgeKind::Routes)
+            .collect();
+
+        strongly_connected(&routes)
+            .into_iter()
+            .filter(|scc| {
+                scc.len() > 1 || routes.iter().any(|e| e.from == scc[0] && e.to == scc[0])
+            })
+            .filter(|scc| {
+                !scc.iter()
+                    .any(|id| self.get(id).is_some_and(|n| n.iteration_guard))
+            })
+            .collect()
+    }
+
+    pub fn render(&self, format: GraphFormat) -> anyhow::Result<String> {
+        Ok(match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Mermaid => self.to_mermaid(),
+            GraphFormat::Json => format!("{}\n", serde_json::to_string_pretty(self)?),
+        })
+    }
+
+    fn to_dot(&self) -> String {
+        let mut out = String::from("digraph handoffs {\n    rankdir=LR;\n    node [shape=box];\n");
+        for n in &self.nodes {
+            let attrs = match (n.kind, n.missing) {
+                (NodeKind::Command, _) => ", shape=folder",
+                (NodeKind::Artifact, _) => ", shape=note",
+                (NodeKind::Agent, true) => ", color=red, style=dashed",
+                (NodeKind::Agent, false) => "",
+            };
+            let _ = writeln!(
+                out,
+                "    {} [label={}{attrs}];",
+                dot_quote(&n.id),
+                dot_quote(&n.label)
+            );
+        }
+        for e in &self.edges {
+            let attrs = match e.kind {
+                EdgeKind::Invokes => "",
+                EdgeKind::Next => " [color=gray]",
+                EdgeKind::Routes => " [style=dashed, label=\"routes\"]",
+                EdgeKind::Produces => " [style=dotted]",
+                EdgeKind::Reads => " [style=dotted, color=gray]",
+            };
+            let _ = writeln!(
+                out,
+                "    {} -> {}{attrs};",
+                dot_quote(&e.from),
+                dot_quote(&e.to)
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart LR\n");
+        for (i, n) in self.nodes.iter().enumerate() {
+            let label = mermaid_escape(&n.label);
+            let _ = match n.kind {
+                NodeKind::Command => writeln!(out, "    n{i}[[\"{label}\"]]"),
+                NodeKind::Agent => writeln!(out, "    n{i}[\"{label}\"]"),
+                NodeKind::Artifact => writeln!(out, "    n{i}[/\"{label}\"/]"),
+            };
+        }
+        for e in &self.edges {
+            let arrow = match e.kind {
+                EdgeKind::Invokes | EdgeKind::Next => "-->",
+                EdgeKind::Routes => "-.->|routes|",
+                EdgeKind::Produces | EdgeKind::Reads => "-.->",
+            };
+            let _ = writeln!(
+                out,
+                "    n{} {arrow} n{}",
+                self.index[&e.from], self.index[&e.to]
+            );
+        }
+        let missing: Vec<String> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.missing)
+            .map(|(i, _)| format!("n{i}"))
+            .collect();
+        if !missing.is_empty() {
+            out.push_str("    classDef missing stroke:#d00,stroke-dasharray:4\n");
+            let _ = writeln!(out, "    class {} missing", missing.join(","));
+        }
+        out
+    }
+}
+
+fn node_id(kind: NodeKind, label: &str) -> String {
+    match kind {
+        NodeKind::Command => format!("command:{label}"),
+        NodeKind::Agent => agent_id(label),
+        NodeKind::Artifact => format!("artifact:{label}"),
+    }
+}
+
+fn agent_id(name: &str) -> String {
+    format!("agent:{name}")
+}
+
+/// A `.runs/` path compiled once for matching against others, segment by
+/// segment. A `<placeholder>` matches any text within its segment.
+struct PathPattern {
+    segments: Vec<Segment>,
+}
+
+enum Segment {
+    Literal(String),
+    /// A segment with placeholders: what it can stand for, and its shape with
+    /// the placeholders blanked (`<>_receipt.json`) for comparing two of them.
+    Placeholders { regex: Regex, shape: String },
+}
+
+impl PathPattern {
+    fn new(path: &str) -> Self {
+        let segments = path
+            .split('/')
+            .map(|segment| {
+                if !PLACEHOLDER.is_match(segment) {
+                    return Segment::Literal(segment.to_string());
+                }
+                let literals: Vec<String> =
+                    PLACEHOLDER.split(segment).map(regex::escape).collect();
+                Segment::Placeholders {
+                    regex: Regex::new(&format!("^{}$", literals.join("[^/]*")))
+                        .expect("escaped segment pattern"),
+                    shape: PLACEHOLDER.replace_all(segment, "<>").into_owned(),
+                }
+            })
+            .collect();
+        Self { segments }
+    }
+
+    /// Whether the two paths can name the same file.
+    fn matches(&self, other: &Self) -> bool {
+        self.segments.len() == other.segments.len()
+            && self
+                .segments
+                .iter()
+                .zip(&other.segments)
+                .all(|pair| match pair {
+                    (Segment::Literal(a), Segment::Literal(b)) => a == b,
+                    (Segment::Placeholders { regex, .. }, Segment::Literal(s))
+                    | (Segment::Literal(s), Segment::Placeholders { regex, .. }) => {
+                        regex.is_match(s)
+                    }
+                    (
+                        Segment::Placeholders { shape: a, .. },
+                        Segment::Placeholders { shape: b, .. },
+                    ) => a == b,
+                })
+    }
+}
+
+fn dot_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn mermaid_escape(s: &str) -> String {
+    s.replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+/// Tarjan's strongly connected components over the routing edges.
+fn strongly_connected<'a>(edges: &[&'a Edge]) -> Vec<Vec<&'a str>> {
+    struct State<'a> {
+        adj: HashMap<&'a str, Vec<&'a str>>,
+        index: HashMap<&'a str, usize>,
+        low: HashMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: HashSet<&'a str>,
+        out: Vec<Vec<&'a str>>,
+    }
+
+    fn visit<'a>(s: &mut State<'a>, v: &'a str) {
+        let i = s.index.len();
+        s.index.insert(v, i);
+        s.low.insert(v, i);
+        s.stack.push(v);
+        s.on_stack.insert(v);
+
+        for w in s.adj.get(v).cloned().unwrap_or_default() {
+            if !s.index.contains_key(w) {
+                visit(s, w);
+                let lw = s.low[w];
+                let lv = s.low.get_mut(v).expect("visited");
+                *lv = (*lv).min(lw);
+            } else if s.on_stack.contains(w) {
+                let iw = s.index[w];
+                let lv = s.low.get_mut(v).expect("visited");
+                *lv = (*lv).min(iw);
+            }
+        }
+
+        if s.low[v] == s.index[v] {
+            let mut scc = Vec::new();
+            while let Some(w) = s.stack.pop() {
+                s.on_stack.remove(w);
+                scc.push(w);
+                if w == v {
+                    break;
+                }
+            }
+            scc.sort_unstable();
+            s.out.push(scc);
+        }
+    }
+
+    let mut s = State {
+        adj: HashMap::new(),
+        index: HashMap::new(),
+        low: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: HashSet::new(),
+        out: Vec::new(),
+    };
+    let mut vertices = BTreeSet::new();
+    for e in edges {
+        s.adj.entry(&e.from).or_default().push(&e.to);
+        vertices.insert(e.from.as_str());
+        vertices.insert(e.to.as_str());
+    }
+    for v in vertices {
+        if !s.index.contains_key(v) {
+            visit(&mut s, v);
+        }
+    }
+    s.out.sort();
+    s.out
+}
+
+/// Check 65: Every declared agent is reachable from some command.
+fn check_unreachable_agents(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let g = HandoffGraph::build(cx)?;
+    if cx.inv.command_md_files.is_empty() {
+        rep.pass("No commands (reachability not checked)");
+        return Ok(());
+    }
+
+    let unreachable: Vec<Finding> = g
+        .unreachable_agents()
+        .into_iter()
+        .map(|n| {
+            Finding::new(
+                Location::file(n.file.clone().unwrap_or_default()),
+                format!(
+                    "`{}` (no command invokes it and no agent routes to it)",
+                    n.label
+                ),
+            )
+        })
+        .collect();
+
+    if unreachable.is_empty() {
+        rep.pass("All agents are reachable from a command");
+    } else {
+        rep.warn_findings("Found agents unreachable from any command:", unreachable);
+    }
+
+    Ok(())
+}
+
+/// Check 66: `route_to_agent` values and "route to `agent`" prose name declared agents.
+fn check_route_targets_exist(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let g = HandoffGraph::build(cx)?;
+
+    let missing: Vec<Finding> = g
+        .missing_route_targets()
+        .into_iter()
+        .map(|e| {
+            Finding::new(
+                Location::file(e.file.clone()).with_line(e.line),
+                format!(
+                    "routes to `{}`, which is not a declared agent",
+                    e.to.trim_start_matches("agent:")
+                ),
+            )
+        })
+        .collect();
+
+    if missing.is_empty() {
+        rep.pass("All routing targets are declared agents");
+    } else {
+        rep.fail_findings("Found routing targets that do not exist:", missing);
+    }
+
+    Ok(())
+}
+
+/// Check 67: Agents that route to each other in a loop bound the loop.
+fn check_routing_cycles_guarded(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let g = HandoffGraph::build(cx)?;
+
+    let cycles: Vec<Finding> = g
+        .unguarded_cycles()
+        .into_iter()
+        .map(|scc| {
+            let names: Vec<&str> = scc
+                .iter()
+                .map(|id| id.trim_start_matches("agent:"))
+                .collect();
+            let file = g
+                .get(scc[0])
+                .and_then(|n| n.file.clone())
+                .unwrap_or_default();
+            Finding::new(
+                Location::file(file),
+                format!(
+                    "{} (no agent in the cycle has an iteration guard)",
+                    names.join(" <-> ")
+                ),
+            )
+        })
+        .collect();
+
+    if cycles.is_empty() {
+        rep.pass("All routing cycles have an iteration guard");
+    } else {
+        rep.warn_findings("Found routing cycles without an iteration guard:", cycles);
+    }
+
+    Ok(())
+}
+
+/// Check 82: Every `.runs/` artifact an agent or command reads is produced by
+/// some agent.
+fn check_inputs_produced(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let g = HandoffGraph::build(cx)?;
+
+    let orphans: Vec<Finding> = g
+        .orphan_inputs()
+        .into_iter()
+        .map(|e| {
+            Finding::new(
+                Location::file(e.file.clone()).with_line(e.line),
+                format!(
+                    "reads `{}`, which no agent produces",
+                    e.from.trim_start_matches("artifact:")
+                ),
+            )
+        })
+        .collect();
+
+    if orphans.is_empty() {
+        rep.pass("All artifacts read are produced by an agent");
+    } else {
+        rep.warn_findings("Found artifacts read but never produced:", orphans);
+    }
+
+    Ok(())
+}
+
+/// Check 83: Every `.runs/` artifact an agent produces is read by some agent or
+/// command.
+fn check_outputs_consumed(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let g = HandoffGraph::build(cx)?;
+
+    let unused: Vec<Finding> = g
+        .unused_outputs()
+        .into_iter()
+        .map(|e| {
+            Finding::new(
+                Location::file(e.file.clone()).with_line(e.line),
+                format!(
+                    "produces `{}`, which no agent or command reads",
+                    e.to.trim_start_matches("artifact:")
+                ),
+            )
+        })
+        .collect();
+
+    if unused.is_empty() {
+        rep.pass("All artifacts produced are read by an agent or command");
+    } else {
+        rep.warn_findings("Found artifacts produced but never read:", unused);
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::OutputFormat;
+    use crate::contracts::Contracts;
+    use crate::contracts::test_utils::REGEXES;
+    use crate::ctx::Ctx;
+    use crate::inventory::Inventory;
+    use tempfile::TempDir;
+
+    fn agent(name: &str, body: &str) -> (String, String) {
+        (
+            format!("agents/{name}.md"),
+            format!("---\nname: {name}\n---\n\n# {name}\n\n{body}\n"),
+        )
+    }
+
+    fn with_pack<T>(files: &[(String, String)], f: impl FnOnce(&CheckCtx) -> T) -> T {
+        let temp = TempDir::new().unwrap();
+        let claude_dir = temp.path().join(".claude");
+        for dir in ["agents", "commands", "skills"] {
+            std::fs::create_dir_all(claude_dir.join(dir)).unwrap();
+        }
+        for (rel, content) in files {
+            std::fs::write(claude_dir.join(rel), content).unwrap();
+        }
+
+        let ctx = Ctx::discover(Some(temp.path().to_path_buf())).unwrap();
+        let inv = Inventory::from_ctx(&ctx).unwrap();
+        let c = Contracts::default();
+        let cx = CheckCtx {
+            ctx: &ctx,
+            inv: &inv,
+            re: &REGEXES,
+            c: &c,
+        };
+        f(&cx)
+    }
+
+    fn sample_pack() -> Vec<(String, String)> {
+        vec![
+            (
+                "commands/flow-1-signal.md".to_string(),
+                "# Flow 1\n\n## Agents to use\n\n- signal-cleanup\n\n## Steps\n\n\
+                 - [ ] requirements-author / requirements-critic (microloop)\n\
+                 - [ ] signal-cleanup\n"
+                    .to_string(),
+            ),
+            agent(
+                "requirements-author",
+                "Write `.runs/<run-id>/signal/requirements.md`.\n\nroute_to_agent: requirements-critic",
+            ),
+            agent(
+                "requirements-critic",
+                "If gaps remain, route back to `requirements-author`.\n\n\
+                 ## Iteration Control\n\nmax_iterations: 3",
+            ),
+            agent(
+                "signal-cleanup",
+                "route_to_agent: null\nroute_to_agent: ghost-critic",
+            ),
+            agent("orphan-analyst", "Never invoked."),
+            agent("loop-author", "route_to_agent: loop-critic"),
+            agent("loop-critic", "route_to_agent: loop-author"),
+        ]
+    }
+
+    #[test]
+    fn test_build_extracts_edges() {
+        with_pack(&sample_pack(), |cx| {
+            let g = HandoffGraph::build(cx).unwrap();
+            let has = |from: &str, to: &str, kind| {
+                g.edges
+                    .iter()
+                    .any(|e| e.from == from && e.to == to && e.kind == kind)
+            };
+
+            assert!(has(
+                "command:flow-1-signal",
+                "agent:signal-cleanup",
+                EdgeKind::Invokes
+            ));
+            assert!(has(
+                "agent:requirements-author",
+                "agent:requirements-critic",
+                EdgeKind::Next
+            ));
+            assert!(has(
+                "agent:requirements-critic",
+                "agent:signal-cleanup",
+                EdgeKind::Next
+            ));
+            assert!(has(
+                "agent:requirements-critic",
+                "agent:requirements-author",
+                EdgeKind::Routes
+            ));
+            assert!(has(
+                "agent:requirements-author",
+                "artifact:.runs/<run-id>/signal/requirements.md",
+                EdgeKind::Produces
+            ));
+            assert!(g.get("agent:requirements-critic").unwrap().iteration_guard);
+        });
+    }
+
+    #[test]
+    fn test_graph_analyses() {
+        with_pack(&sample_pack(), |cx| {
+            let g = HandoffGraph::build(cx).unwrap();
+
+            let unreachable: Vec<&str> = g
+                .unreachable_agents()
+                .iter()
+                .map(|n| n.label.as_str())
+                .collect();
+            assert_eq!(
+                unreachable,
+                vec!["loop-author", "loop-critic", "orphan-analyst"]
+            );
+
+            let missing = g.missing_route_targets();
+            assert_eq!(missing.len(), 1);
+            assert_eq!(missing[0].to, "agent:ghost-critic");
+            assert_eq!(missing[0].file, ".claude/agents/signal-cleanup.md");
+            assert_eq!(missing[0].line, 8);
+
+            // The requirements loop is guarded by the critic; the loop pair is not.
+            assert_eq!(
+                g.unguarded_cycles(),
+                vec![vec!["agent:loop-author", "agent:loop-critic"]]
+            );
+        });
+    }
+
+    #[test]
+    fn test_checks_report() {
+        with_pack(&sample_pack(), |cx| {
+            let mut rep = Reporter::new(OutputFormat::Json, false, false);
+            check_unreachable_agents(cx, &mut rep).unwrap();
+            check_route_targets_exist(cx, &mut rep).unwrap();
+            check_routing_cycles_guarded(cx, &mut rep).unwrap();
+            assert_eq!(rep.warnings, 4);
+            assert_eq!(rep.errors, 1);
+        });
+    }
+
+    #[test]
+    fn test_data_flow_analyses() {
+        let files = vec![
+            (
+                "commands/flow-1-signal.md".to_string(),
+                "# Flow 1\n\n## Steps\n\n- [ ] requirements-author\n- [ ] requirements-critic\n"
+                    .to_string(),
+            ),
+            agent(
+                "requirements-author",
+                "## Outputs\n\n- `.runs/<run-id>/signal/requirements.md`\n- `.runs/<run-id>/signal/notes.md`",
+            ),
+            agent(
+                "requirements-critic",
+                "## Inputs\n\n- `.runs/<run-id>/<flow>/requirements.md`\n- `.runs/<run-id>/build/lint_report.md`",
+            ),
+        ];
+        with_pack(&files, |cx| {
+            let g = HandoffGraph::build(cx).unwrap();
+            assert!(g.edges.iter().any(|e| e.kind == EdgeKind::Reads
+                && e.from == "artifact:.runs/<run-id>/build/lint_report.md"
+                && e.to == "agent:requirements-critic"));
+
+            let orphans: Vec<(&str, usize)> = g
+                .orphan_inputs()
+                .iter()
+                .map(|e| (e.from.as_str(), e.line))
+                .collect();
+            assert_eq!(
+                orphans,
+                [("artifact:.runs/<run-id>/build/lint_report.md", 10)]
+            );
+            let unused: Vec<&str> = g.unused_outputs().iter().map(|e| e.to.as_str()).collect();
+            assert_eq!(unused, ["artifact:.runs/<run-id>/signal/notes.md"]);
+
+            let mut rep = Reporter::new(OutputFormat::Json, false, false);
+            check_inputs_produced(cx, &mut rep).unwrap();
+            check_outputs_consumed(cx, &mut rep).unwrap();
+            assert_eq!((rep.warnings, rep.errors), (2, 0));
+        });
+    }
+
+    #[test]
+    fn test_paths_match_placeholders() {
+        let paths_match = |a: &str, b: &str| PathPattern::new(a).matches(&PathPattern::new(b));
+        assert!(paths_match(
+            ".runs/<run-id>/build/a.md",
+            ".runs/<run-id>/build/a.md"
+        ));
+        assert!(paths_match(
+            ".runs/<run-id>/<flow>/a.md",
+            ".runs/<run-id>/gate/a.md"
+        ));
+        assert!(paths_match(
+            ".runs/<run-id>/gate/<flow>_receipt.json",
+            ".runs/<run-id>/gate/gate_receipt.json"
+        ));
+        assert!(!paths_match(
+            ".runs/<run-id>/<flow>/a.md",
+            ".runs/<run-id>/gate/b.md"
+        ));
+        assert!(!paths_match(
+            ".runs/<run-id>/<flow>/a.md",
+            ".runs/<run-id>/a.md"
+        ));
+    }
+
+    #[test]
+    fn test_render_formats() {
+        with_pack(&sample_pack(), |cx| {
+            let g = HandoffGraph::build(cx).unwrap();
+
+            let dot = g.render(GraphFormat::Dot).unwrap();
+            assert!(dot.starts_with("digraph handoffs {"));
+            assert!(dot.contains(
+                "\"agent:signal-cleanup\" -> \"agent:ghost-critic\" [style=dashed, label=\"routes\"];"
+            ));
+            assert!(dot.contains(
+                "\"agent:ghost-critic\" [label=\"ghost-critic\", color=red, style=dashed];"
+            ));
+
+            let mermaid = g.render(GraphFormat::Mermaid).unwrap();
+            assert!(mermaid.starts_with("flowchart LR\n"));
+            assert!(mermaid.contains("[/\".runs/#lt;run-id#gt;/signal/requirements.md\"/]"));
+            assert!(mermaid.contains("class "));
+
+            let json: serde_json::Value =
+                serde_json::from_str(&g.render(GraphFormat::Json).unwrap()).unwrap();
+            assert_eq!(json["nodes"].as_array().unwrap().len(), g.nodes.len());
+            assert_eq!(json["edges"][0]["kind"], "invokes");
+        });
+    }
+}
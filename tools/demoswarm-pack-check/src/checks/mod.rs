@@ -2,18 +2,54 @@
 //!
 //! Each check is a function that takes (CheckCtx, Reporter) and emits diagnostics.
 //! The registry returns all checks in numeric order.
+//!
+//! Check IDs are globally unique and stable: a retired check's ID is never reused,
+//! and new checks take the next free number (currently 84). ID 63 is reserved for
+//! the unused-suppression report, which the runner emits after every check ran.
+
+/// Declares the check modules, and (for tests that scan check code) their
+/// sources, so the two lists cannot drift apart.
+macro_rules! check_modules {
+    ($($module:ident),* $(,)?) => {
+        $(mod $module;)*
+
+        /// `(module, source)` for every check module.
+        #[cfg(test)]
+        const MODULE_SOURCES: &[(&str, &str)] = &[
+            $((stringify!($module), include_str!(concat!(stringify!($module), ".rs")))),*
+        ];
+    };
+}
 
-mod contracts;
-mod control_plane;
-mod drift;
-mod flow;
-mod structure;
-mod wisdom;
+check_modules! {
+    contracts,
+    control_plane,
+    docs,
+    drift,
+    flow,
+    graph,
+    runs,
+    structure,
+    wisdom,
+}
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use anyhow::bail;
+use clap::ValueEnum;
 
 use super::contracts::{Contracts, Regexes};
 use crate::ctx::Ctx;
 use crate::inventory::Inventory;
 use crate::reporter::Reporter;
+use crate::topology::FlowTopology;
+
+pub use docs::generate_docs;
+pub(crate) use docs::pack_entries;
+pub(crate) use flow::extract_machine_summary_blocks;
+pub use graph::HandoffGraph;
 
 /// Shared context for all checks - avoids signature sprawl.
 pub struct CheckCtx<'a> {
@@ -23,36 +59,380 @@ pub struct CheckCtx<'a> {
     pub c: &'a Contracts,
 }
 
+impl CheckCtx<'_> {
+    /// The pack's flows, from its flow commands and the cleanup contracts.
+    pub fn flows(&self) -> FlowTopology {
+        FlowTopology::discover(self.inv, self.c)
+    }
+}
+
 /// Check function signature.
 pub type CheckFn = fn(&CheckCtx, &mut Reporter) -> anyhow::Result<()>;
 
+/// Which area of the pack a check covers (mirrors the check modules).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Category {
+    Structure,
+    #[value(name = "control_plane", alias = "control-plane")]
+    ControlPlane,
+    Drift,
+    Flow,
+    Wisdom,
+    /// Compare `docs/` against the pack; runs only with `--docs-root`.
+    Docs,
+    /// Validate `.runs/` (index, run metadata, receipts); runs only in `pack-check runs`.
+    Runs,
+}
+
+impl Category {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Category::Structure => "structure",
+            Category::ControlPlane => "control_plane",
+            Category::Drift => "drift",
+            Category::Flow => "flow",
+            Category::Wisdom => "wisdom",
+            Category::Docs => "docs",
+            Category::Runs => "runs",
+        }
+    }
+}
+
 /// A single check specification.
+#[derive(Clone)]
 pub struct CheckSpec {
     pub id: u32,
     pub title: &'static str,
+    pub category: Category,
+    /// Why the check exists, as markdown: first line a summary, then any detail.
+    /// Shown by editors on hover.
+    pub rationale: &'static str,
     pub run: CheckFn,
 }
 
-/// Returns all checks in numeric order (1..53).
+/// How a fixer rewrites the lines of a file.
+#[derive(Clone, Copy)]
+pub enum Rewrite {
+    /// Replace a line; `None` leaves it unchanged.
+    Line(fn(&CheckCtx, &str) -> Option<String>),
+    /// Join a line with the one after it. Applied after every `Line` rewrite.
+    JoinNext(fn(&CheckCtx, &str) -> bool),
+}
+
+/// A mechanical rewrite that clears a check's findings (`--fix`).
+pub struct FixSpec {
+    pub check_id: u32,
+    /// The files the rewrite applies to (the check's scan scope).
+    pub files: fn(&CheckCtx) -> Vec<PathBuf>,
+    pub rewrite: Rewrite,
+}
+
+/// Returns all fixers in check-ID order.
+pub fn fixers() -> Vec<FixSpec> {
+    let mut fixers = drift::fixers();
+    fixers.sort_by_key(|f| f.check_id);
+    fixers
+}
+
+/// Pseudo-check: inline suppression directives that matched nothing or do not
+/// parse. Not in `all()`, because it has to run after every other check, but
+/// `--only`/`--skip` and directives accept its ID.
+pub const UNUSED_SUPPRESSION_ID: u32 = 63;
+pub const UNUSED_SUPPRESSION_TITLE: &str = "Checking suppression directives are used...";
+
+static KNOWN_IDS: LazyLock<HashSet<u32>> = LazyLock::new(|| {
+    all()
+        .iter()
+        .map(|c| c.id)
+        .chain([UNUSED_SUPPRESSION_ID])
+        .collect()
+});
+
+/// Whether `id` names a registered check or the unused-suppression pseudo-check.
+pub fn is_known_id(id: u32) -> bool {
+    KNOWN_IDS.contains(&id)
+}
+
+/// Returns all checks in numeric order.
+///
+/// Panics if two checks register the same ID; IDs are how operators, baselines
+/// and SARIF rules refer to checks, so a collision is a programming error.
 pub fn all() -> Vec<CheckSpec> {
     let mut checks = Vec::new();
 
-    // Structure checks (1, 2, 6, 9, 10, 15)
+    // Structure checks (1, 2, 6, 9, 10, 11, 15, 68, 69, 70)
     checks.extend(structure::checks());
 
-    // Control-plane checks (3, 4, 16, 17, 18, 19, 20, 21, 28, 29, 31, 32, 33, 34, 35)
+    // Control-plane checks (4, 17, 18, 19, 32, 54, 55, 71, 72)
     checks.extend(control_plane::checks());
 
-    // Drift checks (7, 8, 14, 23, 30, 38, 39, 40, 42, 45, 46, 47, 48, 49, 50, 52, 53)
+    // Drift checks (7, 8, 14, 23, 30, 38, 39, 40, 42, 45, 46, 47, 48, 50, 52, 53, 62, 64, 73)
     checks.extend(drift::checks());
 
-    // Flow checks (5, 11, 12, 13, 22, 25, 26, 27, 37, 43, 44, 45, 46, 47, 48, 49, 50)
+    // Flow checks (5, 12, 13, 22, 25, 26, 43, 44, 49, 56, 57, 59, 60, 61, 81)
     checks.extend(flow::checks());
 
+    // Handoff graph checks (65, 66, 67, 82, 83)
+    checks.extend(graph::checks());
+
     // Wisdom checks (24, 36, 41)
     checks.extend(wisdom::checks());
 
+    // Docs drift checks (74, 75, 76), run only with --docs-root
+    checks.extend(docs::checks());
+
+    // Run artifact checks (77, 78, 79, 80), run only by `pack-check runs`
+    checks.extend(runs::checks());
+
+    let mut seen: HashMap<u32, &'static str> = HashMap::new();
+    for check in &checks {
+        if let Some(prev) = seen.insert(check.id, check.title) {
+            panic!(
+                "duplicate check id {}: {:?} and {:?}",
+                check.id, prev, check.title
+            );
+        }
+    }
+
     // Sort by ID to ensure consistent ordering
     checks.sort_by_key(|c| c.id);
     checks
 }
+
+/// Why check `id` exists (see `CheckSpec::rationale`).
+pub fn rationale(id: u32) -> Option<&'static str> {
+    all().into_iter().find(|c| c.id == id).map(|c| c.rationale)
+}
+
+/// Which checks to run, from `--only`, `--skip`, `--category` and `--skip-category`.
+///
+/// `only` and `categories` are a union (empty means "everything"); the skip lists
+/// are applied afterwards and always win.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selection {
+    pub only: Vec<u32>,
+    pub skip: Vec<u32>,
+    pub categories: Vec<Category>,
+    pub skip_categories: Vec<Category>,
+}
+
+impl Selection {
+    pub fn includes(&self, check: &CheckSpec) -> bool {
+        let wanted = (self.only.is_empty() && self.categories.is_empty())
+            || self.only.contains(&check.id)
+            || self.categories.contains(&check.category);
+
+        wanted && !self.skip.contains(&check.id) && !self.skip_categories.contains(&check.category)
+    }
+
+    /// Whether unused suppression directives (63) are reported.
+    pub fn includes_unused_suppressions(&self) -> bool {
+        let wanted = (self.only.is_empty() && self.categories.is_empty())
+            || self.only.contains(&UNUSED_SUPPRESSION_ID);
+        wanted && !self.skip.contains(&UNUSED_SUPPRESSION_ID)
+    }
+
+    /// `--only 63` and nothing else: every check runs, so directives can be
+    /// judged, but only the unused-suppression report is shown.
+    pub fn only_unused_suppressions(&self) -> bool {
+        !self.only.is_empty()
+            && self.only.iter().all(|&id| id == UNUSED_SUPPRESSION_ID)
+            && self.categories.is_empty()
+    }
+}
+
+/// Returns the registered checks that `sel` includes, in numeric order.
+///
+/// Unknown IDs are an error rather than a silent no-op, so a typo in `--only`
+/// can't turn into a green run that checked nothing.
+pub fn select(sel: &Selection) -> anyhow::Result<Vec<CheckSpec>> {
+    for id in sel.only.iter().chain(&sel.skip) {
+        if !is_known_id(*id) {
+            bail!("unknown check id: {id}");
+        }
+    }
+
+    let selected: Vec<CheckSpec> = if sel.only_unused_suppressions() {
+        let every = Selection {
+            only: Vec::new(),
+            ..sel.clone()
+        };
+        all().into_iter().filter(|c| every.includes(c)).collect()
+    } else {
+        all().into_iter().filter(|c| sel.includes(c)).collect()
+    };
+    if selected.is_empty() {
+        bail!("no checks selected");
+    }
+    Ok(selected)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(checks: &[CheckSpec]) -> Vec<u32> {
+        checks.iter().map(|c| c.id).collect()
+    }
+
+    #[test]
+    fn test_all_ids_unique_and_sorted() {
+        let ids = ids(&all());
+        let mut sorted = ids.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(ids, sorted);
+    }
+
+    #[test]
+    fn test_all_categories_match_modules() {
+        let checks = all();
+        let category_of = |id: u32| checks.iter().find(|c| c.id == id).unwrap().category;
+
+        assert_eq!(category_of(1), Category::Structure);
+        assert_eq!(category_of(4), Category::ControlPlane);
+        assert_eq!(category_of(7), Category::Drift);
+        assert_eq!(category_of(5), Category::Flow);
+        assert_eq!(category_of(24), Category::Wisdom);
+    }
+
+    #[test]
+    fn test_fixers_target_registered_checks() {
+        let checks = all();
+        for fixer in fixers() {
+            assert!(
+                checks.iter().any(|c| c.id == fixer.check_id),
+                "fixer for unknown check {}",
+                fixer.check_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_check_has_a_rationale() {
+        for check in all() {
+            assert!(
+                !check.rationale.trim().is_empty(),
+                "check {} has no rationale",
+                check.id
+            );
+        }
+
+        let r = rationale(55).unwrap();
+        assert!(r.starts_with("Agents have clear job section.\n\n"), "{r}");
+        assert!(rationale(UNUSED_SUPPRESSION_ID).is_none());
+    }
+
+    #[test]
+    fn test_select_default_is_everything() {
+        let selected = select(&Selection::default()).unwrap();
+        assert_eq!(selected.len(), all().len());
+    }
+
+    #[test]
+    fn test_select_only_ids() {
+        let sel = Selection {
+            only: vec![53, 7],
+            ..Default::default()
+        };
+        assert_eq!(ids(&select(&sel).unwrap()), vec![7, 53]);
+    }
+
+    #[test]
+    fn test_select_category_union_with_only() {
+        let sel = Selection {
+            only: vec![1],
+            categories: vec![Category::Wisdom],
+            ..Default::default()
+        };
+        assert_eq!(ids(&select(&sel).unwrap()), vec![1, 24, 36, 41]);
+    }
+
+    #[test]
+    fn test_select_skip_wins() {
+        let sel = Selection {
+            categories: vec![Category::Wisdom],
+            skip: vec![36],
+            ..Default::default()
+        };
+        assert_eq!(ids(&select(&sel).unwrap()), vec![24, 41]);
+    }
+
+    #[test]
+    fn test_select_accepts_unused_suppression_id() {
+        // `--only 63` runs everything (to judge directives); `--skip 63` is a no-op here.
+        let only = Selection {
+            only: vec![UNUSED_SUPPRESSION_ID],
+            ..Default::default()
+        };
+        assert_eq!(select(&only).unwrap().len(), all().len());
+        assert!(only.only_unused_suppressions() && only.includes_unused_suppressions());
+
+        let skip = Selection {
+            skip: vec![UNUSED_SUPPRESSION_ID],
+            ..Default::default()
+        };
+        assert_eq!(select(&skip).unwrap().len(), all().len());
+        assert!(!skip.includes_unused_suppressions());
+
+        let with_other = Selection {
+            only: vec![7, UNUSED_SUPPRESSION_ID],
+            ..Default::default()
+        };
+        assert_eq!(ids(&select(&with_other).unwrap()), vec![7]);
+        assert!(with_other.includes_unused_suppressions());
+        assert!(!Selection::default().only_unused_suppressions());
+    }
+
+    #[test]
+    fn test_select_skip_category() {
+        let sel = Selection {
+            skip_categories: vec![Category::Drift, Category::Flow],
+            ..Default::default()
+        };
+        let selected = select(&sel).unwrap();
+        assert!(!selected.is_empty());
+        assert!(
+            selected
+                .iter()
+                .all(|c| c.category != Category::Drift && c.category != Category::Flow)
+        );
+    }
+
+    #[test]
+    fn test_select_unknown_id_is_error() {
+        let sel = Selection {
+            only: vec![999],
+            ..Default::default()
+        };
+        let err = select(&sel).err().unwrap().to_string();
+        assert!(err.contains("999"), "{err}");
+    }
+
+    #[test]
+    fn test_select_nothing_is_error() {
+        let sel = Selection {
+            categories: vec![Category::Wisdom],
+            skip_categories: vec![Category::Wisdom],
+            ..Default::default()
+        };
+        assert!(select(&sel).is_err());
+    }
+
+    #[test]
+    fn test_category_value_names() {
+        assert_eq!(
+            Category::from_str("control_plane", false).unwrap(),
+            Category::ControlPlane
+        );
+        assert_eq!(
+            Category::from_str("control-plane", false).unwrap(),
+            Category::ControlPlane
+        );
+        assert_eq!(Category::from_str("drift", false).unwrap(), Category::Drift);
+    }
+}
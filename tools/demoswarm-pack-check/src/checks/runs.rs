@@ -0,0 +1,744 @@
+//! Run artifact checks: `.runs/index.json`, each run's `run_meta.json` and
+//! its flow receipts.
+//!
+//! These validate what the flows wrote rather than the pack, so they run only
+//! in `pack-check runs` (`Ctx::runs_dir`), on every run or one `--run-id`.
+//! A run is a `.runs/<run-id>/` directory with a `run_meta.json`; other
+//! directories (ad-hoc notes) are not runs.
+//!
+//! Checks: 77, 78, 79, 80
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::contracts::{ACTION_VALUES, STATUS_VALUES};
+use crate::reporter::{Finding, Location, Reporter};
+
+use super::{Category, CheckCtx, CheckSpec};
+
+pub fn checks() -> Vec<CheckSpec> {
+    vec![
+        CheckSpec {
+            id: 77,
+            title: "Checking .runs/index.json matches each run's run_meta.json...",
+            category: Category::Runs,
+            rationale: "`.runs/index.json` agrees with each run's `run_meta.json`.\n\
+                \n\
+                Every index entry must name a run directory with a `run_meta.json` whose\n\
+                `run_id` is the directory name, appear once, copy `canonical_key`,\n\
+                `task_key`, `task_title`, `issue_number` and `pr_number` from it, and have\n\
+                a `last_flow` the run has started. A run missing from the index warns:\n\
+                alias resolution starts at the index, so the run can't be found by issue.",
+            run: check_index_matches_run_meta,
+        },
+        CheckSpec {
+            id: 78,
+            title: "Checking every started flow has a receipt...",
+            category: Category::Runs,
+            rationale: "Every flow in `run_meta.json` `flows_started` has its receipt.\n\
+                \n\
+                The receipt is `.runs/<run-id>/<flow>/<flow>_receipt.json` (the name the\n\
+                cleanup contract gives it). A missing receipt for an earlier flow fails:\n\
+                its cleanup never sealed it. For the latest started flow it only warns,\n\
+                since that flow may still be running.",
+            run: check_started_flows_have_receipts,
+        },
+        CheckSpec {
+            id: 79,
+            title: "Checking receipts use the canonical status and action enums...",
+            category: Category::Runs,
+            rationale: "Receipts use the canonical enums.\n\
+                \n\
+                `status` (required) and every `flow_summary` value must be one of\n\
+                `STATUS_VALUES`; `recommended_action`, when set, one of `ACTION_VALUES`.\n\
+                These are the axes the Machine Summary contract defines (and that\n\
+                `Regexes::canon_status` / `canon_action` are built from); reporters and\n\
+                later flows branch on them. Receipts that aren't valid JSON fail here.",
+            run: check_receipt_enums,
+        },
+        CheckSpec {
+            id: 80,
+            title: "Checking receipt flow summaries match the flow receipts...",
+            category: Category::Runs,
+            rationale: "A receipt's `flow_summary` agrees with the flow receipts.\n\
+                \n\
+                Each non-null `flow_summary.<flow>` (the wisdom receipt summarizes the\n\
+                whole run) must equal the `status` of that flow's receipt in the same\n\
+                run, and the receipt must exist. A summary that disagrees misreports the\n\
+                run to whoever reads only the last receipt.",
+            run: check_flow_summaries,
+        },
+    ]
+}
+
+/// `index.json` fields copied from `run_meta.json`.
+const INDEX_META_FIELDS: [&str; 5] = [
+    "canonical_key",
+    "task_key",
+    "task_title",
+    "issue_number",
+    "pr_number",
+];
+
+/// One `.runs/<run-id>/` directory with a `run_meta.json`.
+struct Run {
+    /// The directory name.
+    id: String,
+    dir: PathBuf,
+    meta: PathBuf,
+}
+
+impl Run {
+    /// `.runs/<run-id>/<flow>/<receipt>`, with the receipt name from the
+    /// cleanup contract (`<flow>_receipt.json` if the flow has none).
+    fn receipt(&self, cx: &CheckCtx, flow: &str) -> PathBuf {
+        let cleanup = format!("{flow}-cleanup");
+        let name =
+            cx.c.cleanup_agents
+                .iter()
+                .find(|(agent, _)| *agent == cleanup)
+                .map_or_else(|| format!("{flow}_receipt.json"), |(_, r)| r.clone());
+        self.dir.join(flow).join(name)
+    }
+
+    /// Every `*_receipt.json` under the run.
+    fn receipts(&self, cx: &CheckCtx) -> Vec<PathBuf> {
+        cx.ctx
+            .files_under(&self.dir)
+            .into_iter()
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.ends_with("_receipt.json"))
+            })
+            .collect()
+    }
+}
+
+/// The runs to check, by directory name; empty outside runs mode.
+fn runs(cx: &CheckCtx) -> Vec<Run> {
+    let Some(runs_dir) = &cx.ctx.runs_dir else {
+        return Vec::new();
+    };
+    cx.ctx
+        .files_under(runs_dir)
+        .into_iter()
+        .filter(|p| p.file_name().is_some_and(|n| n == "run_meta.json"))
+        .filter_map(|meta| {
+            let dir = meta.parent()?.to_path_buf();
+            if dir.parent() != Some(runs_dir.as_path()) {
+                return None;
+            }
+            let id = dir.file_name()?.to_str()?.to_string();
+            Some(Run { id, dir, meta })
+        })
+        .filter(|run| cx.ctx.run_id.as_ref().is_none_or(|id| *id == run.id))
+        .collect()
+}
+
+/// A JSON file's text and parsed value.
+fn read_json(
+    cx: &CheckCtx,
+    path: &Path,
+) -> anyhow::Result<(Arc<str>, Result<Value, serde_json::Error>)> {
+    let text = cx.ctx.read_utf8(path)?;
+    let value = serde_json::from_str(&text);
+    Ok((text, value))
+}
+
+/// A finding at `path`, on `line` if known.
+fn at(cx: &CheckCtx, path: &Path, line: Option<usize>, text: String) -> Finding {
+    let loc = Location::file(cx.ctx.rel(path));
+    Finding::new(
+        match line {
+            Some(line) => loc.with_line(line),
+            None => loc,
+        },
+        text,
+    )
+}
+
+fn invalid_json(cx: &CheckCtx, path: &Path, e: &serde_json::Error) -> Finding {
+    let line = (e.line() > 0).then_some(e.line());
+    at(cx, path, line, format!("invalid JSON: {e}"))
+}
+
+/// 1-based line of `"keys[0]":`, then of `"keys[1]":` at or after it, and so
+/// on (pretty-printed JSON puts nested keys below their parent).
+fn key_line(text: &str, keys: &[&str]) -> Option<usize> {
+    let mut line: usize = 0;
+    for key in keys {
+        let needle = format!("\"{key}\":");
+        let start = line.saturating_sub(1);
+        line = start + text.lines().skip(start).position(|l| l.contains(&needle))? + 1;
+    }
+    Some(line)
+}
+
+/// A receipt's `flow_summary` entries, in file order.
+fn flow_summary<'a>(receipt: &'a Value, text: &str) -> Vec<(&'a String, &'a Value)> {
+    let mut entries: Vec<_> = receipt
+        .get("flow_summary")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .collect();
+    entries.sort_by_key(|(flow, _)| key_line(text, &["flow_summary", flow]));
+    entries
+}
+
+/// Check 77: `.runs/index.json` agrees with each run's `run_meta.json`.
+fn check_index_matches_run_meta(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let Some(runs_dir) = &cx.ctx.runs_dir else {
+        rep.pass("Not checking .runs/");
+        return Ok(());
+    };
+
+    let mut broken = Vec::new();
+    let mut metas: BTreeMap<String, Value> = BTreeMap::new();
+    for run in runs(cx) {
+        let (text, meta) = read_json(cx, &run.meta)?;
+        let meta = match meta {
+            Ok(meta) => meta,
+            Err(e) => {
+                broken.push(invalid_json(cx, &run.meta, &e));
+                continue;
+            }
+        };
+        if let Some(id) = meta.get("run_id").and_then(Value::as_str)
+            && id != run.id
+        {
+            broken.push(at(
+                cx,
+                &run.meta,
+                key_line(&text, &["run_id"]),
+                format!("run_id is `{id}`, but the run directory is `{}`", run.id),
+            ));
+        }
+        metas.insert(run.id, meta);
+    }
+
+    let index = runs_dir.join("index.json");
+    // run_id -> entries seen so far
+    let mut listed: BTreeMap<String, usize> = BTreeMap::new();
+    if !cx.ctx.is_file(&index) {
+        if !metas.is_empty() {
+            broken.push(at(cx, &index, None, "does not exist".to_string()));
+        }
+    } else {
+        let (text, value) = read_json(cx, &index)?;
+        match value {
+            Err(e) => broken.push(invalid_json(cx, &index, &e)),
+            Ok(value) => {
+                let entries = value.get("runs").and_then(Value::as_array);
+                if entries.is_none() {
+                    broken.push(at(cx, &index, None, "has no `runs` array".to_string()));
+                }
+                for entry in entries.into_iter().flatten() {
+                    let Some(id) = entry.get("run_id").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    if cx.ctx.run_id.as_ref().is_some_and(|only| only != id) {
+                        continue;
+                    }
+                    let seen = listed.entry(id.to_string()).or_insert(0);
+                    let quoted = format!("\"{id}\"");
+                    let line = text
+                        .lines()
+                        .enumerate()
+                        .filter(|(_, l)| l.contains("\"run_id\"") && l.contains(&quoted))
+                        .nth(*seen)
+                        .map(|(i, _)| i + 1);
+                    *seen += 1;
+                    let mut fail = |msg: String| broken.push(at(cx, &index, line, msg));
+
+                    if listed[id] > 1 {
+                        fail(format!("`{id}` is listed more than once"));
+                        continue;
+                    }
+                    let Some(meta) = metas.get(id) else {
+                        fail(format!("`{id}` has no .runs/{id}/run_meta.json"));
+                        continue;
+                    };
+                    for field in INDEX_META_FIELDS {
+                        if let (Some(have), Some(want)) = (entry.get(field), meta.get(field))
+                            && have != want
+                        {
+                            fail(format!(
+                                "`{id}`: {field} is {have}, but run_meta.json has {want}"
+                            ));
+                        }
+                    }
+                    let started: Vec<&str> = meta
+                        .get("flows_started")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_str)
+                        .collect();
+                    if let Some(last) = entry.get("last_flow").and_then(Value::as_str)
+                        && !started.contains(&last)
+                    {
+                        fail(format!(
+                            "`{id}`: last_flow is `{last}`, but run_meta.json flows_started is [{}]",
+                            started.join(", ")
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    let unindexed: Vec<Finding> = metas
+        .keys()
+        .filter(|id| !listed.contains_key(*id))
+        .map(|id| {
+            at(
+                cx,
+                &runs_dir.join(id).join("run_meta.json"),
+                None,
+                format!("`{id}` is not in .runs/index.json"),
+            )
+        })
+        .collect();
+
+    if broken.is_empty() && unindexed.is_empty() {
+        rep.pass(format!(
+            ".runs/index.json matches {} run{}",
+            metas.len(),
+            if metas.len() == 1 { "" } else { "s" }
+        ));
+    }
+    if !broken.is_empty() {
+        rep.fail_findings("Run index and run metadata disagree:", broken);
+    }
+    if !unindexed.is_empty() {
+        rep.warn_findings("Runs missing from .runs/index.json:", unindexed);
+    }
+
+    Ok(())
+}
+
+/// Check 78: Every flow in `run_meta.json` `flows_started` has its receipt.
+fn check_started_flows_have_receipts(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let mut missing = Vec::new();
+    let mut pending = Vec::new();
+
+    for run in runs(cx) {
+        let (text, meta) = read_json(cx, &run.meta)?;
+        // Invalid run_meta.json is reported by check 77
+        let Ok(meta) = meta else { continue };
+        let started: Vec<&str> = meta
+            .get("flows_started")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        let line = key_line(&text, &["flows_started"]);
+
+        for (i, flow) in started.iter().enumerate() {
+            let receipt = run.receipt(cx, flow);
+            if cx.ctx.is_file(&receipt) {
+                continue;
+            }
+            let text = format!(
+                "`{}`: flow `{flow}` was started, but {} does not exist",
+                run.id,
+                cx.ctx.rel(&receipt)
+            );
+            if i + 1 == started.len() {
+                pending.push(at(cx, &run.meta, line, text));
+            } else {
+                missing.push(at(cx, &run.meta, line, text));
+            }
+        }
+    }
+
+    if missing.is_empty() && pending.is_empty() {
+        rep.pass("Every started flow has a receipt");
+    }
+    if !missing.is_empty() {
+        rep.fail_findings("Started flows have no receipt:", missing);
+    }
+    if !pending.is_empty() {
+        rep.warn_findings("Latest flow has no receipt yet:", pending);
+    }
+
+    Ok(())
+}
+
+/// Check 79: Receipts use the canonical enums.
+fn check_receipt_enums(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let mut broken = Vec::new();
+    let mut checked = 0;
+
+    for run in runs(cx) {
+        for receipt in run.receipts(cx) {
+            let (text, value) = read_json(cx, &receipt)?;
+            let value = match value {
+                Ok(value) => value,
+                Err(e) => {
+                    broken.push(invalid_json(cx, &receipt, &e));
+                    continue;
+                }
+            };
+            checked += 1;
+
+            let mut check = |keys: &[&str], value: Option<&Value>, allowed: &[&str]| {
+                let ok = value
+                    .and_then(Value::as_str)
+                    .is_some_and(|v| allowed.contains(&v));
+                if !ok {
+                    let found = value.map_or("missing".to_string(), Value::to_string);
+                    broken.push(at(
+                        cx,
+                        &receipt,
+                        key_line(&text, keys),
+                        format!("{} is {found}, not {}", keys.join("."), allowed.join(" | ")),
+                    ));
+                }
+            };
+
+            check(&["status"], value.get("status"), STATUS_VALUES);
+            if let Some(action) = value.get("recommended_action").filter(|a| !a.is_null()) {
+                check(&["recommended_action"], Some(action), ACTION_VALUES);
+            }
+            for (flow, status) in flow_summary(&value, &text) {
+                if !status.is_null() {
+                    check(&["flow_summary", flow], Some(status), STATUS_VALUES);
+                }
+            }
+        }
+    }
+
+    if broken.is_empty() {
+        rep.pass(format!(
+            "{checked} receipt{} use the canonical enums",
+            if checked == 1 { "" } else { "s" }
+        ));
+    } else {
+        rep.fail_findings("Receipts with non-canonical values:", broken);
+    }
+
+    Ok(())
+}
+
+/// Check 80: A receipt's `flow_summary` agrees with the flow receipts.
+fn check_flow_summaries(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let mut broken = Vec::new();
+
+    for run in runs(cx) {
+        for receipt in run.receipts(cx) {
+            let (text, value) = read_json(cx, &receipt)?;
+            let Ok(value) = value else { continue };
+            for (flow, summarized) in flow_summary(&value, &text) {
+                let Some(summarized) = summarized.as_str() else {
+                    continue;
+                };
+                let line = key_line(&text, &["flow_summary", flow]);
+                let other = run.receipt(cx, flow);
+                let rel = cx.ctx.rel(&other);
+
+                let actual = if cx.ctx.is_file(&other) {
+                    match read_json(cx, &other)?.1 {
+                        Ok(v) => v.get("status").and_then(Value::as_str).map(str::to_string),
+                        Err(_) => continue,
+                    }
+                } else {
+                    broken.push(at(
+                        cx,
+                        &receipt,
+                        line,
+                        format!("flow_summary.{flow} is {summarized}, but {rel} does not exist"),
+                    ));
+                    continue;
+                };
+                if actual.as_deref() != Some(summarized) {
+                    broken.push(at(
+                        cx,
+                        &receipt,
+                        line,
+                        format!(
+                            "flow_summary.{flow} is {summarized}, but {rel} has status {}",
+                            actual.as_deref().unwrap_or("missing")
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    if broken.is_empty() {
+        rep.pass("Receipt flow summaries match the flow receipts");
+    } else {
+        rep.fail_findings(
+            "Receipt flow summaries disagree with the flow receipts:",
+            broken,
+        );
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn receipt(flow: &str, status: &str, action: &str) -> String {
+        format!(
+            "{{\n  \"flow\": \"{flow}\",\n  \"status\": \"{status}\",\n  \"recommended_action\": \"{action}\"\n}}\n"
+        )
+    }
+
+    /// A pack with one consistent run, `feat-a`, that has run signal and plan.
+    fn create_runs() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        write(root, ".claude/agents/a.md", "# A\n");
+        write(
+            root,
+            ".runs/index.json",
+            "{\n  \"version\": 1,\n  \"runs\": [\n    {\n      \"run_id\": \"feat-a\",\n      \
+             \"canonical_key\": \"gh-1\",\n      \"issue_number\": 1,\n      \
+             \"status\": \"VERIFIED\",\n      \"last_flow\": \"plan\"\n    }\n  ]\n}\n",
+        );
+        write(
+            root,
+            ".runs/feat-a/run_meta.json",
+            "{\n  \"run_id\": \"feat-a\",\n  \"canonical_key\": \"gh-1\",\n  \"issue_number\": 1,\n  \
+             \"flows_started\": [\"signal\", \"plan\"]\n}\n",
+        );
+        write(
+            root,
+            ".runs/feat-a/signal/signal_receipt.json",
+            &receipt("signal", "VERIFIED", "PROCEED"),
+        );
+        write(
+            root,
+            ".runs/feat-a/plan/plan_receipt.json",
+            &receipt("plan", "UNVERIFIED", "RERUN"),
+        );
+        write(root, ".runs/notes/critique.md", "# Not a run\n");
+        dir
+    }
+
+    fn run(
+        root: &Path,
+        run_id: Option<&str>,
+        check: fn(&CheckCtx, &mut Reporter) -> anyhow::Result<()>,
+    ) -> Vec<String> {
+        use crate::contracts::Contracts;
+        use crate::contracts::test_utils::REGEXES;
+        use crate::ctx::Ctx;
+        use crate::inventory::Inventory;
+        use crate::reporter::Level;
+
+        let mut ctx = Ctx::discover(Some(root.to_path_buf())).unwrap();
+        ctx.runs_dir = Some(root.join(".runs"));
+        ctx.run_id = run_id.map(str::to_string);
+        let inv = Inventory::from_ctx(&ctx).unwrap();
+        let contracts = Contracts::default();
+        let cx = CheckCtx {
+            ctx: &ctx,
+            inv: &inv,
+            re: &REGEXES,
+            c: &contracts,
+        };
+        let mut rep = Reporter::silent();
+        check(&cx, &mut rep).unwrap();
+        rep.diagnostics()
+            .iter()
+            .filter(|d| d.level != Level::Pass)
+            .map(|d| {
+                let level = if d.level == Level::Fail {
+                    "fail"
+                } else {
+                    "warn"
+                };
+                let loc = d.location.as_ref().unwrap();
+                match loc.line {
+                    Some(line) => format!("{level} {}:{line}: {}", loc.path, d.message),
+                    None => format!("{level} {}: {}", loc.path, d.message),
+                }
+            })
+            .collect()
+    }
+
+    /// Test: A consistent run passes all four checks.
+    #[test]
+    fn test_consistent_run_passes() {
+        let dir = create_runs();
+        for check in checks() {
+            assert_eq!(
+                run(dir.path(), None, check.run),
+                Vec::<String>::new(),
+                "check {}",
+                check.id
+            );
+        }
+    }
+
+    /// Test: Index entries must match run_meta.json, appear once and have a
+    /// run; unindexed runs warn.
+    #[test]
+    fn test_index_matches_run_meta() {
+        let dir = create_runs();
+        let root = dir.path();
+        write(
+            root,
+            ".runs/index.json",
+            "{\n  \"runs\": [\n    { \"run_id\": \"feat-a\", \"issue_number\": 2, \"last_flow\": \"gate\" },\n    \
+             { \"run_id\": \"feat-a\" },\n    { \"run_id\": \"gone\" }\n  ]\n}\n",
+        );
+        write(
+            root,
+            ".runs/feat-b/run_meta.json",
+            "{\n  \"run_id\": \"renamed\",\n  \"flows_started\": []\n}\n",
+        );
+
+        assert_eq!(
+            run(root, None, check_index_matches_run_meta),
+            vec![
+                "fail .runs/feat-b/run_meta.json:2: Run index and run metadata disagree: \
+                 run_id is `renamed`, but the run directory is `feat-b`",
+                "fail .runs/index.json:3: Run index and run metadata disagree: \
+                 `feat-a`: issue_number is 2, but run_meta.json has 1",
+                "fail .runs/index.json:3: Run index and run metadata disagree: \
+                 `feat-a`: last_flow is `gate`, but run_meta.json flows_started is [signal, plan]",
+                "fail .runs/index.json:4: Run index and run metadata disagree: \
+                 `feat-a` is listed more than once",
+                "fail .runs/index.json:5: Run index and run metadata disagree: \
+                 `gone` has no .runs/gone/run_meta.json",
+                "warn .runs/feat-b/run_meta.json: Runs missing from .runs/index.json: \
+                 `feat-b` is not in .runs/index.json",
+            ]
+        );
+
+        // --run-id limits the check to one run
+        assert_eq!(
+            run(root, Some("feat-b"), check_index_matches_run_meta).len(),
+            2
+        );
+    }
+
+    /// Test: Earlier started flows need a receipt; the latest only warns.
+    #[test]
+    fn test_started_flows_have_receipts() {
+        let dir = create_runs();
+        let root = dir.path();
+        std::fs::remove_file(root.join(".runs/feat-a/signal/signal_receipt.json")).unwrap();
+        write(
+            root,
+            ".runs/feat-a/run_meta.json",
+            "{\n  \"run_id\": \"feat-a\",\n  \"flows_started\": [\"signal\", \"plan\", \"build\"]\n}\n",
+        );
+
+        assert_eq!(
+            run(root, None, check_started_flows_have_receipts),
+            vec![
+                "fail .runs/feat-a/run_meta.json:3: Started flows have no receipt: \
+                 `feat-a`: flow `signal` was started, but \
+                 .runs/feat-a/signal/signal_receipt.json does not exist",
+                "warn .runs/feat-a/run_meta.json:3: Latest flow has no receipt yet: \
+                 `feat-a`: flow `build` was started, but \
+                 .runs/feat-a/build/build_receipt.json does not exist",
+            ]
+        );
+    }
+
+    /// Test: status, recommended_action and flow_summary values must be canonical.
+    #[test]
+    fn test_receipt_enums() {
+        let dir = create_runs();
+        let root = dir.path();
+        write(
+            root,
+            ".runs/feat-a/plan/plan_receipt.json",
+            &receipt("plan", "PASS", "CONTINUE"),
+        );
+        write(
+            root,
+            ".runs/feat-a/wisdom/wisdom_receipt.json",
+            "{\n  \"recommended_action\": null,\n  \"flow_summary\": {\n    \"signal\": \"OK\",\n    \
+             \"plan\": null\n  }\n}\n",
+        );
+        write(
+            root,
+            ".runs/feat-a/gate/gate_receipt.json",
+            "{ \"status\": ",
+        );
+
+        let diags = run(root, None, check_receipt_enums);
+        assert!(
+            diags[0].starts_with(
+                "fail .runs/feat-a/gate/gate_receipt.json:1: Receipts with non-canonical values: \
+                 invalid JSON:"
+            ),
+            "{}",
+            diags[0]
+        );
+        assert_eq!(
+            diags[1..],
+            [
+                "fail .runs/feat-a/plan/plan_receipt.json:3: Receipts with non-canonical values: \
+                 status is \"PASS\", not VERIFIED | UNVERIFIED | CANNOT_PROCEED",
+                "fail .runs/feat-a/plan/plan_receipt.json:4: Receipts with non-canonical values: \
+                 recommended_action is \"CONTINUE\", not PROCEED | RERUN | BOUNCE | FIX_ENV",
+                "fail .runs/feat-a/wisdom/wisdom_receipt.json: Receipts with non-canonical values: \
+                 status is missing, not VERIFIED | UNVERIFIED | CANNOT_PROCEED",
+                "fail .runs/feat-a/wisdom/wisdom_receipt.json:4: Receipts with non-canonical values: \
+                 flow_summary.signal is \"OK\", not VERIFIED | UNVERIFIED | CANNOT_PROCEED",
+            ]
+        );
+    }
+
+    /// Test: flow_summary entries must match the status of that flow's receipt.
+    #[test]
+    fn test_flow_summaries() {
+        let dir = create_runs();
+        let root = dir.path();
+        write(
+            root,
+            ".runs/feat-a/wisdom/wisdom_receipt.json",
+            "{\n  \"status\": \"VERIFIED\",\n  \"flow_summary\": {\n    \"signal\": \"VERIFIED\",\n    \
+             \"plan\": \"VERIFIED\",\n    \"build\": \"VERIFIED\",\n    \"gate\": null\n  }\n}\n",
+        );
+
+        assert_eq!(
+            run(root, None, check_flow_summaries),
+            vec![
+                "fail .runs/feat-a/wisdom/wisdom_receipt.json:5: Receipt flow summaries disagree \
+                 with the flow receipts: flow_summary.plan is VERIFIED, but \
+                 .runs/feat-a/plan/plan_receipt.json has status UNVERIFIED",
+                "fail .runs/feat-a/wisdom/wisdom_receipt.json:6: Receipt flow summaries disagree \
+                 with the flow receipts: flow_summary.build is VERIFIED, but \
+                 .runs/feat-a/build/build_receipt.json does not exist",
+            ]
+        );
+    }
+
+    /// Test: key_line follows nested keys down a pretty-printed document.
+    #[test]
+    fn test_key_line() {
+        let text = "{\n  \"a\": {\n    \"status\": 1\n  },\n  \"status\": 2\n}";
+        assert_eq!(key_line(text, &["status"]), Some(3));
+        assert_eq!(key_line(text, &["a", "status"]), Some(3));
+        assert_eq!(key_line("{\"a\": {\"b\": 1}}", &["a", "b"]), Some(1));
+        assert_eq!(key_line(text, &["missing"]), None);
+    }
+}
@@ -1,57 +1,111 @@
 //! Structure checks: required files exist, no duplicates.
 //!
-//! Checks: 1, 2, 6, 9, 10, 11, 15
+//! Checks: 1, 2, 6, 9, 10, 11, 15, 68, 69, 70
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
-use crate::reporter::Reporter;
+use crate::frontmatter::Fields;
+use crate::reporter::{Finding, Location, Reporter};
 use crate::util::extract_frontmatter_name;
 
-use super::{CheckCtx, CheckSpec};
+use super::{Category, CheckCtx, CheckSpec};
 
 pub fn checks() -> Vec<CheckSpec> {
     vec![
         CheckSpec {
             id: 1,
             title: "Checking required agents...",
+            category: Category::Structure,
+            rationale: "Required agents exist.",
             run: check_required_agents,
         },
         CheckSpec {
             id: 2,
             title: "Checking flow commands...",
+            category: Category::Structure,
+            rationale: "Required flow commands exist.\n\
+                \n\
+                A flow is required when the cleanup contracts name a cleanup agent for it.",
             run: check_flow_commands,
         },
         CheckSpec {
             id: 6,
             title: "Checking agent frontmatter name contracts...",
+            category: Category::Structure,
+            rationale: "No duplicate agent names.",
             run: check_duplicate_agents,
         },
         CheckSpec {
             id: 9,
             title: "Checking skills...",
+            category: Category::Structure,
+            rationale: "Required skills exist.",
             run: check_skills,
         },
         CheckSpec {
             id: 10,
             title: "Checking CLAUDE.md...",
+            category: Category::Structure,
+            rationale: "CLAUDE.md exists and has key sections.",
             run: check_claude_md,
         },
         CheckSpec {
             id: 11,
             title: "Checking agent skills sections...",
+            category: Category::Structure,
+            rationale: "Agents using demoswarm.sh must have a ## Skills section.",
             run: check_agent_skills_section,
         },
         CheckSpec {
             id: 15,
             title: "Checking customizer command...",
+            category: Category::Structure,
+            rationale: "Customizer command exists.",
             run: check_customizer,
         },
+        CheckSpec {
+            id: 68,
+            title: "Checking flow topology (commands, cleanup agents, receipts)...",
+            category: Category::Structure,
+            rationale: "Flow commands, cleanup contracts and receipts agree.\n\
+                \n\
+                Flow numbering must run 1..N without gaps or duplicates, and every flow needs\n\
+                a `<flow>-cleanup` contract sealing `<flow>_receipt.json`. Cleanup agents\n\
+                without a flow command are reported by check 2, cleanup agents that are not\n\
+                required agents by check 69.",
+            run: check_flow_topology,
+        },
+        CheckSpec {
+            id: 69,
+            title: "Checking contract tables are self-consistent...",
+            category: Category::Structure,
+            rationale: "The contract tables do not contradict each other.\n\
+                \n\
+                Unused regexes are not reported here: the regexes are compiled into\n\
+                pack-check, so a unit test holds them against the check sources instead.",
+            run: check_contract_consistency,
+        },
+        CheckSpec {
+            id: 70,
+            title: "Checking agent, command and skill frontmatter schema...",
+            category: Category::Structure,
+            rationale: "Agent, command and skill frontmatter is valid YAML and matches\n\
+                its schema.\n\
+                \n\
+                Required fields are present and non-empty, agent `model` and `color` come\n\
+                from the contract enums (`docs/reference/model-allocation.md`,\n\
+                `visual-style.md`), and `tools` / `allowed-tools` name known tools. Every\n\
+                skill directory needs a `SKILL.md`. Fields the schema does not know are\n\
+                warnings: Claude Code may accept them, but a typo lands there too.",
+            run: check_frontmatter_schema,
+        },
     ]
 }
 
 /// Check 1: Required agents exist.
 fn check_required_agents(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
-    for agent in cx.c.required_agents {
+    for agent in &cx.c.required_agents {
         if cx.inv.agent(agent).is_some() {
             rep.pass(format!("{agent}.md exists"));
         } else {
@@ -64,19 +118,19 @@ fn check_required_agents(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()
 
 /// Check 2: Required flow commands exist.
 fn check_flow_commands(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
-    for i in 1..=6 {
-        let prefix = format!("flow-{i}-");
-        let exists = cx.inv.command_md_files.iter().any(|p| {
-            p.file_name()
-                .and_then(|s| s.to_str())
-                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".md"))
-        });
-
-        if exists {
-            rep.pass(format!("flow-{i} command exists"));
-        } else {
-            rep.fail(format!("flow-{i} command MISSING"));
-        }
+    let topo = cx.flows();
+
+    if topo.flows.is_empty() {
+        rep.fail("No flow commands (flow-N-<name>.md) found");
+    }
+    for flow in &topo.flows {
+        rep.pass(format!("{} command exists", flow.stem()));
+    }
+    for (flow, cleanup) in &topo.orphan_cleanups {
+        rep.fail(format!(
+            "flow-N-{flow} command MISSING (required by cleanup agent {})",
+            cleanup.agent
+        ));
     }
 
     Ok(())
@@ -96,21 +150,34 @@ fn check_duplicate_agents(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<(
             .and_then(|s| s.to_str())
             .unwrap_or("<unknown>");
         let Some(name) = extract_frontmatter_name(&content) else {
-            rep.fail(format!("{rel} missing frontmatter `name:`"));
+            rep.fail_at(
+                Location::file(rel.clone()),
+                format!("{rel} missing frontmatter `name:`"),
+            );
             mismatches_found = true;
             continue;
         };
 
         if name != stem {
-            rep.fail(format!(
-                "{rel} frontmatter name '{name}' does not match filename stem '{stem}'"
-            ));
+            let mut loc = Location::file(rel.clone());
+            if let Some(idx) = content.lines().position(|l| l.starts_with("name:")) {
+                loc = loc.with_line(idx + 1);
+            }
+            rep.fail_at(
+                loc,
+                format!("{rel} frontmatter name '{name}' does not match filename stem '{stem}'"),
+            );
             mismatches_found = true;
         }
 
         if let Some(prev) = agent_names.get(&name) {
-            rep.fail(format!("Duplicate agent name '{name}' in:"));
-            rep.indent_lines([prev.clone(), rel.clone()]);
+            rep.fail_findings(
+                format!("Duplicate agent name '{name}' in:"),
+                [
+                    Finding::new(Location::file(prev.clone()), ""),
+                    Finding::new(Location::file(rel.clone()), ""),
+                ],
+            );
             duplicates_found = true;
         } else {
             agent_names.insert(name, rel);
@@ -130,9 +197,9 @@ fn check_duplicate_agents(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<(
 
 /// Check 9: Required skills exist.
 fn check_skills(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
-    for skill in cx.c.required_skills {
+    for skill in &cx.c.required_skills {
         let path = cx.ctx.skills_dir.join(skill).join("SKILL.md");
-        if path.is_file() {
+        if cx.ctx.is_file(&path) {
             rep.pass(format!("{skill} skill exists"));
         } else {
             rep.fail(format!("{skill} skill MISSING"));
@@ -145,15 +212,18 @@ fn check_skills(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
 /// Check 10: CLAUDE.md exists and has key sections.
 fn check_claude_md(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let claude_md = cx.ctx.repo_root.join("CLAUDE.md");
-    if claude_md.is_file() {
+    if cx.ctx.is_file(&claude_md) {
         rep.pass("CLAUDE.md exists");
 
         let content = cx.ctx.read_utf8(&claude_md)?;
-        for section in cx.c.claude_md_sections {
+        for section in &cx.c.claude_md_sections {
             if content.contains(section) {
                 rep.pass(format!("CLAUDE.md documents '{section}'"));
             } else {
-                rep.warn(format!("CLAUDE.md missing documentation for '{section}'"));
+                rep.warn_at(
+                    Location::file(cx.ctx.rel(&claude_md)),
+                    format!("CLAUDE.md missing documentation for '{section}'"),
+                );
             }
         }
     } else {
@@ -166,14 +236,14 @@ fn check_claude_md(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
 /// Check 15: Customizer command exists.
 fn check_customizer(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
     let customize_cmd = cx.ctx.commands_dir.join("customize-pack.md");
-    if customize_cmd.is_file() {
+    if cx.ctx.is_file(&customize_cmd) {
         rep.pass("customize-pack command exists");
     } else {
         rep.warn("customize-pack command MISSING (optional but recommended)");
     }
 
     let pack_customizer = cx.ctx.agents_dir.join("pack-customizer.md");
-    if pack_customizer.is_file() {
+    if cx.ctx.is_file(&pack_customizer) {
         rep.pass("pack-customizer agent exists");
     } else {
         rep.warn("pack-customizer agent MISSING (optional but recommended)");
@@ -199,15 +269,291 @@ fn check_agent_skills_section(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Resu
             if has_skills {
                 rep.pass(format!("{rel} uses demoswarm.sh and has Skills section"));
             } else {
-                rep.fail(format!(
-                    "{rel} uses demoswarm.sh but MISSING '## Skills' section"
+                rep.fail_at(
+                    Location::file(rel.clone()),
+                    format!("{rel} uses demoswarm.sh but MISSING '## Skills' section"),
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check 68: Flow commands, cleanup contracts and receipts agree.
+fn check_flow_topology(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let topo = cx.flows();
+    let mut issues = Vec::new();
+
+    for path in &topo.malformed {
+        issues.push(Finding::new(
+            Location::file(cx.ctx.rel(path)),
+            "flow command name is not flow-N-<name>.md",
+        ));
+    }
+
+    let mut by_number: HashMap<u32, Vec<&str>> = HashMap::new();
+    for flow in &topo.flows {
+        by_number.entry(flow.number).or_default().push(&flow.name);
+    }
+    let max = topo.flows.iter().map(|f| f.number).max().unwrap_or(0);
+    for n in 1..=max {
+        match by_number.get(&n).map(Vec::as_slice) {
+            None => issues.push(Finding::new(
+                Location::file(cx.ctx.rel(&cx.ctx.commands_dir)),
+                format!("no flow-{n}-<name>.md (flows must be numbered 1..{max} without gaps)"),
+            )),
+            Some(names) if names.len() > 1 => issues.push(Finding::new(
+                Location::file(cx.ctx.rel(&cx.ctx.commands_dir)),
+                format!("flow number {n} is used by: {}", names.join(", ")),
+            )),
+            Some(_) => {}
+        }
+    }
+
+    for flow in &topo.flows {
+        let loc = Location::file(cx.ctx.rel(&flow.command));
+        let Some(cleanup) = &flow.cleanup else {
+            issues.push(Finding::new(
+                loc,
+                format!(
+                    "{} has no {}-cleanup in the cleanup contracts",
+                    flow.stem(),
+                    flow.name
+                ),
+            ));
+            continue;
+        };
+        if cleanup.receipt != flow.expected_receipt() {
+            issues.push(Finding::new(
+                loc,
+                format!(
+                    "{} seals {} (expected {})",
+                    cleanup.agent,
+                    cleanup.receipt,
+                    flow.expected_receipt()
+                ),
+            ));
+        }
+    }
+
+    if issues.is_empty() {
+        rep.pass(format!(
+            "{} flows agree with the cleanup contracts",
+            topo.flows.len()
+        ));
+    } else {
+        rep.fail_findings("Flow topology is inconsistent:", issues);
+    }
+
+    Ok(())
+}
+
+/// Check 69: The contract tables do not contradict each other.
+fn check_contract_consistency(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let mut pack_agents: Vec<String> = cx.inv.agents_by_stem.keys().cloned().collect();
+    pack_agents.sort();
+    let issues = cx.c.inconsistencies(&pack_agents);
+    if issues.is_empty() {
+        rep.pass("Contract tables are consistent");
+        return Ok(());
+    }
+
+    match contracts_location(cx) {
+        Some(path) => rep.fail_findings(
+            "Contract tables contradict each other:",
+            issues
+                .into_iter()
+                .map(|msg| Finding::new(Location::file(path.clone()), msg)),
+        ),
+        // The built-in tables: nothing in the pack to point at.
+        None => {
+            for msg in issues {
+                rep.fail(format!("Contract tables contradict each other: {msg}"));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// What check 70 expects of one kind of pack file's frontmatter.
+struct FrontmatterSchema {
+    required: &'static [&'static str],
+    /// Every field the kind accepts; others are reported as warnings.
+    known: &'static [&'static str],
+    tools_field: &'static str,
+    /// `model` and `color` take the agent enums from the contracts.
+    agent_enums: bool,
+}
+
+const AGENT_FRONTMATTER: FrontmatterSchema = FrontmatterSchema {
+    required: &["name", "description"],
+    known: &["name", "description", "model", "color", "tools"],
+    tools_field: "tools",
+    agent_enums: true,
+};
+
+const COMMAND_FRONTMATTER: FrontmatterSchema = FrontmatterSchema {
+    required: &["description"],
+    known: &[
+        "description",
+        "argument-hint",
+        "allowed-tools",
+        "model",
+        "disable-model-invocation",
+    ],
+    tools_field: "allowed-tools",
+    agent_enums: false,
+};
+
+const SKILL_FRONTMATTER: FrontmatterSchema = FrontmatterSchema {
+    required: &["name", "description"],
+    known: &[
+        "name",
+        "description",
+        "allowed-tools",
+        "license",
+        "metadata",
+    ],
+    tools_field: "allowed-tools",
+    agent_enums: false,
+};
+
+/// Check 70: Agent, command and skill frontmatter is valid YAML and matches
+/// its schema.
+fn check_frontmatter_schema(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
+    let mut errors = Vec::new();
+    let mut unknown = Vec::new();
+
+    let kinds = [
+        (&AGENT_FRONTMATTER, &cx.inv.agent_md_files),
+        (&COMMAND_FRONTMATTER, &cx.inv.command_md_files),
+        (&SKILL_FRONTMATTER, &cx.inv.skill_md_files),
+    ];
+    for (schema, files) in kinds {
+        for path in files {
+            validate_frontmatter(cx, schema, path, &mut errors, &mut unknown)?;
+        }
+    }
+
+    let files = cx.ctx.files_under(&cx.ctx.skills_dir);
+    let skill_dirs: BTreeSet<PathBuf> = files
+        .iter()
+        .filter_map(|p| {
+            let rel = p.strip_prefix(&cx.ctx.skills_dir).ok()?;
+            let dir = rel.components().next()?;
+            (rel.components().count() > 1).then(|| cx.ctx.skills_dir.join(dir))
+        })
+        .collect();
+    for dir in skill_dirs {
+        if !files.contains(&dir.join("SKILL.md")) {
+            errors.push(Finding::new(
+                Location::file(cx.ctx.rel(&dir)),
+                "missing SKILL.md",
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        rep.pass("Agent, command and skill frontmatter matches the schema");
+    } else {
+        rep.fail_findings("Frontmatter does not match the schema:", errors);
+    }
+    if !unknown.is_empty() {
+        rep.warn_findings("Frontmatter has fields the schema does not know:", unknown);
+    }
+
+    Ok(())
+}
+
+fn validate_frontmatter(
+    cx: &CheckCtx,
+    schema: &FrontmatterSchema,
+    path: &Path,
+    errors: &mut Vec<Finding>,
+    unknown: &mut Vec<Finding>,
+) -> anyhow::Result<()> {
+    let rel = cx.ctx.rel(path);
+    let at =
+        |line: usize, text: String| Finding::new(Location::file(rel.clone()).with_line(line), text);
+
+    let md = cx.ctx.markdown(path)?;
+    let Some(fm) = &md.frontmatter else {
+        errors.push(at(
+            1,
+            "no frontmatter (a `---` block at the top)".to_string(),
+        ));
+        return Ok(());
+    };
+    let fields = match Fields::parse(fm) {
+        Ok(fields) => fields,
+        Err(e) => {
+            errors.push(at(e.line, format!("invalid YAML: {}", e.message)));
+            return Ok(());
+        }
+    };
+
+    for key in schema.required {
+        match fields.get(key) {
+            None => errors.push(at(fm.start, format!("missing `{key}`"))),
+            Some(field) if field.text().is_none() => {
+                errors.push(at(field.line, format!("`{key}` must be non-empty text")));
+            }
+            Some(_) => {}
+        }
+    }
+
+    if schema.agent_enums {
+        for (key, allowed) in [("model", &cx.c.agent_models), ("color", &cx.c.agent_colors)] {
+            let Some(field) = fields.get(key) else {
+                continue;
+            };
+            let value = field.text().unwrap_or_default();
+            if !allowed.contains(&value) {
+                errors.push(at(
+                    field.line,
+                    format!("{key} `{value}` is not one of: {}", allowed.join(", ")),
                 ));
             }
         }
     }
+
+    if let Some(field) = fields.get(schema.tools_field) {
+        match field.list() {
+            None => errors.push(at(
+                field.line,
+                format!("`{}` must be a list of tool names", field.key),
+            )),
+            Some(tools) => {
+                for tool in tools {
+                    // `Bash(git:*)` restricts a known tool's arguments.
+                    let name = tool.split('(').next().unwrap_or_default().trim();
+                    if !name.starts_with("mcp__") && !cx.c.known_tools.iter().any(|t| t == name) {
+                        errors.push(at(field.line, format!("unknown tool `{tool}`")));
+                    }
+                }
+            }
+        }
+    }
+
+    for field in fields.iter() {
+        if !schema.known.contains(&field.key.as_str()) {
+            unknown.push(at(field.line, format!("`{}`", field.key)));
+        }
+    }
+
     Ok(())
 }
 
+/// Where contract findings point: the pack's override file, if it has one.
+fn contracts_location(cx: &CheckCtx) -> Option<String> {
+    crate::contracts_file::FILE_NAMES
+        .iter()
+        .map(|name| cx.ctx.claude_dir.join(name))
+        .find(|p| cx.ctx.is_file(p))
+        .map(|p| cx.ctx.rel(&p))
+}
+
 // =============================================================================
 // Unit Tests
 // =============================================================================
@@ -411,6 +757,7 @@ name: test-agent
             "signal-cleanup",
             "plan-cleanup",
             "build-cleanup",
+            "review-cleanup",
             "gate-cleanup",
             "deploy-cleanup",
             "wisdom-cleanup",
@@ -418,7 +765,7 @@ name: test-agent
 
         for agent in cleanup_agents {
             assert!(
-                contracts.required_agents.contains(&agent),
+                contracts.required_agents.iter().any(|x| x == agent),
                 "Required agents should contain cleanup agent: {}",
                 agent
             );
@@ -439,7 +786,7 @@ name: test-agent
 
         for agent in infra_agents {
             assert!(
-                contracts.required_agents.contains(&agent),
+                contracts.required_agents.iter().any(|x| x == agent),
                 "Required agents should contain infrastructure agent: {}",
                 agent
             );
@@ -455,7 +802,7 @@ name: test-agent
 
         for agent in gh_agents {
             assert!(
-                contracts.required_agents.contains(&agent),
+                contracts.required_agents.iter().any(|x| x == agent),
                 "Required agents should contain GitHub agent: {}",
                 agent
             );
@@ -489,7 +836,7 @@ name: test-agent
 
         for skill in expected_skills {
             assert!(
-                contracts.required_skills.contains(&skill),
+                contracts.required_skills.iter().any(|x| x == skill),
                 "Required skills should contain: {}",
                 skill
             );
@@ -516,7 +863,7 @@ name: test-agent
 
         for section in expected_sections {
             assert!(
-                contracts.claude_md_sections.contains(&section),
+                contracts.claude_md_sections.iter().any(|x| x == section),
                 "CLAUDE.md sections should contain: {}",
                 section
             );
@@ -714,6 +1061,285 @@ name: different-name
         assert!(errors > 0, "Should detect missing flow commands");
     }
 
+    /// Helper: run one check and return (errors, warnings).
+    fn run_one(
+        repo_root: &std::path::Path,
+        check: fn(&super::CheckCtx, &mut crate::reporter::Reporter) -> anyhow::Result<()>,
+    ) -> (usize, usize) {
+        use crate::cli::OutputFormat;
+        use crate::contracts::Contracts;
+        use crate::contracts::test_utils::REGEXES;
+        use crate::ctx::Ctx;
+        use crate::inventory::Inventory;
+        use crate::reporter::Reporter;
+
+        let ctx = Ctx::discover(Some(repo_root.to_path_buf())).unwrap();
+        let inv = Inventory::from_ctx(&ctx).unwrap();
+        let contracts = Contracts::default();
+        let cx = super::CheckCtx {
+            ctx: &ctx,
+            inv: &inv,
+            re: &REGEXES,
+            c: &contracts,
+        };
+        let mut rep = Reporter::new(OutputFormat::Json, false, false);
+        check(&cx, &mut rep).unwrap();
+        (rep.errors, rep.warnings)
+    }
+
+    const SEVEN_FLOWS: [&str; 7] = [
+        "flow-1-signal",
+        "flow-2-plan",
+        "flow-3-build",
+        "flow-4-review",
+        "flow-5-gate",
+        "flow-6-deploy",
+        "flow-7-wisdom",
+    ];
+
+    /// Test: the seven default flows agree with the default contracts.
+    #[test]
+    fn test_check_flow_topology_default_flows_pass() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_root = create_test_pack(&temp_dir);
+        for name in SEVEN_FLOWS {
+            create_command(&repo_root, name, "# Flow\n");
+        }
+
+        assert_eq!(run_one(&repo_root, super::check_flow_commands), (0, 0));
+        assert_eq!(run_one(&repo_root, super::check_flow_topology), (0, 0));
+    }
+
+    /// Test: a numbering gap and a flow without a cleanup contract are reported.
+    #[test]
+    fn test_check_flow_topology_inconsistent() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_root = create_test_pack(&temp_dir);
+        for name in SEVEN_FLOWS {
+            create_command(&repo_root, name, "# Flow\n");
+        }
+        create_command(&repo_root, "flow-9-retro", "# Flow 9\n");
+
+        assert_eq!(run_one(&repo_root, super::check_flow_topology), (2, 0));
+
+        // Dropping a flow whose cleanup agent is in the contracts fails check 2
+        std::fs::remove_file(repo_root.join(".claude/commands/flow-4-review.md")).unwrap();
+        assert_eq!(run_one(&repo_root, super::check_flow_commands), (1, 0));
+    }
+
+    /// Test: the default contract tables pass check 69.
+    #[test]
+    fn test_check_contract_consistency_default_pass() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_root = create_test_pack(&temp_dir);
+
+        assert_eq!(
+            run_one(&repo_root, super::check_contract_consistency),
+            (0, 0)
+        );
+    }
+
+    /// Test: contradictions point at the override file, or nowhere without one.
+    #[test]
+    fn test_check_contract_consistency_location() {
+        use crate::checker::PackChecker;
+        use crate::checks::Selection;
+        use crate::contracts::Contracts;
+        use crate::files::MemoryFiles;
+
+        let mut contracts = Contracts::default();
+        contracts.required_agents.push("plan-cleanup".to_string());
+        let checker = |files: MemoryFiles| {
+            PackChecker::new("pack")
+                .files(files)
+                .contracts(contracts.clone())
+                .selection(Selection {
+                    only: vec![69],
+                    ..Default::default()
+                })
+                .run()
+                .unwrap()
+        };
+        let files = MemoryFiles::new().with_file("pack/.claude/agents/a.md", "");
+
+        let report = checker(files.clone());
+        assert_eq!(report.errors, 1);
+        assert_eq!(report.diagnostics[0].location, None);
+
+        let report = checker(files.with_file("pack/.claude/pack-contracts.toml", ""));
+        let loc = report.diagnostics[0].location.as_ref().unwrap();
+        assert_eq!(loc.path, ".claude/pack-contracts.toml");
+    }
+
+    /// Test: contradictions between contract tables are reported.
+    #[test]
+    fn test_contract_inconsistencies_detected() {
+        use crate::contracts::Contracts;
+
+        let mut c = Contracts::default();
+        assert!(c.inconsistencies(&[]).is_empty());
+
+        c.required_agents.retain(|a| a != "gate-cleanup");
+        c.critics.push("style-critic".to_string());
+        c.required_skills.retain(|s| s != "secrets-tools");
+        c.openq_allowed_agents.push("ghost-agent".to_string());
+        c.required_agents.push("plan-cleanup".to_string());
+
+        let found = c.inconsistencies(&["custom-agent".to_string()]);
+        assert_eq!(
+            found,
+            vec![
+                "cleanup agent gate-cleanup is not a required agent",
+                "critic style-critic is not in critic_and_verifier_agents",
+                "index_allowed_agents names unknown agent gate-cleanup",
+                "secrets_allowed_agents restricts secrets-tools, which is not a required skill",
+                "openq_allowed_agents names unknown agent ghost-agent",
+                "required_agents lists plan-cleanup twice",
+            ]
+        );
+
+        // A pack-local agent is a known allow-list member
+        assert!(
+            c.inconsistencies(&["ghost-agent".to_string()])
+                .iter()
+                .all(|m| !m.contains("ghost-agent"))
+        );
+    }
+
+    /// Test: well-formed agent, command and skill frontmatter passes check 70.
+    #[test]
+    fn test_check_frontmatter_schema_pass() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_root = create_test_pack(&temp_dir);
+        create_agent(
+            &repo_root,
+            "work-planner",
+            "---\nname: work-planner\ndescription: >\n  Plans work.\nmodel: haiku\ncolor: purple\ntools: Read, Grep, Bash(git:*), mcp__github__get_issue\n---\n",
+        );
+        create_command(
+            &repo_root,
+            "flow-1-signal",
+            "---\ndescription: Signal flow\nargument-hint: <issue>\n---\n",
+        );
+        create_skill(
+            &repo_root,
+            "runs-index",
+            "---\nname: runs-index\ndescription: Index\nallowed-tools: [Bash, Read]\n---\n",
+        );
+
+        assert_eq!(run_one(&repo_root, super::check_frontmatter_schema), (0, 0));
+    }
+
+    /// Test: check 70 points at the offending line of each schema violation.
+    #[test]
+    fn test_check_frontmatter_schema_findings() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_root = create_test_pack(&temp_dir);
+        create_agent(
+            &repo_root,
+            "a",
+            "---\nname: a\nmodel: opus\ncolor: teal\ntools: Read, Reed\nskills: x\n---\n",
+        );
+        create_agent(&repo_root, "b", "---\nname: b\ndescription: x: y\n---\n");
+        create_agent(&repo_root, "c", "# No frontmatter\n");
+        create_command(&repo_root, "flow-1-signal", "---\ndescription:\n---\n");
+        std::fs::create_dir_all(repo_root.join(".claude/skills/orphan")).unwrap();
+        std::fs::write(repo_root.join(".claude/skills/orphan/run.sh"), "").unwrap();
+
+        let found = run_diagnostics(&repo_root, super::check_frontmatter_schema);
+        let headline = "Frontmatter does not match the schema";
+        assert_eq!(
+            found,
+            vec![
+                format!("fail .claude/agents/a.md:1: {headline}: missing `description`"),
+                format!(
+                    "fail .claude/agents/a.md:3: {headline}: model `opus` is not one of: inherit, haiku, sonnet"
+                ),
+                format!(
+                    "fail .claude/agents/a.md:4: {headline}: color `teal` is not one of: purple, red, green, blue, orange, cyan, pink"
+                ),
+                format!("fail .claude/agents/a.md:5: {headline}: unknown tool `Reed`"),
+                format!(
+                    "fail .claude/agents/b.md:3: {headline}: invalid YAML: mapping values are not allowed in this context"
+                ),
+                format!(
+                    "fail .claude/agents/c.md:1: {headline}: no frontmatter (a `---` block at the top)"
+                ),
+                format!(
+                    "fail .claude/commands/flow-1-signal.md:2: {headline}: `description` must be non-empty text"
+                ),
+                format!("fail .claude/skills/orphan: {headline}: missing SKILL.md"),
+                "warn .claude/agents/a.md:6: Frontmatter has fields the schema does not know: `skills`"
+                    .to_string(),
+            ]
+        );
+    }
+
+    /// Run one check; its diagnostics as `level path:line: message`.
+    fn run_diagnostics(
+        repo_root: &std::path::Path,
+        check: fn(&super::CheckCtx, &mut crate::reporter::Reporter) -> anyhow::Result<()>,
+    ) -> Vec<String> {
+        use crate::contracts::Contracts;
+        use crate::contracts::test_utils::REGEXES;
+        use crate::ctx::Ctx;
+        use crate::inventory::Inventory;
+        use crate::reporter::{Level, Reporter};
+
+        let ctx = Ctx::discover(Some(repo_root.to_path_buf())).unwrap();
+        let inv = Inventory::from_ctx(&ctx).unwrap();
+        let contracts = Contracts::default();
+        let cx = super::CheckCtx {
+            ctx: &ctx,
+            inv: &inv,
+            re: &REGEXES,
+            c: &contracts,
+        };
+        let mut rep = Reporter::silent();
+        check(&cx, &mut rep).unwrap();
+        rep.diagnostics()
+            .iter()
+            .filter(|d| d.level != Level::Pass)
+            .map(|d| {
+                let level = if d.level == Level::Fail {
+                    "fail"
+                } else {
+                    "warn"
+                };
+                let loc = d.location.as_ref().unwrap();
+                match loc.line {
+                    Some(line) => format!("{level} {}:{line}: {}", loc.path, d.message),
+                    None => format!("{level} {}: {}", loc.path, d.message),
+                }
+            })
+            .collect()
+    }
+
+    /// Test: `Regexes::users` attributes a regex to checks exactly when a
+    /// check module reads it, and only to registered checks.
+    #[test]
+    fn test_regex_users_match_check_sources() {
+        use crate::contracts::test_utils::REGEXES;
+        use regex::Regex;
+
+        // Check sources without their test modules.
+        let sources: String = super::super::MODULE_SOURCES
+            .iter()
+            .map(|(_, src)| src.split("#[cfg(test)]\nmod tests").next().unwrap())
+            .collect();
+        let registered: Vec<u32> = super::super::all().iter().map(|c| c.id).collect();
+
+        for (name, ids) in REGEXES.users() {
+            let used = Regex::new(&format!(r"\bre\s*\.\s*{name}\b"))
+                .unwrap()
+                .is_match(&sources);
+            assert_eq!(!ids.is_empty(), used, "regex {name}: users {ids:?}");
+            for id in ids {
+                assert!(registered.contains(id), "regex {name}: check {id}");
+            }
+        }
+    }
+
     /// Test: Agent with missing frontmatter name is flagged (covers lines 94-96).
     #[test]
     fn test_check_duplicate_agents_missing_frontmatter_name() {
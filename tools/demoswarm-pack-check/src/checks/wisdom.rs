@@ -3,25 +3,31 @@
 //! Checks: 24, 36, 41
 
 use super::contracts::headings;
-use crate::reporter::Reporter;
+use crate::reporter::{Location, Reporter};
 
-use super::{CheckCtx, CheckSpec};
+use super::{Category, CheckCtx, CheckSpec};
 
 pub fn checks() -> Vec<CheckSpec> {
     vec![
         CheckSpec {
             id: 24,
             title: "Checking Swarm-Proposed status in ADR author...",
+            category: Category::Wisdom,
+            rationale: "Swarm-Proposed status in ADR author.",
             run: check_swarm_proposed,
         },
         CheckSpec {
             id: 36,
             title: "Checking smoke-verifier domain verdict separation...",
+            category: Category::Wisdom,
+            rationale: "smoke-verifier domain verdict separation.",
             run: check_smoke_verifier,
         },
         CheckSpec {
             id: 41,
             title: "Checking Flow 6 regression markers are grep-stable...",
+            category: Category::Wisdom,
+            rationale: "Flow 6 regression markers match wisdom-cleanup (grep-stable).",
             run: check_regression_markers,
         },
     ]
@@ -34,7 +40,10 @@ fn check_swarm_proposed(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()>
         if content.contains("Swarm-Proposed") {
             rep.pass("adr-author.md uses Swarm-Proposed status");
         } else {
-            rep.fail("adr-author.md does NOT use Swarm-Proposed status");
+            rep.fail_at(
+                Location::file(cx.ctx.rel(adr_author)),
+                "adr-author.md does NOT use Swarm-Proposed status",
+            );
         }
     }
 
@@ -67,7 +76,10 @@ fn check_smoke_verifier(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()>
     if issues.is_empty() {
         rep.pass("smoke-verifier has correct domain verdict separation");
     } else {
-        rep.fail(format!("smoke-verifier issues: {}", issues.join(" ")));
+        rep.fail_at(
+            Location::file(cx.ctx.rel(smoke_verifier)),
+            format!("smoke-verifier issues: {}", issues.join(" ")),
+        );
     }
 
     Ok(())
@@ -75,27 +87,32 @@ fn check_smoke_verifier(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()>
 
 /// Check 41: Flow 6 regression markers match wisdom-cleanup (grep-stable).
 fn check_regression_markers(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result<()> {
-    if let Some(flow6_wisdom) = cx.inv.command("flow-7-wisdom") {
+    let topo = cx.flows();
+    if let Some(flow6_wisdom) = topo.flow("wisdom").map(|f| &f.command) {
         let content = cx.ctx.read_utf8(flow6_wisdom)?;
-        if content.contains(cx.c.reg_marker_literal) {
+        if content.contains(cx.c.reg_marker_literal.as_str()) {
             rep.pass(format!(
                 "Flow 6 documents heading-based REG marker ({})",
                 cx.c.reg_marker_literal
             ));
-        } else if content.contains("- REG-NNN:") {
-            rep.fail(
+        } else if let Some(idx) = content.lines().position(|l| l.contains("- REG-NNN:")) {
+            rep.fail_at(
+                Location::file(cx.ctx.rel(flow6_wisdom)).with_line(idx + 1),
                 "Flow 6 still documents list-based REG markers (- REG-NNN:) (should use headings)",
             );
         } else {
-            rep.warn("Flow 6 may be missing stable regression marker documentation");
+            rep.warn_at(
+                Location::file(cx.ctx.rel(flow6_wisdom)),
+                "Flow 6 may be missing stable regression marker documentation",
+            );
         }
     } else {
-        rep.warn("flow-7-wisdom.md not found (cannot validate regression marker docs)");
+        rep.warn("wisdom flow command not found (cannot validate regression marker docs)");
     }
 
     if let Some(wisdom_cleanup) = cx.inv.agent("wisdom-cleanup") {
         let content = cx.ctx.read_utf8(wisdom_cleanup)?;
-        if content.contains(cx.c.reg_marker_literal) {
+        if content.contains(cx.c.reg_marker_literal.as_str()) {
             rep.pass(format!(
                 "wisdom-cleanup references heading-based REG marker ({})",
                 cx.c.reg_marker_literal
@@ -103,7 +120,10 @@ fn check_regression_markers(cx: &CheckCtx, rep: &mut Reporter) -> anyhow::Result
         } else if cx.re.grep_reg_marker.is_match(&content) {
             rep.pass("wisdom-cleanup grep appears to use heading REG marker");
         } else {
-            rep.warn("wisdom-cleanup may not be aligned to heading-based REG markers");
+            rep.warn_at(
+                Location::file(cx.ctx.rel(wisdom_cleanup)),
+                "wisdom-cleanup may not be aligned to heading-based REG markers",
+            );
         }
     } else {
         rep.warn("wisdom-cleanup.md not found (cannot validate marker alignment)");
@@ -1,16 +1,73 @@
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+use crate::checks::{Category, Selection};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     /// Human-friendly text output (default).
+    #[default]
     Text,
     /// Machine-readable JSON summary.
     Json,
+    /// SARIF 2.1.0 log for code-scanning tools.
+    Sarif,
+}
+
+/// Export format for `--graph`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT.
+    Dot,
+    /// Mermaid flowchart (renders in GitHub markdown).
+    Mermaid,
+    /// Nodes and edges as JSON.
+    Json,
+}
+
+/// What to do instead of checking the pack.
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Reference docs under --docs-root (default `docs`).
+    #[command(subcommand)]
+    Docs(DocsCommand),
+    /// Validate `.runs/` instead of the pack: the index, each run's
+    /// `run_meta.json` and its receipts. Takes the check flags (--only,
+    /// --format, --baseline, ...) like a pack run.
+    Runs {
+        /// Check only this run (`.runs/<ID>/`).
+        #[arg(long, value_name = "ID")]
+        run_id: Option<String>,
+    },
+    /// Compare two versions of the pack in contract terms: agents and skills
+    /// added or removed, frontmatter, Machine Summary keys, `inv get` markers
+    /// and flow agent sequences. Prints text or, with --format json, JSON.
+    Diff {
+        /// The old pack: a directory containing `.claude/`, or a git revision
+        /// of the repository at --repo-root (default: the current one).
+        #[arg(long, value_name = "DIR|REV")]
+        from: String,
+        /// The new pack, like --from.
+        #[arg(long, value_name = "DIR|REV")]
+        to: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum DocsCommand {
+    /// Rewrite the generated sections of the agents and skills indexes, the
+    /// agent matrix and the routing table from the pack.
+    Generate {
+        /// Write nothing; print the changes as a diff and exit 1 if any
+        /// generated section is out of date.
+        #[arg(long)]
+        check: bool,
+    },
 }
 
-#[derive(Debug, Clone, Parser)]
+#[derive(Debug, Clone, Default, Parser)]
 #[command(
     name = "pack-check",
     about = "Validate a DemoSwarm .claude pack for structural + contract consistency",
@@ -18,22 +75,131 @@ pub enum OutputFormat {
     disable_help_subcommand = true
 )]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Repo root directory (the directory that contains .claude/).
     ///
     /// If omitted, pack-check walks up from the current working directory
     /// until it finds a .claude/ directory.
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub repo_root: Option<PathBuf>,
 
     /// Output format.
-    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, global = true)]
     pub format: OutputFormat,
 
     /// Disable ANSI colors in text output.
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub no_color: bool,
 
     /// Treat warnings as errors for the process exit code.
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub strict_warnings: bool,
+
+    /// Run checks on N threads (default: one per CPU). Output is the same for any N.
+    #[arg(long, short = 'j', value_name = "N", global = true)]
+    pub jobs: Option<NonZeroUsize>,
+
+    /// Only report diagnostics not recorded in this baseline file, and list
+    /// baseline entries that no longer fire.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with = "write_baseline",
+        global = true
+    )]
+    pub baseline: Option<PathBuf>,
+
+    /// Record all current warnings/failures to this baseline file and exit 0.
+    #[arg(long, value_name = "FILE", global = true)]
+    pub write_baseline: Option<PathBuf>,
+
+    /// Rewrite mechanically correctable drift (old taxonomy, direct or split
+    /// demoswarm invocations, non-canonical OpenQ codes) in place, then run the checks.
+    #[arg(long, conflicts_with = "git_ref")]
+    pub fix: bool,
+
+    /// With --fix: print the rewrites as a unified diff instead of applying them.
+    #[arg(long, requires = "fix")]
+    pub dry_run: bool,
+
+    /// Print the agent handoff graph (commands, agents, routing, `.runs/`
+    /// artifacts) in this format instead of running the checks.
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        value_enum,
+        conflicts_with_all = ["fix", "baseline", "write_baseline"]
+    )]
+    pub graph: Option<GraphFormat>,
+
+    /// Keep running: re-run the checks affected by each edit under .claude/ or
+    /// to CLAUDE.md, and print the diagnostics that appeared or cleared.
+    #[arg(
+        long,
+        conflicts_with_all = ["fix", "graph", "write_baseline", "format", "git_ref"]
+    )]
+    pub watch: bool,
+
+    /// Serve diagnostics, hover, go-to-definition and agent-name completion
+    /// for the pack's markdown over the Language Server Protocol (stdio).
+    #[arg(
+        long,
+        conflicts_with_all = ["fix", "graph", "write_baseline", "format", "watch", "git_ref"]
+    )]
+    pub lsp: bool,
+
+    /// Check the pack as committed at this git revision (branch, tag or
+    /// commit), read with `git ls-tree` / `git cat-file` instead of from the
+    /// working tree. The pack is at --repo-root, or else the repository's
+    /// top level.
+    #[arg(long, value_name = "REV", global = true)]
+    pub git_ref: Option<String>,
+
+    /// Also run the docs checks (category `docs`): the indexes under
+    /// DIR/reference/ and the flow counts in README.md / CLAUDE.md against the
+    /// pack. DIR is relative to the repo root (usually `docs`).
+    #[arg(long, value_name = "DIR", global = true)]
+    pub docs_root: Option<PathBuf>,
+
+    /// Run only these check IDs (comma-separated or repeated).
+    #[arg(long, value_name = "ID", value_delimiter = ',', global = true)]
+    pub only: Vec<u32>,
+
+    /// Skip these check IDs (comma-separated or repeated).
+    #[arg(long, value_name = "ID", value_delimiter = ',', global = true)]
+    pub skip: Vec<u32>,
+
+    /// Run only checks in these categories (combined with --only as a union).
+    #[arg(
+        long = "category",
+        value_name = "CATEGORY",
+        value_enum,
+        value_delimiter = ',',
+        global = true
+    )]
+    pub categories: Vec<Category>,
+
+    /// Skip checks in these categories.
+    #[arg(
+        long = "skip-category",
+        value_name = "CATEGORY",
+        value_enum,
+        value_delimiter = ',',
+        global = true
+    )]
+    pub skip_categories: Vec<Category>,
+}
+
+impl Cli {
+    /// The check selection requested on the command line.
+    pub fn selection(&self) -> Selection {
+        Selection {
+            only: self.only.clone(),
+            skip: self.skip.clone(),
+            categories: self.categories.clone(),
+            skip_categories: self.skip_categories.clone(),
+        }
+    }
 }
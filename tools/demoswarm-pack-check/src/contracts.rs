@@ -17,90 +17,113 @@ pub mod headings {
 }
 
 /// All pack contracts bundled for easy passing.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Contracts {
-    pub required_agents: &'static [&'static str],
-    pub required_skills: &'static [&'static str],
-    pub cleanup_agents: &'static [(&'static str, &'static str)],
-    pub critics: &'static [&'static str],
-    pub critic_and_verifier_agents: &'static [&'static str],
-    /// Gate agents list. Currently only used in contract tests to verify
-    /// the list is complete. May be used in future checks.
-    #[allow(dead_code)]
-    pub gate_agents: &'static [&'static str],
-    pub gh_agents: &'static [&'static str],
-    pub banned_patterns: &'static [&'static str],
-    pub sealing_markers: &'static [&'static str],
-    pub repo_operator_result_fields: &'static [&'static str],
-    pub claude_md_sections: &'static [&'static str],
-    pub flow_specific_patterns: &'static [&'static str],
-    pub raw_git_patterns: &'static [(&'static str, &'static str)],
-    pub reg_marker_literal: &'static str,
+    pub required_agents: Vec<String>,
+    pub required_skills: Vec<String>,
+    pub cleanup_agents: Vec<(String, String)>,
+    pub critics: Vec<String>,
+    pub critic_and_verifier_agents: Vec<String>,
+    pub gate_agents: Vec<String>,
+    pub gh_agents: Vec<String>,
+    pub banned_patterns: Vec<String>,
+    pub sealing_markers: Vec<String>,
+    pub repo_operator_result_fields: Vec<String>,
+    pub claude_md_sections: Vec<String>,
+    pub flow_specific_patterns: Vec<String>,
+    pub raw_git_patterns: Vec<(String, String)>,
+    pub reg_marker_literal: String,
     // Skill ownership
-    pub index_allowed_agents: &'static [&'static str],
-    pub secrets_allowed_agents: &'static [&'static str],
-    pub openq_allowed_agents: &'static [&'static str],
+    pub index_allowed_agents: Vec<String>,
+    pub secrets_allowed_agents: Vec<String>,
+    pub openq_allowed_agents: Vec<String>,
     // GH body hygiene
-    pub gh_posting_agents: &'static [&'static str],
-    pub gh_body_forbidden_patterns: &'static [&'static str],
+    pub gh_posting_agents: Vec<String>,
+    pub gh_body_forbidden_patterns: Vec<String>,
     // Flow boundary enforcement (check 52)
-    pub skill_cli_subcommands: &'static [&'static str],
+    pub skill_cli_subcommands: Vec<String>,
     // OpenQ prefix validation (check 53)
-    pub openq_flow_codes: &'static [&'static str],
+    pub openq_flow_codes: Vec<String>,
+    // Frontmatter schema (check 70)
+    pub agent_models: Vec<String>,
+    pub agent_colors: Vec<String>,
+    pub known_tools: Vec<String>,
+    // Portable pack (check 73)
+    pub harness_patterns: Vec<String>,
+    pub harness_exceptions: Vec<String>,
 }
 
 impl Default for Contracts {
     fn default() -> Self {
         Self {
-            required_agents: REQUIRED_AGENTS,
-            required_skills: REQUIRED_SKILLS,
-            cleanup_agents: CLEANUP_AGENTS,
-            critics: CRITICS,
-            critic_and_verifier_agents: CRITIC_AND_VERIFIER_AGENTS,
-            gate_agents: GATE_AGENTS,
-            gh_agents: GH_AGENTS,
-            banned_patterns: BANNED_PATTERNS,
-            sealing_markers: SEALING_MARKERS,
-            repo_operator_result_fields: REPO_OPERATOR_RESULT_FIELDS,
-            claude_md_sections: CLAUDE_MD_SECTIONS,
-            flow_specific_patterns: FLOW_SPECIFIC_PATTERNS,
-            raw_git_patterns: RAW_GIT_PATTERNS,
-            reg_marker_literal: REG_MARKER_LITERAL,
-            index_allowed_agents: INDEX_ALLOWED_AGENTS,
-            secrets_allowed_agents: SECRETS_ALLOWED_AGENTS,
-            openq_allowed_agents: OPENQ_ALLOWED_AGENTS,
-            gh_posting_agents: GH_POSTING_AGENTS,
-            gh_body_forbidden_patterns: GH_BODY_FORBIDDEN_PATTERNS,
-            skill_cli_subcommands: SKILL_CLI_SUBCOMMANDS,
-            openq_flow_codes: OPENQ_FLOW_CODES,
+            required_agents: owned(REQUIRED_AGENTS),
+            required_skills: owned(REQUIRED_SKILLS),
+            cleanup_agents: owned_pairs(CLEANUP_AGENTS),
+            critics: owned(CRITICS),
+            critic_and_verifier_agents: owned(CRITIC_AND_VERIFIER_AGENTS),
+            gate_agents: owned(GATE_AGENTS),
+            gh_agents: owned(GH_AGENTS),
+            banned_patterns: owned(BANNED_PATTERNS),
+            sealing_markers: owned(SEALING_MARKERS),
+            repo_operator_result_fields: owned(REPO_OPERATOR_RESULT_FIELDS),
+            claude_md_sections: owned(CLAUDE_MD_SECTIONS),
+            flow_specific_patterns: owned(FLOW_SPECIFIC_PATTERNS),
+            raw_git_patterns: owned_pairs(RAW_GIT_PATTERNS),
+            reg_marker_literal: REG_MARKER_LITERAL.to_string(),
+            index_allowed_agents: owned(INDEX_ALLOWED_AGENTS),
+            secrets_allowed_agents: owned(SECRETS_ALLOWED_AGENTS),
+            openq_allowed_agents: owned(OPENQ_ALLOWED_AGENTS),
+            gh_posting_agents: owned(GH_POSTING_AGENTS),
+            gh_body_forbidden_patterns: owned(GH_BODY_FORBIDDEN_PATTERNS),
+            skill_cli_subcommands: owned(SKILL_CLI_SUBCOMMANDS),
+            openq_flow_codes: owned(OPENQ_FLOW_CODES),
+            agent_models: owned(AGENT_MODELS),
+            agent_colors: owned(AGENT_COLORS),
+            known_tools: owned(KNOWN_TOOLS),
+            harness_patterns: owned(HARNESS_PATTERNS),
+            harness_exceptions: owned(HARNESS_EXCEPTIONS),
         }
     }
 }
 
+fn owned(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+fn owned_pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+    items
+        .iter()
+        .map(|(a, b)| (a.to_string(), b.to_string()))
+        .collect()
+}
+
 /// Compiled regex patterns used across checks.
 pub struct Regexes {
-    // Machine Summary axis patterns.
-    // The following patterns are from the harness-era routing schema.
-    // Currently only used in contract tests to verify regex correctness.
-    // Kept for potential future reintroduction of structured routing checks.
-    #[allow(dead_code)]
+    // Machine Summary axis patterns from the harness-era routing schema.
+    // No check uses them; they are compiled only for the contract tests
+    // that pin the patterns. The axes themselves (`STATUS_VALUES`,
+    // `ACTION_VALUES`) are what the runs checks hold receipts to.
+    #[cfg(test)]
     pub canon_status: Regex,
+    #[cfg(test)]
     pub canon_action: Regex,
-    #[allow(dead_code)]
+    #[cfg(test)]
     pub route_to_agent: Regex,
-    #[allow(dead_code)]
+    #[cfg(test)]
     pub route_to_flow: Regex,
-    #[allow(dead_code)]
+    #[cfg(test)]
     pub recommended_action_present: Regex,
 
-    // Status validation (harness-era, kept for contract tests)
-    #[allow(dead_code)]
+    // Status validation (harness-era, test-only like the above)
+    #[cfg(test)]
     pub blocked_status: Regex,
 
     // Taxonomy
     pub old_fr_id: Regex,
     pub old_bdd_tag: Regex,
     pub bare_nfr_id: Regex,
+    /// Like `old_fr_id`, but captures the digits for `--fix` (REQ-*).
+    pub old_fr_id_rewrite: Regex,
 
     // Decision spine
     pub spine_marker: Regex,
@@ -151,14 +174,18 @@ pub struct Regexes {
     // Structured extraction calls (cross-agent contract checks)
     pub ms_get_invocation: Regex,
     pub inv_get_invocation: Regex,
+    pub receipt_get_invocation: Regex,
 
-    // Boundary check patterns (checks 45-47)
+    // Boundary check patterns (checks 57-59)
     pub skill_names_in_prose: Regex,
     pub demoswarm_shim_ref: Regex,
     pub flow_output_arrow: Regex,
 
     // GH body hygiene patterns (check 50)
     pub gh_heredoc_pattern: Regex,
+
+    // OpenQ prefixes (check 53)
+    pub openq_non_canonical_prefix: Regex,
 }
 
 impl Regexes {
@@ -176,23 +203,32 @@ impl Regexes {
 
         Ok(Self {
             // Machine Summary canonical axis lines (line-anchored, need multiline)
-            canon_status: ml(
-                r"^\s*status:\s*VERIFIED\s*\|\s*UNVERIFIED\s*\|\s*CANNOT_PROCEED\s*$",
-            )?,
-            canon_action: ml(
-                r"^\s*recommended_action:\s*PROCEED\s*\|\s*RERUN\s*\|\s*BOUNCE\s*\|\s*FIX_ENV\s*$",
-            )?,
+            #[cfg(test)]
+            canon_status: ml(&format!(
+                r"^\s*status:\s*{}\s*$",
+                STATUS_VALUES.join(r"\s*\|\s*")
+            ))?,
+            #[cfg(test)]
+            canon_action: ml(&format!(
+                r"^\s*recommended_action:\s*{}\s*$",
+                ACTION_VALUES.join(r"\s*\|\s*")
+            ))?,
+            #[cfg(test)]
             route_to_agent: ml(r"^\s*route_to_agent:")?,
+            #[cfg(test)]
             route_to_flow: ml(r"^\s*route_to_flow:")?,
+            #[cfg(test)]
             recommended_action_present: ml(r"^\s*recommended_action:")?,
 
             // Status validation
+            #[cfg(test)]
             blocked_status: Regex::new(r"status:.*BLOCKED[^_]|status:.*BLOCKED$")?,
 
             // Taxonomy
             old_fr_id: Regex::new(r"(^|[^A-Za-z0-9_])FR-[0-9]{1,3}([^A-Za-z0-9_]|$)")?,
             old_bdd_tag: Regex::new(r"@FR-")?,
             bare_nfr_id: Regex::new(r"(^|[^A-Za-z0-9_])NFR-[0-9]{1,3}([^A-Za-z0-9_]|$)")?,
+            old_fr_id_rewrite: Regex::new(r"(^|[^A-Za-z0-9_])FR-([0-9]{1,3})(?-u:\b)")?,
 
             // Decision spine
             spine_marker: Regex::new(r"ADR_CHOSEN_OPTION|ADR_DRIVER|DRIVER:")?,
@@ -256,15 +292,18 @@ impl Regexes {
             inv_get_invocation: dotall(
                 r###"(?m)^\s*bash\s+\.claude/scripts/demoswarm\.sh\s+inv\s+get.*?--file\s+"([^"]+)".*?--marker\s+"([^"]+)""###,
             )?,
+            receipt_get_invocation: dotall(
+                r###"(?m)^\s*bash\s+\.claude/scripts/demoswarm\.sh\s+receipt\s+get.*?--file\s+"?([^"\s]+)"?.*?--key\s+"?([^"\s]+)"?"###,
+            )?,
 
-            // Boundary check patterns (checks 45-47)
-            // Check 45: Skill names that should not appear in flow commands
+            // Boundary check patterns (checks 57-59)
+            // Check 57: Skill names that should not appear in flow commands
             skill_names_in_prose: Regex::new(
                 r"\b(runs-derive|runs-index|openq-tools|secrets-tools|test-runner|auto-linter|policy-runner)\b",
             )?,
-            // Check 45: CLI shim reference
+            // Check 57: CLI shim reference
             demoswarm_shim_ref: Regex::new(r"demoswarm\.sh")?,
-            // Check 47: Flow output arrows (agent -> file or agent -> .runs/)
+            // Check 59: Flow output arrows (agent -> file or agent -> .runs/)
             flow_output_arrow: Regex::new(
                 r"(agent|cleanup|author|critic|analyzer|designer|planner)\s*[-→>]+\s*\.?runs/",
             )?,
@@ -272,16 +311,157 @@ impl Regexes {
             // GH body hygiene (check 50): heredoc pattern for safe body passing
             // Matches: -f body="$(cat <<'EOF' or --body "$(cat <<'EOF'
             gh_heredoc_pattern: Regex::new(r#"(-f\s+body=|--body\s+)"\$\(cat\s+<<'EOF'"#)?,
+
+            // OpenQ (check 53): known non-canonical flow codes, for --fix
+            openq_non_canonical_prefix: Regex::new(r"OQ-(SIGNAL|PLN|BLD|REV|GAT|DEP|WIS)-")?,
         })
     }
+
+    /// Which checks (or their `--fix` rewrites) use each regex. An empty list
+    /// means no check uses it; the structure tests hold this against the check
+    /// sources. This is a test rather than part of check 69 because no pack
+    /// can change the regexes, only an edit to pack-check can.
+    ///
+    /// Every field must be listed: the destructuring below stops compiling when
+    /// a field is added without an entry.
+    #[cfg(test)]
+    pub fn users(&self) -> Vec<(&'static str, &'static [u32])> {
+        macro_rules! users {
+            ($($field:ident => [$($id:expr),*]),* $(,)?) => {{
+                let Self { $($field: _),* } = self;
+                vec![$((stringify!($field), &[$($id),*][..])),*]
+            }};
+        }
+
+        users! {
+            canon_status => [],
+            canon_action => [],
+            route_to_agent => [],
+            route_to_flow => [],
+            recommended_action_present => [],
+            blocked_status => [],
+            old_fr_id => [7],
+            old_bdd_tag => [7],
+            bare_nfr_id => [23],
+            old_fr_id_rewrite => [7],
+            spine_marker => [25],
+            status_enum => [43],
+            precedence => [43],
+            todo_status => [43],
+            smoke_signal => [36],
+            ensure_branch_op => [38],
+            claude_sub => [40],
+            domain_verdict => [30],
+            cannot_proceed_sem => [12],
+            reseal_pattern => [13],
+            reseal_guard => [44],
+            jq_has_runs => [26],
+            jq_quote => [26],
+            pr_first => [56],
+            issue_drafts => [42],
+            grep_reg_marker => [41],
+            bespoke_pipeline => [45],
+            index_upsert_cmd => [46],
+            secrets_cmd => [46],
+            openq_cmd => [46],
+            shim_line_continuation => [47],
+            direct_demoswarm_invocation => [48],
+            ms_get_invocation => [60],
+            inv_get_invocation => [49],
+            receipt_get_invocation => [81],
+            skill_names_in_prose => [57],
+            demoswarm_shim_ref => [57],
+            flow_output_arrow => [59],
+            gh_heredoc_pattern => [50],
+            openq_non_canonical_prefix => [53],
+        }
+    }
+}
+
+/// Skills whose subcommands are restricted to an allow-list of agents (check 46).
+const OWNED_SKILLS: [&str; 3] = ["runs-index", "secrets-tools", "openq-tools"];
+
+impl Contracts {
+    /// Contradictions between the contract tables (check 69).
+    ///
+    /// `pack_agents` are the agents the pack declares; an agent named in an
+    /// allow-list is known when it is required or present in the pack.
+    pub fn inconsistencies(&self, pack_agents: &[String]) -> Vec<String> {
+        let mut out = Vec::new();
+        let has = |list: &[String], item: &str| list.iter().any(|x| x == item);
+
+        for (agent, _) in &self.cleanup_agents {
+            if !has(&self.required_agents, agent) {
+                out.push(format!("cleanup agent {agent} is not a required agent"));
+            }
+        }
+        for critic in &self.critics {
+            if !has(&self.critic_and_verifier_agents, critic) {
+                out.push(format!(
+                    "critic {critic} is not in critic_and_verifier_agents"
+                ));
+            }
+        }
+        for gate in &self.gate_agents {
+            if !has(&self.critic_and_verifier_agents, gate) {
+                out.push(format!(
+                    "gate agent {gate} is not in critic_and_verifier_agents"
+                ));
+            }
+        }
+        for gh in &self.gh_agents {
+            if !has(&self.gh_posting_agents, gh) {
+                out.push(format!("GH agent {gh} is not in gh_posting_agents"));
+            }
+        }
+
+        let owners = [
+            ("index_allowed_agents", &self.index_allowed_agents),
+            ("secrets_allowed_agents", &self.secrets_allowed_agents),
+            ("openq_allowed_agents", &self.openq_allowed_agents),
+        ];
+        for (skill, (list_name, allowed)) in OWNED_SKILLS.iter().zip(owners) {
+            if !allowed.is_empty() && !has(&self.required_skills, skill) {
+                out.push(format!(
+                    "{list_name} restricts {skill}, which is not a required skill"
+                ));
+            }
+            for agent in allowed {
+                if !has(&self.required_agents, agent) && !has(pack_agents, agent) {
+                    out.push(format!("{list_name} names unknown agent {agent}"));
+                }
+            }
+        }
+
+        let lists = [
+            ("required_agents", &self.required_agents),
+            ("required_skills", &self.required_skills),
+            ("critics", &self.critics),
+            (
+                "critic_and_verifier_agents",
+                &self.critic_and_verifier_agents,
+            ),
+        ];
+        for (name, list) in lists {
+            let mut seen = std::collections::HashSet::new();
+            for item in list {
+                if !seen.insert(item) {
+                    out.push(format!("{name} lists {item} twice"));
+                }
+            }
+        }
+
+        out
+    }
 }
 
 /// Required agents (must exist in `.claude/agents/`).
 pub const REQUIRED_AGENTS: &[&str] = &[
-    // Cleanup agents (all 6 flows)
+    // Cleanup agents (all 7 flows)
     "signal-cleanup",
     "plan-cleanup",
     "build-cleanup",
+    "review-cleanup",
     "gate-cleanup",
     "deploy-cleanup",
     "wisdom-cleanup",
@@ -361,6 +541,12 @@ pub const REQUIRED_SKILLS: &[&str] = &[
     "secrets-tools",
 ];
 
+/// The status axis of Machine Summaries and receipts.
+pub const STATUS_VALUES: &[&str] = &["VERIFIED", "UNVERIFIED", "CANNOT_PROCEED"];
+
+/// The routing axis of Machine Summaries and receipts (`recommended_action`).
+pub const ACTION_VALUES: &[&str] = &["PROCEED", "RERUN", "BOUNCE", "FIX_ENV"];
+
 /// Cleanup agents with their expected receipt filenames.
 pub const CLEANUP_AGENTS: &[(&str, &str)] = &[
     ("signal-cleanup", "signal_receipt.json"),
@@ -525,6 +711,59 @@ pub const OPENQ_FLOW_CODES: &[&str] = &[
     "WISDOM", // Wisdom (Flow 7)
 ];
 
+/// Agent `model:` tiers (check 70), as in `docs/reference/model-allocation.md`.
+pub const AGENT_MODELS: &[&str] = &["inherit", "haiku", "sonnet"];
+
+/// Agent `color:` values (check 70), as in `docs/reference/visual-style.md`
+/// and the agent template.
+pub const AGENT_COLORS: &[&str] = &["purple", "red", "green", "blue", "orange", "cyan", "pink"];
+
+/// Tool names allowed in `tools:` / `allowed-tools:` (check 70). `mcp__*`
+/// tools and argument patterns (`Bash(git:*)`) are accepted on top.
+pub const KNOWN_TOOLS: &[&str] = &[
+    "AskUserQuestion",
+    "Bash",
+    "BashOutput",
+    "Edit",
+    "ExitPlanMode",
+    "Glob",
+    "Grep",
+    "KillShell",
+    "LS",
+    "MultiEdit",
+    "NotebookEdit",
+    "NotebookRead",
+    "Read",
+    "SlashCommand",
+    "Skill",
+    "Task",
+    "TodoWrite",
+    "WebFetch",
+    "WebSearch",
+    "Write",
+];
+
+/// Harness / Flow Studio references that must not appear anywhere in
+/// `.claude/` (check 73): users copy the pack into their own repos, where
+/// these names mean nothing. A `swarm/` path segment is matched on top.
+/// Unlike check 8, every line counts, frontmatter and quotes included.
+pub const HARNESS_PATTERNS: &[&str] = &[
+    "Flow Studio",    // Flow Studio UI
+    "flow-studio",    // Flow Studio references
+    "flow_studio",    // Flow Studio snake_case
+    "localhost:5000", // Flow Studio default port
+    "UIID",           // Flow Studio UI identifiers
+    "validate_swarm", // Harness validation script
+    "FR-OP-",         // Harness operationalization FRs (FR-OP-001..005)
+    "selftest",       // Harness selftest system
+    "swarm-ops",      // Harness ops agent
+];
+
+/// Lines containing one of these are exempt from check 73 (example FR ids
+/// in requirements templates).
+pub const HARNESS_EXCEPTIONS: &[&str] =
+    &["FR-001", "FR-002", "FR-003", "FR-004", "FR-005", "FR-006"];
+
 /// Test utilities: cached regex compilation for performance.
 ///
 /// Regex compilation is expensive (~5-10ms per call). Tests that repeatedly
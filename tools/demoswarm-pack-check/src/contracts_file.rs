@@ -0,0 +1,560 @@
+//! Pack-local contract overrides: `.claude/pack-contracts.{toml,json}`.
+//!
+//! The compiled defaults in `contracts.rs` describe the stock DemoSwarm pack.
+//! Forked packs that add agents or rename flows describe the difference in a
+//! versioned data file instead of patching pack-check:
+//!
+//! ```toml
+//! version = 1
+//!
+//! [required_agents]
+//! add = ["release-notes-author"]
+//! remove = ["gh-researcher"]
+//!
+//! [openq_flow_codes]
+//! replace = ["SIG", "PLAN", "BUILD", "SHIP"]
+//!
+//! [cleanup_agents.add]
+//! ship-cleanup = "ship_receipt.json"
+//! ```
+//!
+//! Every list in `Contracts` accepts `add` / `remove` (extend the defaults) or
+//! `replace` (override them). Unknown keys, a wrong version, or removing an
+//! entry that isn't there are errors, so a typo can't silently weaken a check.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+use serde::Deserialize;
+
+use crate::contracts::Contracts;
+use crate::files::FileProvider;
+
+/// The only schema version this build understands.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// File names looked up in `.claude/`, in order.
+pub const FILE_NAMES: &[&str] = &["pack-contracts.toml", "pack-contracts.json"];
+
+/// Patch for a plain string list.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListPatch {
+    #[serde(default)]
+    pub add: Vec<String>,
+    #[serde(default)]
+    pub remove: Vec<String>,
+    pub replace: Option<Vec<String>>,
+}
+
+/// Patch for a keyed list (`cleanup_agents`: agent -> receipt,
+/// `raw_git_patterns`: needle -> display). `remove` takes keys.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PairPatch {
+    #[serde(default)]
+    pub add: BTreeMap<String, String>,
+    #[serde(default)]
+    pub remove: Vec<String>,
+    pub replace: Option<BTreeMap<String, String>>,
+}
+
+/// On-disk shape of `pack-contracts.{toml,json}`. Field names match `Contracts`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContractsFile {
+    pub version: u32,
+    pub required_agents: Option<ListPatch>,
+    pub required_skills: Option<ListPatch>,
+    pub cleanup_agents: Option<PairPatch>,
+    pub critics: Option<ListPatch>,
+    pub critic_and_verifier_agents: Option<ListPatch>,
+    pub gate_agents: Option<ListPatch>,
+    pub gh_agents: Option<ListPatch>,
+    pub banned_patterns: Option<ListPatch>,
+    pub sealing_markers: Option<ListPatch>,
+    pub repo_operator_result_fields: Option<ListPatch>,
+    pub claude_md_sections: Option<ListPatch>,
+    pub flow_specific_patterns: Option<ListPatch>,
+    pub raw_git_patterns: Option<PairPatch>,
+    pub reg_marker_literal: Option<String>,
+    pub index_allowed_agents: Option<ListPatch>,
+    pub secrets_allowed_agents: Option<ListPatch>,
+    pub openq_allowed_agents: Option<ListPatch>,
+    pub gh_posting_agents: Option<ListPatch>,
+    pub gh_body_forbidden_patterns: Option<ListPatch>,
+    pub skill_cli_subcommands: Option<ListPatch>,
+    pub openq_flow_codes: Option<ListPatch>,
+    pub agent_models: Option<ListPatch>,
+    pub agent_colors: Option<ListPatch>,
+    pub known_tools: Option<ListPatch>,
+    pub harness_patterns: Option<ListPatch>,
+    pub harness_exceptions: Option<ListPatch>,
+}
+
+/// Returns the contracts file in `claude_dir`, if any.
+///
+/// Having both a TOML and a JSON file is an error: there is no sensible merge order.
+pub fn find(files: &dyn FileProvider, claude_dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+    let found: Vec<PathBuf> = FILE_NAMES
+        .iter()
+        .map(|name| claude_dir.join(name))
+        .filter(|p| files.is_file(p))
+        .collect();
+
+    match found.as_slice() {
+        [] => Ok(None),
+        [one] => Ok(Some(one.clone())),
+        _ => bail!(
+            "both .claude/{} and .claude/{} exist; keep only one",
+            FILE_NAMES[0],
+            FILE_NAMES[1]
+        ),
+    }
+}
+
+/// Default contracts with `.claude/pack-contracts.{toml,json}` applied, if present.
+pub fn load(files: &dyn FileProvider, claude_dir: &Path) -> anyhow::Result<Contracts> {
+    let mut contracts = Contracts::default();
+
+    if let Some(path) = find(files, claude_dir)? {
+        let text = files
+            .read_to_string(&path)
+            .with_context(|| format!("read {}", path.display()))?;
+        let file = parse(&path, &text).with_context(|| format!("invalid {}", path.display()))?;
+        file.apply(&mut contracts)
+            .with_context(|| format!("invalid {}", path.display()))?;
+    }
+
+    Ok(contracts)
+}
+
+/// Parse a contracts file; the format is chosen by extension.
+pub fn parse(path: &Path, text: &str) -> anyhow::Result<ContractsFile> {
+    let file: ContractsFile = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => serde_json::from_str(text)?,
+        _ => toml::from_str(text)?,
+    };
+
+    if file.version != SCHEMA_VERSION {
+        bail!(
+            "unsupported version {} (this pack-check understands version {SCHEMA_VERSION})",
+            file.version
+        );
+    }
+
+    Ok(file)
+}
+
+impl ContractsFile {
+    /// Apply every patch in the file on top of `c`.
+    pub fn apply(&self, c: &mut Contracts) -> anyhow::Result<()> {
+        apply_list(
+            "required_agents",
+            &self.required_agents,
+            &mut c.required_agents,
+        )?;
+        apply_list(
+            "required_skills",
+            &self.required_skills,
+            &mut c.required_skills,
+        )?;
+        apply_pairs(
+            "cleanup_agents",
+            &self.cleanup_agents,
+            &mut c.cleanup_agents,
+        )?;
+        apply_list("critics", &self.critics, &mut c.critics)?;
+        apply_list(
+            "critic_and_verifier_agents",
+            &self.critic_and_verifier_agents,
+            &mut c.critic_and_verifier_agents,
+        )?;
+        apply_list("gate_agents", &self.gate_agents, &mut c.gate_agents)?;
+        apply_list("gh_agents", &self.gh_agents, &mut c.gh_agents)?;
+        apply_list(
+            "banned_patterns",
+            &self.banned_patterns,
+            &mut c.banned_patterns,
+        )?;
+        apply_list(
+            "sealing_markers",
+            &self.sealing_markers,
+            &mut c.sealing_markers,
+        )?;
+        apply_list(
+            "repo_operator_result_fields",
+            &self.repo_operator_result_fields,
+            &mut c.repo_operator_result_fields,
+        )?;
+        apply_list(
+            "claude_md_sections",
+            &self.claude_md_sections,
+            &mut c.claude_md_sections,
+        )?;
+        apply_list(
+            "flow_specific_patterns",
+            &self.flow_specific_patterns,
+            &mut c.flow_specific_patterns,
+        )?;
+        apply_pairs(
+            "raw_git_patterns",
+            &self.raw_git_patterns,
+            &mut c.raw_git_patterns,
+        )?;
+        if let Some(literal) = &self.reg_marker_literal {
+            check_entry("reg_marker_literal", literal)?;
+            c.reg_marker_literal = literal.clone();
+        }
+        apply_list(
+            "index_allowed_agents",
+            &self.index_allowed_agents,
+            &mut c.index_allowed_agents,
+        )?;
+        apply_list(
+            "secrets_allowed_agents",
+            &self.secrets_allowed_agents,
+            &mut c.secrets_allowed_agents,
+        )?;
+        apply_list(
+            "openq_allowed_agents",
+            &self.openq_allowed_agents,
+            &mut c.openq_allowed_agents,
+        )?;
+        apply_list(
+            "gh_posting_agents",
+            &self.gh_posting_agents,
+            &mut c.gh_posting_agents,
+        )?;
+        apply_list(
+            "gh_body_forbidden_patterns",
+            &self.gh_body_forbidden_patterns,
+            &mut c.gh_body_forbidden_patterns,
+        )?;
+        apply_list(
+            "skill_cli_subcommands",
+            &self.skill_cli_subcommands,
+            &mut c.skill_cli_subcommands,
+        )?;
+        apply_list(
+            "openq_flow_codes",
+            &self.openq_flow_codes,
+            &mut c.openq_flow_codes,
+        )?;
+        apply_list("agent_models", &self.agent_models, &mut c.agent_models)?;
+        apply_list("agent_colors", &self.agent_colors, &mut c.agent_colors)?;
+        apply_list("known_tools", &self.known_tools, &mut c.known_tools)?;
+        apply_list(
+            "harness_patterns",
+            &self.harness_patterns,
+            &mut c.harness_patterns,
+        )?;
+        apply_list(
+            "harness_exceptions",
+            &self.harness_exceptions,
+            &mut c.harness_exceptions,
+        )?;
+        Ok(())
+    }
+}
+
+fn apply_list(
+    field: &str,
+    patch: &Option<ListPatch>,
+    list: &mut Vec<String>,
+) -> anyhow::Result<()> {
+    let Some(patch) = patch else {
+        return Ok(());
+    };
+
+    if let Some(replace) = &patch.replace {
+        if !patch.add.is_empty() || !patch.remove.is_empty() {
+            bail!("{field}: `replace` cannot be combined with `add` or `remove`");
+        }
+        for entry in replace {
+            check_entry(&format!("{field}.replace"), entry)?;
+        }
+        *list = replace.clone();
+        return Ok(());
+    }
+
+    for entry in &patch.remove {
+        let before = list.len();
+        list.retain(|e| e != entry);
+        if list.len() == before {
+            bail!("{field}.remove: {entry:?} is not in the default list");
+        }
+    }
+
+    for entry in &patch.add {
+        check_entry(&format!("{field}.add"), entry)?;
+        if !list.contains(entry) {
+            list.push(entry.clone());
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_pairs(
+    field: &str,
+    patch: &Option<PairPatch>,
+    pairs: &mut Vec<(String, String)>,
+) -> anyhow::Result<()> {
+    let Some(patch) = patch else {
+        return Ok(());
+    };
+
+    if let Some(replace) = &patch.replace {
+        if !patch.add.is_empty() || !patch.remove.is_empty() {
+            bail!("{field}: `replace` cannot be combined with `add` or `remove`");
+        }
+        for (key, value) in replace {
+            check_entry(&format!("{field}.replace"), key)?;
+            check_entry(&format!("{field}.replace.{key}"), value)?;
+        }
+        *pairs = replace
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        return Ok(());
+    }
+
+    for key in &patch.remove {
+        let before = pairs.len();
+        pairs.retain(|(k, _)| k != key);
+        if pairs.len() == before {
+            bail!("{field}.remove: {key:?} is not in the default list");
+        }
+    }
+
+    for (key, value) in &patch.add {
+        check_entry(&format!("{field}.add"), key)?;
+        check_entry(&format!("{field}.add.{key}"), value)?;
+        match pairs.iter_mut().find(|(k, _)| k == key) {
+            Some(existing) => existing.1 = value.clone(),
+            None => pairs.push((key.clone(), value.clone())),
+        }
+    }
+
+    Ok(())
+}
+
+fn check_entry(field: &str, entry: &str) -> anyhow::Result<()> {
+    if entry.trim().is_empty() {
+        bail!("{field}: entries must be non-empty strings");
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::DiskFiles;
+    use tempfile::TempDir;
+
+    fn toml_file(text: &str) -> anyhow::Result<ContractsFile> {
+        parse(Path::new("pack-contracts.toml"), text)
+    }
+
+    fn applied(text: &str) -> anyhow::Result<Contracts> {
+        let mut c = Contracts::default();
+        toml_file(text)?.apply(&mut c)?;
+        Ok(c)
+    }
+
+    #[test]
+    fn test_version_only_keeps_defaults() {
+        assert_eq!(applied("version = 1").unwrap(), Contracts::default());
+    }
+
+    #[test]
+    fn test_missing_version_is_error() {
+        let err = toml_file("[critics]\nadd = [\"x\"]").unwrap_err();
+        assert!(format!("{err:#}").contains("version"), "{err:#}");
+    }
+
+    #[test]
+    fn test_unsupported_version_is_error() {
+        let err = toml_file("version = 2").unwrap_err();
+        assert!(err.to_string().contains("unsupported version 2"), "{err}");
+    }
+
+    #[test]
+    fn test_unknown_field_is_error() {
+        let err = toml_file("version = 1\nrequired_agent = {}").unwrap_err();
+        assert!(format!("{err:#}").contains("required_agent"), "{err:#}");
+
+        let err = toml_file("version = 1\n[critics]\nappend = [\"x\"]").unwrap_err();
+        assert!(format!("{err:#}").contains("append"), "{err:#}");
+    }
+
+    #[test]
+    fn test_add_and_remove_extend_defaults() {
+        let c = applied(
+            r#"
+            version = 1
+            [required_agents]
+            add = ["release-notes-author", "clarifier"]
+            remove = ["gh-researcher"]
+            "#,
+        )
+        .unwrap();
+
+        assert!(
+            c.required_agents
+                .iter()
+                .any(|a| a == "release-notes-author")
+        );
+        assert!(!c.required_agents.iter().any(|a| a == "gh-researcher"));
+        // Adding an existing entry is a no-op.
+        assert_eq!(
+            c.required_agents
+                .iter()
+                .filter(|a| *a == "clarifier")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_replace_overrides_defaults() {
+        let c = applied(
+            r#"
+            version = 1
+            [openq_flow_codes]
+            replace = ["SIG", "SHIP"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(c.openq_flow_codes, vec!["SIG", "SHIP"]);
+    }
+
+    #[test]
+    fn test_replace_with_add_is_error() {
+        let err = applied(
+            r#"
+            version = 1
+            [critics]
+            replace = ["a"]
+            add = ["b"]
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("critics"), "{err}");
+    }
+
+    #[test]
+    fn test_remove_unknown_entry_is_error() {
+        let err = applied(
+            r#"
+            version = 1
+            [critics]
+            remove = ["not-a-critic"]
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not-a-critic"), "{err}");
+    }
+
+    #[test]
+    fn test_empty_entry_is_error() {
+        let err = applied(
+            r#"
+            version = 1
+            [gh_agents]
+            add = [" "]
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("gh_agents.add"), "{err}");
+    }
+
+    #[test]
+    fn test_pairs_add_update_and_remove() {
+        let c = applied(
+            r#"
+            version = 1
+            [cleanup_agents]
+            remove = ["review-cleanup"]
+            [cleanup_agents.add]
+            ship-cleanup = "ship_receipt.json"
+            plan-cleanup = "plan_receipt_v2.json"
+            "#,
+        )
+        .unwrap();
+
+        let receipt = |agent: &str| {
+            c.cleanup_agents
+                .iter()
+                .find(|(a, _)| a == agent)
+                .map(|(_, r)| r.as_str())
+        };
+        assert_eq!(receipt("ship-cleanup"), Some("ship_receipt.json"));
+        assert_eq!(receipt("plan-cleanup"), Some("plan_receipt_v2.json"));
+        assert_eq!(receipt("review-cleanup"), None);
+    }
+
+    #[test]
+    fn test_reg_marker_literal_override() {
+        let c = applied("version = 1\nreg_marker_literal = \"^## REG-\"").unwrap();
+        assert_eq!(c.reg_marker_literal, "^## REG-");
+    }
+
+    #[test]
+    fn test_json_format() {
+        let file = parse(
+            Path::new("pack-contracts.json"),
+            r#"{"version": 1, "gh_agents": {"add": ["gh-issue-resolver"]}}"#,
+        )
+        .unwrap();
+        let mut c = Contracts::default();
+        file.apply(&mut c).unwrap();
+        assert!(c.gh_agents.iter().any(|a| a == "gh-issue-resolver"));
+    }
+
+    #[test]
+    fn test_load_without_file_is_default() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(load(&DiskFiles, tmp.path()).unwrap(), Contracts::default());
+    }
+
+    #[test]
+    fn test_load_reads_toml_file() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("pack-contracts.toml"),
+            "version = 1\n[required_skills]\nadd = [\"deploy-tools\"]\n",
+        )
+        .unwrap();
+
+        let c = load(&DiskFiles, tmp.path()).unwrap();
+        assert!(c.required_skills.iter().any(|s| s == "deploy-tools"));
+    }
+
+    #[test]
+    fn test_load_error_names_file() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("pack-contracts.json"), "{ not json").unwrap();
+
+        let err = load(&DiskFiles, tmp.path()).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("pack-contracts.json"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn test_both_files_is_error() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("pack-contracts.toml"), "version = 1").unwrap();
+        std::fs::write(tmp.path().join("pack-contracts.json"), "{\"version\": 1}").unwrap();
+
+        assert!(find(&DiskFiles, tmp.path()).is_err());
+    }
+}
@@ -1,12 +1,20 @@
 use std::{
     cell::RefCell,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, OnceLock, RwLock},
 };
 
 use anyhow::{Context, bail};
 
+use crate::files::{DiskFiles, FileProvider, GitFiles};
+use crate::markdown::Markdown;
+
+/// The pack being checked, with caches shared by every check.
+///
+/// `Ctx` is `Sync`: checks run in parallel against one context. The `.claude/`
+/// tree is walked once, and file contents and parsed markdown are cached on
+/// first read (`preload` warms the cache with the inventory's files).
 #[derive(Debug)]
 pub struct Ctx {
     pub repo_root: PathBuf,
@@ -14,10 +22,65 @@ pub struct Ctx {
     pub agents_dir: PathBuf,
     pub commands_dir: PathBuf,
     pub skills_dir: PathBuf,
-    cache: RefCell<HashMap<PathBuf, Arc<str>>>,
+    /// The docs tree the `docs` checks compare against (`--docs-root`);
+    /// `None` when they don't run.
+    pub docs_root: Option<PathBuf>,
+    /// `.runs/` when the `runs` checks run (`pack-check runs`), else `None`.
+    pub runs_dir: Option<PathBuf>,
+    /// Limit the `runs` checks to this run (`--run-id`).
+    pub run_id: Option<String>,
+    files: Arc<dyn FileProvider>,
+    /// Every file under `claude_dir`, sorted; walked on first use.
+    claude_tree: OnceLock<Vec<PathBuf>>,
+    cache: RwLock<HashMap<PathBuf, Arc<str>>>,
+    md_cache: RwLock<HashMap<PathBuf, Arc<Markdown>>>,
+}
+
+/// What a check looked at through its `Ctx`: files it read or tested, and
+/// directories it listed. Watch mode re-runs a check when one of them changes.
+#[derive(Debug, Clone, Default)]
+pub struct Reads {
+    files: HashSet<PathBuf>,
+    dirs: HashSet<PathBuf>,
+}
+
+impl Reads {
+    /// Whether a change to `path` can change what the check saw.
+    pub fn touches(&self, path: &Path) -> bool {
+        self.files.contains(path) || self.dirs.iter().any(|d| path.starts_with(d))
+    }
+}
+
+thread_local! {
+    /// The reads of the check running on this thread, while traced.
+    static READS: RefCell<Option<Reads>> = const { RefCell::new(None) };
+}
+
+fn note_file(path: &Path) {
+    READS.with_borrow_mut(|reads| {
+        if let Some(reads) = reads {
+            reads.files.insert(path.to_path_buf());
+        }
+    });
+}
+
+fn note_dir(path: &Path) {
+    READS.with_borrow_mut(|reads| {
+        if let Some(reads) = reads {
+            reads.dirs.insert(path.to_path_buf());
+        }
+    });
 }
 
 impl Ctx {
+    /// Run `f`, collecting what it reads through any `Ctx` on this thread.
+    pub fn traced<T>(f: impl FnOnce() -> T) -> (T, Reads) {
+        let outer = READS.replace(Some(Reads::default()));
+        let out = f();
+        let reads = READS.replace(outer).unwrap_or_default();
+        (out, reads)
+    }
+
     pub fn discover(repo_root_arg: Option<PathBuf>) -> anyhow::Result<Self> {
         let repo_root = match repo_root_arg {
             Some(p) => p,
@@ -27,8 +90,31 @@ impl Ctx {
             format!("Failed to canonicalize repo root: {}", repo_root.display())
         })?;
 
+        Self::with_files(repo_root, Arc::new(DiskFiles))
+    }
+
+    /// The pack as committed at git revision `rev`, read from the object
+    /// store rather than the work tree. The pack is at `repo_root_arg`, or
+    /// else the top level of the repository containing the current directory.
+    pub fn discover_at_git_ref(repo_root_arg: Option<PathBuf>, rev: &str) -> anyhow::Result<Self> {
+        let dir = repo_root_arg.clone().unwrap_or_else(|| PathBuf::from("."));
+        let files = GitFiles::open(&dir, rev)?;
+        let repo_root = match repo_root_arg {
+            Some(p) => p
+                .canonicalize()
+                .with_context(|| format!("Failed to canonicalize repo root: {}", p.display()))?,
+            None => files.toplevel().to_path_buf(),
+        };
+
+        Self::with_files(repo_root, Arc::new(files))
+            .with_context(|| format!("at git revision `{rev}`"))
+    }
+
+    /// A context reading the pack at `repo_root` through `files`. The root is
+    /// used as given (no canonicalization), so it may be a virtual path.
+    pub fn with_files(repo_root: PathBuf, files: Arc<dyn FileProvider>) -> anyhow::Result<Self> {
         let claude_dir = repo_root.join(".claude");
-        if !claude_dir.is_dir() {
+        if !files.is_dir(&claude_dir) {
             bail!(
                 "Repo root does not contain a .claude/ directory: {}",
                 repo_root.display()
@@ -41,7 +127,13 @@ impl Ctx {
             skills_dir: claude_dir.join("skills"),
             repo_root,
             claude_dir,
-            cache: RefCell::new(HashMap::new()),
+            docs_root: None,
+            runs_dir: None,
+            run_id: None,
+            files,
+            claude_tree: OnceLock::new(),
+            cache: RwLock::new(HashMap::new()),
+            md_cache: RwLock::new(HashMap::new()),
         })
     }
 
@@ -72,19 +164,97 @@ impl Ctx {
     }
 
     pub fn read_utf8(&self, path: &Path) -> anyhow::Result<Arc<str>> {
-        if let Some(hit) = self.cache.borrow().get(path).cloned() {
+        note_file(path);
+        if let Some(hit) = read_lock(&self.cache).get(path).cloned() {
             return Ok(hit);
         }
 
-        let s = std::fs::read_to_string(path)
+        // Two checks may race to read the same file; both get equal contents.
+        let s = self
+            .files
+            .read_to_string(path)
             .with_context(|| format!("Failed to read file as UTF-8: {}", path.display()))?;
         let arc: Arc<str> = Arc::from(s);
-        self.cache
-            .borrow_mut()
-            .insert(path.to_path_buf(), arc.clone());
+        write_lock(&self.cache).insert(path.to_path_buf(), arc.clone());
         Ok(arc)
     }
 
+    /// The parsed markdown model of a file (cached alongside the raw text).
+    pub fn markdown(&self, path: &Path) -> anyhow::Result<Arc<Markdown>> {
+        note_file(path);
+        if let Some(hit) = read_lock(&self.md_cache).get(path).cloned() {
+            return Ok(hit);
+        }
+
+        let md = Arc::new(Markdown::parse(self.read_utf8(path)?));
+        write_lock(&self.md_cache).insert(path.to_path_buf(), md.clone());
+        Ok(md)
+    }
+
+    /// Read `paths` into the cache up front. Unreadable files are skipped here
+    /// and reported by whichever check reads them.
+    pub fn preload<'a>(&self, paths: impl IntoIterator<Item = &'a PathBuf>) {
+        for path in paths {
+            let _ = self.read_utf8(path);
+        }
+    }
+
+    /// Forget the cached contents of `paths`, and with `tree_changed` the
+    /// `.claude/` walk too (files were added or removed).
+    pub fn invalidate(&mut self, paths: &[PathBuf], tree_changed: bool) {
+        let cache = self.cache.get_mut().unwrap_or_else(|e| e.into_inner());
+        let md_cache = self.md_cache.get_mut().unwrap_or_else(|e| e.into_inner());
+        for path in paths {
+            cache.remove(path);
+            md_cache.remove(path);
+        }
+        if tree_changed {
+            self.claude_tree.take();
+        }
+    }
+
+    pub fn is_file(&self, path: &Path) -> bool {
+        note_file(path);
+        match self.claude_tree(path) {
+            Some(tree) => tree.binary_search_by(|p| p.as_path().cmp(path)).is_ok(),
+            None => self.files.is_file(path),
+        }
+    }
+
+    pub fn is_dir(&self, path: &Path) -> bool {
+        note_dir(path);
+        self.files.is_dir(path)
+    }
+
+    /// Every file under `dir` (recursively), sorted.
+    pub fn files_under(&self, dir: &Path) -> Vec<PathBuf> {
+        note_dir(dir);
+        match self.claude_tree(dir) {
+            Some(tree) => tree
+                .iter()
+                .filter(|p| p.starts_with(dir) && p.as_path() != dir)
+                .cloned()
+                .collect(),
+            None => self.files.files_under(dir),
+        }
+    }
+
+    /// The cached `.claude/` walk, if `path` is inside `.claude/`.
+    fn claude_tree(&self, path: &Path) -> Option<&[PathBuf]> {
+        if !path.starts_with(&self.claude_dir) {
+            return None;
+        }
+        Some(
+            self.claude_tree
+                .get_or_init(|| self.files.files_under(&self.claude_dir)),
+        )
+    }
+
+    /// The provider the pack is read through.
+    pub fn files(&self) -> &dyn FileProvider {
+        &*self.files
+    }
+
     pub fn rel(&self, path: &Path) -> String {
         path.strip_prefix(&self.repo_root)
             .map(|p| p.display().to_string())
@@ -92,30 +262,42 @@ impl Ctx {
     }
 
     pub fn agent_md_files(&self) -> anyhow::Result<Vec<PathBuf>> {
-        list_md_files(&self.agents_dir)
+        Ok(md_files_in(
+            self.files_under(&self.agents_dir),
+            &self.agents_dir,
+        ))
     }
 
     pub fn command_md_files(&self) -> anyhow::Result<Vec<PathBuf>> {
-        list_md_files(&self.commands_dir)
+        Ok(md_files_in(
+            self.files_under(&self.commands_dir),
+            &self.commands_dir,
+        ))
     }
 }
 
-fn list_md_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
-    let mut out = Vec::new();
-    if !dir.is_dir() {
-        return Ok(out);
-    }
-    for entry in std::fs::read_dir(dir)
-        .with_context(|| format!("Failed to read directory: {}", dir.display()))?
-    {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_file() && path.extension().is_some_and(|e| e == "md") {
-            out.push(path);
-        }
-    }
-    out.sort();
-    Ok(out)
+// A poisoned lock only means another check panicked mid-insert; the map is
+// still a valid cache.
+fn read_lock<T>(lock: &RwLock<T>) -> std::sync::RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn write_lock<T>(lock: &RwLock<T>) -> std::sync::RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The `.md` files directly in `dir`, sorted.
+#[cfg(test)]
+fn list_md_files(files: &dyn FileProvider, dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    Ok(md_files_in(files.files_under(dir), dir))
+}
+
+/// The `.md` files of a recursive listing of `dir` that sit directly in `dir`.
+fn md_files_in(listing: Vec<PathBuf>, dir: &Path) -> Vec<PathBuf> {
+    listing
+        .into_iter()
+        .filter(|p| p.parent() == Some(dir) && p.extension().is_some_and(|e| e == "md"))
+        .collect()
 }
 
 // =============================================================================
@@ -269,6 +451,31 @@ mod tests {
         );
     }
 
+    /// Test that checks can share one context across threads.
+    #[test]
+    fn test_ctx_is_sync() {
+        fn assert_sync<T: Sync + Send>() {}
+        assert_sync::<Ctx>();
+    }
+
+    /// Test that the `.claude/` walk is cached while files outside it are not.
+    #[test]
+    fn test_files_under_caches_claude_tree() {
+        let tmp = TempDir::new().unwrap();
+        let agents = tmp.path().join(".claude/agents");
+        std::fs::create_dir_all(&agents).unwrap();
+        std::fs::write(agents.join("a.md"), "a").unwrap();
+
+        let ctx = Ctx::discover(Some(tmp.path().to_path_buf())).unwrap();
+        assert_eq!(ctx.files_under(&ctx.agents_dir).len(), 1);
+        assert!(ctx.is_file(&ctx.agents_dir.join("a.md")));
+
+        std::fs::write(agents.join("b.md"), "b").unwrap();
+        std::fs::write(tmp.path().join("CLAUDE.md"), "c").unwrap();
+        assert_eq!(ctx.files_under(&ctx.agents_dir).len(), 1);
+        assert!(ctx.is_file(&ctx.repo_root.join("CLAUDE.md")));
+    }
+
     // -------------------------------------------------------------------------
     // list_md_files tests
     // -------------------------------------------------------------------------
@@ -279,7 +486,7 @@ mod tests {
     fn test_list_md_files_nonexistent_dir() {
         let nonexistent = PathBuf::from("/definitely/not/a/real/dir/xyz789");
 
-        let result = list_md_files(&nonexistent).unwrap();
+        let result = list_md_files(&DiskFiles, &nonexistent).unwrap();
 
         assert!(
             result.is_empty(),
@@ -298,7 +505,7 @@ mod tests {
         std::fs::write(tmp.path().join("file3.txt"), "text").unwrap();
         std::fs::write(tmp.path().join("file4.rs"), "rust").unwrap();
 
-        let result = list_md_files(tmp.path()).unwrap();
+        let result = list_md_files(&DiskFiles, tmp.path()).unwrap();
 
         assert_eq!(result.len(), 2, "Should only return .md files");
         for path in &result {
@@ -320,7 +527,7 @@ mod tests {
         std::fs::write(tmp.path().join("a_file.md"), "").unwrap();
         std::fs::write(tmp.path().join("m_file.md"), "").unwrap();
 
-        let result = list_md_files(tmp.path()).unwrap();
+        let result = list_md_files(&DiskFiles, tmp.path()).unwrap();
 
         assert_eq!(result.len(), 3);
         assert!(
@@ -343,7 +550,7 @@ mod tests {
         std::fs::write(tmp.path().join("file.md"), "content").unwrap();
         std::fs::create_dir(tmp.path().join("subdir.md")).unwrap(); // A directory with .md name
 
-        let result = list_md_files(tmp.path()).unwrap();
+        let result = list_md_files(&DiskFiles, tmp.path()).unwrap();
 
         assert_eq!(result.len(), 1, "Should only return files, not directories");
         assert!(result[0].is_file());
@@ -399,4 +606,34 @@ mod tests {
 
         assert!(result.is_empty());
     }
+
+    #[test]
+    fn test_traced_reads_and_invalidate() {
+        let tmp = TempDir::new().unwrap();
+        let agents = tmp.path().join(".claude/agents");
+        std::fs::create_dir_all(&agents).unwrap();
+        let a = agents.join("a.md");
+        std::fs::write(&a, "one").unwrap();
+        let mut ctx = Ctx::discover(Some(tmp.path().to_path_buf())).unwrap();
+
+        let (text, reads) = Ctx::traced(|| ctx.read_utf8(&ctx.agents_dir.join("a.md")).unwrap());
+        assert_eq!(&*text, "one");
+        let a = ctx.agents_dir.join("a.md");
+        assert!(reads.touches(&a));
+        assert!(!reads.touches(&ctx.agents_dir.join("b.md")));
+
+        let (_, reads) = Ctx::traced(|| ctx.files_under(&ctx.agents_dir));
+        assert!(reads.touches(&ctx.agents_dir.join("b.md")));
+        assert!(!Ctx::traced(|| ()).1.touches(&a));
+
+        std::fs::write(&a, "two").unwrap();
+        assert_eq!(&*ctx.read_utf8(&a).unwrap(), "one", "cached");
+        ctx.invalidate(std::slice::from_ref(&a), false);
+        assert_eq!(&*ctx.read_utf8(&a).unwrap(), "two");
+
+        std::fs::write(ctx.agents_dir.join("b.md"), "").unwrap();
+        assert_eq!(ctx.files_under(&ctx.agents_dir).len(), 1, "cached");
+        ctx.invalidate(&[], true);
+        assert_eq!(ctx.files_under(&ctx.agents_dir).len(), 2);
+    }
 }
@@ -0,0 +1,566 @@
+//! Pack diff: what changed, in contract terms, between two versions of a pack.
+//!
+//! `pack-check diff --from A --to B` loads both packs (directories or git
+//! revisions) and compares what the rest of the pack leans on: which agents
+//! and skills exist, their frontmatter, the Machine Summary keys each producer
+//! documents, the `inv get` markers each agent reads, and the agents each flow
+//! invokes. The result is an upgrade checklist, printed as text or JSON.
+//!
+//! Not a check: nothing here reports diagnostics.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+use anyhow::Context;
+use serde::Serialize;
+
+use crate::frontmatter::{Field, Fields};
+
+use crate::checks::{CheckCtx, HandoffGraph, extract_machine_summary_blocks, pack_entries};
+
+/// The contract changes from one pack to another.
+#[derive(Debug, Default, Serialize)]
+pub struct PackDiff {
+    /// How the old pack was named on the command line.
+    pub from: String,
+    pub to: String,
+    pub agents: NameChanges,
+    pub skills: NameChanges,
+    /// Frontmatter fields that changed, on agents and skills in both packs.
+    pub frontmatter: Vec<FrontmatterChange>,
+    /// Machine Summary keys, per agent in both packs.
+    pub machine_summary: Vec<SetChange<String>>,
+    /// `inv get` markers read, per agent in both packs.
+    pub inv_markers: Vec<SetChange<InvMarker>>,
+    /// Flows whose agent sequence changed, or that were added or removed.
+    pub flows: Vec<FlowChange>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct NameChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FrontmatterChange {
+    /// `agent` or `skill`.
+    pub kind: &'static str,
+    pub name: String,
+    pub field: String,
+    /// `None` when the field was added.
+    pub from: Option<String>,
+    /// `None` when the field was removed.
+    pub to: Option<String>,
+}
+
+/// Items one agent gained or lost.
+#[derive(Debug, Serialize)]
+pub struct SetChange<T> {
+    pub agent: String,
+    pub added: Vec<T>,
+    pub removed: Vec<T>,
+}
+
+/// A `demoswarm.sh inv get --file F --marker M` call.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct InvMarker {
+    pub file: String,
+    pub marker: String,
+}
+
+/// A flow command's agents, in the order it first calls them.
+#[derive(Debug, Serialize)]
+pub struct FlowChange {
+    /// The flow command's stem (`flow-3-build`).
+    pub flow: String,
+    /// `None` when the flow was added.
+    pub from: Option<Vec<String>>,
+    /// `None` when the flow was removed.
+    pub to: Option<Vec<String>>,
+}
+
+/// Compare the pack `from` (labelled `from_label`) with `to`.
+pub fn diff_packs(
+    from_label: &str,
+    from: &CheckCtx,
+    to_label: &str,
+    to: &CheckCtx,
+) -> anyhow::Result<PackDiff> {
+    let old = Snapshot::load(from).with_context(|| format!("read {from_label}"))?;
+    let new = Snapshot::load(to).with_context(|| format!("read {to_label}"))?;
+
+    let mut diff = PackDiff {
+        from: from_label.to_string(),
+        to: to_label.to_string(),
+        agents: name_changes(&old.agents, &new.agents),
+        skills: name_changes(&old.skills, &new.skills),
+        ..PackDiff::default()
+    };
+
+    for (name, a, b) in common(&old.agents, &new.agents) {
+        diff.frontmatter
+            .extend(field_changes("agent", name, &a.frontmatter, &b.frontmatter));
+        if let Some(change) = set_change(name, &a.summary_keys, &b.summary_keys) {
+            diff.machine_summary.push(change);
+        }
+        if let Some(change) = set_change(name, &a.inv_markers, &b.inv_markers) {
+            diff.inv_markers.push(change);
+        }
+    }
+    for (name, a, b) in common(&old.skills, &new.skills) {
+        diff.frontmatter.extend(field_changes("skill", name, a, b));
+    }
+
+    let flows: BTreeSet<&String> = old.flows.keys().chain(new.flows.keys()).collect();
+    for flow in flows {
+        let (a, b) = (old.flows.get(flow), new.flows.get(flow));
+        if a != b {
+            diff.flows.push(FlowChange {
+                flow: flow.clone(),
+                from: a.cloned(),
+                to: b.cloned(),
+            });
+        }
+    }
+
+    Ok(diff)
+}
+
+impl PackDiff {
+    /// Whether the packs have the same contracts.
+    pub fn is_empty(&self) -> bool {
+        self.agents.added.is_empty()
+            && self.agents.removed.is_empty()
+            && self.skills.added.is_empty()
+            && self.skills.removed.is_empty()
+            && self.frontmatter.is_empty()
+            && self.machine_summary.is_empty()
+            && self.inv_markers.is_empty()
+            && self.flows.is_empty()
+    }
+
+    /// The checklist as text: one section per kind of change, omitting the
+    /// kinds with none.
+    pub fn render_text(&self) -> String {
+        let mut out = format!("Pack diff: {} → {}\n", self.from, self.to);
+        if self.is_empty() {
+            out.push_str("\nNo contract changes.\n");
+            return out;
+        }
+
+        for (title, names) in [("Agents", &self.agents), ("Skills", &self.skills)] {
+            if names.added.is_empty() && names.removed.is_empty() {
+                continue;
+            }
+            let _ = writeln!(
+                out,
+                "\n{title}: {} added, {} removed",
+                names.added.len(),
+                names.removed.len()
+            );
+            for name in &names.added {
+                let _ = writeln!(out, "  + {name}");
+            }
+            for name in &names.removed {
+                let _ = writeln!(out, "  - {name}");
+            }
+        }
+
+        if !self.frontmatter.is_empty() {
+            let _ = writeln!(
+                out,
+                "\nFrontmatter: {}",
+                count(self.frontmatter.len(), "change")
+            );
+            for c in &self.frontmatter {
+                let change = match (&c.from, &c.to) {
+                    (Some(a), Some(b)) => format!("{a} → {b}"),
+                    (None, Some(b)) => format!("added ({b})"),
+                    (Some(a), None) => format!("removed (was {a})"),
+                    (None, None) => continue,
+                };
+                let _ = writeln!(out, "  {} {}: {}: {change}", c.kind, c.name, c.field);
+            }
+        }
+
+        let markers: Vec<SetChange<String>> = self
+            .inv_markers
+            .iter()
+            .map(|c| SetChange {
+                agent: c.agent.clone(),
+                added: c.added.iter().map(InvMarker::to_string).collect(),
+                removed: c.removed.iter().map(InvMarker::to_string).collect(),
+            })
+            .collect();
+        for (title, changes) in [
+            ("Machine Summary keys", &self.machine_summary),
+            ("Inventory markers read (inv get)", &markers),
+        ] {
+            if changes.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "\n{title}: {}", count(changes.len(), "agent"));
+            for c in changes {
+                let mut items: Vec<String> = c.added.iter().map(|k| format!("+ {k}")).collect();
+                items.extend(c.removed.iter().map(|k| format!("- {k}")));
+                let _ = writeln!(out, "  {}: {}", c.agent, items.join(", "));
+            }
+        }
+
+        if !self.flows.is_empty() {
+            let _ = writeln!(
+                out,
+                "\nFlow agent sequences: {}",
+                count(self.flows.len(), "flow")
+            );
+            for c in &self.flows {
+                let change = match (&c.from, &c.to) {
+                    (Some(a), Some(b)) => format!("{} → {}", a.join(", "), b.join(", ")),
+                    (None, Some(b)) => format!("added ({})", b.join(", ")),
+                    (Some(a), None) => format!("removed (was {})", a.join(", ")),
+                    (None, None) => continue,
+                };
+                let _ = writeln!(out, "  {}: {change}", c.flow);
+            }
+        }
+
+        out
+    }
+}
+
+impl std::fmt::Display for InvMarker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} in {}", self.marker, self.file)
+    }
+}
+
+/// What one pack exposes to the rest of the pack.
+struct Snapshot {
+    agents: BTreeMap<String, AgentContract>,
+    /// Skill name → frontmatter.
+    skills: BTreeMap<String, BTreeMap<String, String>>,
+    /// Flow command stem → agents it invokes.
+    flows: BTreeMap<String, Vec<String>>,
+}
+
+struct AgentContract {
+    frontmatter: BTreeMap<String, String>,
+    summary_keys: BTreeSet<String>,
+    inv_markers: BTreeSet<InvMarker>,
+}
+
+impl Snapshot {
+    fn load(cx: &CheckCtx) -> anyhow::Result<Self> {
+        let [(_, agent_files), (_, skill_files)] = pack_entries(cx);
+
+        let mut agents = BTreeMap::new();
+        for (name, path) in agent_files {
+            let content = cx.ctx.read_utf8(&path)?;
+            let inv_markers = cx
+                .re
+                .inv_get_invocation
+                .captures_iter(&content)
+                .map(|cap| InvMarker {
+                    file: cap[1].to_string(),
+                    marker: cap[2].to_string(),
+                })
+                .collect();
+            agents.insert(
+                name,
+                AgentContract {
+                    frontmatter: frontmatter(cx, &path)?,
+                    summary_keys: machine_summary_keys(&content),
+                    inv_markers,
+                },
+            );
+        }
+
+        let mut skills = BTreeMap::new();
+        for (name, path) in skill_files {
+            skills.insert(name, frontmatter(cx, &path)?);
+        }
+
+        let graph = HandoffGraph::build(cx).context("build handoff graph")?;
+        let flows = cx
+            .flows()
+            .flows
+            .iter()
+            .map(|flow| {
+                let stem = flow.stem();
+                let agents = graph
+                    .invoked_by(&stem)
+                    .into_iter()
+                    .map(String::from)
+                    .collect();
+                (stem, agents)
+            })
+            .collect();
+
+        Ok(Self {
+            agents,
+            skills,
+            flows,
+        })
+    }
+}
+
+/// A file's frontmatter fields as text; empty if it has none or it isn't
+/// valid YAML (check 70 reports that).
+fn frontmatter(cx: &CheckCtx, path: &std::path::Path) -> anyhow::Result<BTreeMap<String, String>> {
+    let md = cx.ctx.markdown(path)?;
+    let Some(fields) = md
+        .frontmatter
+        .as_ref()
+        .and_then(|fm| Fields::parse(fm).ok())
+    else {
+        return Ok(BTreeMap::new());
+    };
+    Ok(fields
+        .iter()
+        .map(|f| (f.key.clone(), field_text(f)))
+        .collect())
+}
+
+/// A field's value on one line: scalars as text, lists comma-separated,
+/// anything else as flow-style YAML.
+fn field_text(field: &Field) -> String {
+    if let Some(text) = field.text() {
+        return text;
+    }
+    if let Some(list) = field.list() {
+        return list.join(", ");
+    }
+    serde_norway::to_string(&field.value)
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The `key:` names in an agent's `## Machine Summary` sections. Nested keys
+/// count by their own name, as `ms get` resolves them segment by segment.
+fn machine_summary_keys(content: &str) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    for block in extract_machine_summary_blocks(content) {
+        for line in block.lines() {
+            let t = line.trim_start();
+            let t = t.strip_prefix("- ").unwrap_or(t);
+            let Some((key, _)) = t.split_once(':') else {
+                continue;
+            };
+            let is_key = key.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+            if is_key {
+                keys.insert(key.to_string());
+            }
+        }
+    }
+    keys
+}
+
+fn name_changes<V>(old: &BTreeMap<String, V>, new: &BTreeMap<String, V>) -> NameChanges {
+    NameChanges {
+        added: new
+            .keys()
+            .filter(|k| !old.contains_key(*k))
+            .cloned()
+            .collect(),
+        removed: old
+            .keys()
+            .filter(|k| !new.contains_key(*k))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// The entries in both maps, by name.
+fn common<'a, V>(
+    old: &'a BTreeMap<String, V>,
+    new: &'a BTreeMap<String, V>,
+) -> impl Iterator<Item = (&'a String, &'a V, &'a V)> {
+    old.iter()
+        .filter_map(|(name, a)| Some((name, a, new.get(name)?)))
+}
+
+fn field_changes(
+    kind: &'static str,
+    name: &str,
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+) -> Vec<FrontmatterChange> {
+    let fields: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    fields
+        .into_iter()
+        .filter(|f| old.get(*f) != new.get(*f))
+        .map(|f| FrontmatterChange {
+            kind,
+            name: name.to_string(),
+            field: f.clone(),
+            from: old.get(f).cloned(),
+            to: new.get(f).cloned(),
+        })
+        .collect()
+}
+
+fn set_change<T: Ord + Clone>(
+    agent: &str,
+    old: &BTreeSet<T>,
+    new: &BTreeSet<T>,
+) -> Option<SetChange<T>> {
+    let added: Vec<T> = new.difference(old).cloned().collect();
+    let removed: Vec<T> = old.difference(new).cloned().collect();
+    (!added.is_empty() || !removed.is_empty()).then(|| SetChange {
+        agent: agent.to_string(),
+        added,
+        removed,
+    })
+}
+
+fn count(n: usize, noun: &str) -> String {
+    format!("{n} {noun}{}", if n == 1 { "" } else { "s" })
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::PackChecker;
+    use crate::files::MemoryFiles;
+
+    fn pack(root: &str, files: &[(&str, &str)]) -> MemoryFiles {
+        files
+            .iter()
+            .map(|(rel, content)| (format!("{root}/.claude/{rel}"), content.to_string()))
+            .collect()
+    }
+
+    fn diff(old: &[(&str, &str)], new: &[(&str, &str)]) -> PackDiff {
+        let a = PackChecker::new("a").files(pack("a", old)).load().unwrap();
+        let b = PackChecker::new("b").files(pack("b", new)).load().unwrap();
+        diff_packs("old", &a.cx(), "new", &b.cx()).unwrap()
+    }
+
+    const FLOW: &str = "# Flow 3\n\n## Steps\n\n- [ ] code-implementer\n- [ ] build-cleanup\n";
+    const IMPLEMENTER: &str = "---\nname: code-implementer\nmodel: sonnet\n---\n\n\
+         ## Machine Summary\n\nstatus: VERIFIED\nconcerns: []\n";
+    const CLEANUP: &str = "---\nname: build-cleanup\n---\n\n\
+         ```bash\nbash .claude/scripts/demoswarm.sh inv get --file \".runs/x/build/ci.md\" --marker \"CI_STATUS\"\n```\n";
+
+    #[test]
+    fn test_identical_packs_have_no_changes() {
+        let files = [
+            ("commands/flow-3-build.md", FLOW),
+            ("agents/code-implementer.md", IMPLEMENTER),
+        ];
+        let d = diff(&files, &files);
+        assert!(d.is_empty());
+        assert_eq!(
+            d.render_text(),
+            "Pack diff: old → new\n\nNo contract changes.\n"
+        );
+    }
+
+    /// Test: Each kind of contract change is reported, and only on entries in
+    /// both packs (an added agent is just added).
+    #[test]
+    fn test_contract_changes() {
+        let d = diff(
+            &[
+                ("commands/flow-3-build.md", FLOW),
+                ("agents/code-implementer.md", IMPLEMENTER),
+                ("agents/build-cleanup.md", CLEANUP),
+                ("agents/old-agent.md", "---\nname: old-agent\n---\n"),
+                (
+                    "skills/runs-derive/SKILL.md",
+                    "---\nname: runs-derive\n---\n",
+                ),
+            ],
+            &[
+                (
+                    "commands/flow-3-build.md",
+                    "# Flow 3\n\n## Steps\n\n- [ ] code-implementer\n- [ ] code-critic\n- [ ] build-cleanup\n",
+                ),
+                (
+                    "agents/code-implementer.md",
+                    "---\nname: code-implementer\nmodel: opus\ncolor: green\n---\n\n\
+                     ## Machine Summary\n\nstatus: VERIFIED\nblockers: []\n",
+                ),
+                (
+                    "agents/build-cleanup.md",
+                    &CLEANUP.replace("CI_STATUS", "CI_SIGNAL"),
+                ),
+                (
+                    "agents/code-critic.md",
+                    "---\nname: code-critic\n---\n\n## Machine Summary\n\nstatus: x\n",
+                ),
+                (
+                    "skills/runs-derive/SKILL.md",
+                    "---\nname: runs-derive\n---\n",
+                ),
+                ("skills/runs-index/SKILL.md", "---\nname: runs-index\n---\n"),
+            ],
+        );
+
+        assert_eq!(d.agents.added, ["code-critic"]);
+        assert_eq!(d.agents.removed, ["old-agent"]);
+        assert_eq!(d.skills.added, ["runs-index"]);
+        assert!(d.skills.removed.is_empty());
+
+        let text = d.render_text();
+        assert!(
+            text.contains("Agents: 1 added, 1 removed\n  + code-critic\n  - old-agent\n"),
+            "{text}"
+        );
+        assert!(
+            text.contains(
+                "Frontmatter: 2 changes\n\
+                 \x20 agent code-implementer: color: added (green)\n\
+                 \x20 agent code-implementer: model: sonnet → opus\n"
+            ),
+            "{text}"
+        );
+        assert!(
+            text.contains(
+                "Machine Summary keys: 1 agent\n  code-implementer: + blockers, - concerns\n"
+            ),
+            "{text}"
+        );
+        assert!(
+            text.contains(
+                "Inventory markers read (inv get): 1 agent\n  \
+                 build-cleanup: + CI_SIGNAL in .runs/x/build/ci.md, - CI_STATUS in .runs/x/build/ci.md\n"
+            ),
+            "{text}"
+        );
+        assert!(
+            text.contains(
+                "Flow agent sequences: 1 flow\n  flow-3-build: \
+                 code-implementer, build-cleanup → code-implementer, code-critic, build-cleanup\n"
+            ),
+            "{text}"
+        );
+
+        let json = serde_json::to_value(&d).unwrap();
+        assert_eq!(json["frontmatter"][1]["from"], "sonnet");
+        assert_eq!(json["inv_markers"][0]["added"][0]["marker"], "CI_SIGNAL");
+        assert_eq!(json["flows"][0]["to"][1], "code-critic");
+    }
+
+    #[test]
+    fn test_machine_summary_keys() {
+        let keys = machine_summary_keys(
+            "## Machine Summary\n\n```yaml\nstatus: VERIFIED\ncounts:\n  requirements: 3\n\
+             - route_to_agent: null\nNote: prose\n```\n\n## Next\n\nother: 1\n",
+        );
+        assert_eq!(
+            keys.into_iter().collect::<Vec<_>>(),
+            ["counts", "requirements", "route_to_agent", "status"]
+        );
+    }
+}
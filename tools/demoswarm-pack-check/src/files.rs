@@ -0,0 +1,589 @@
+//! Where the pack's files come from.
+//!
+//! Checks never touch `std::fs`: reads, existence tests and directory walks go
+//! through the `Ctx`'s `FileProvider`. `DiskFiles` is the real file system;
+//! `MemoryFiles` holds a pack in memory (a PR's tree, tests); `Overlay` lays
+//! open editor buffers over another provider; `GitFiles` reads a commit's
+//! tree without checking it out.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+
+use anyhow::{Context, bail};
+use walkdir::WalkDir;
+
+/// Read access to a pack. Paths are the ones `Ctx` builds: the repo root joined
+/// with `.claude/...`, `CLAUDE.md`, `.runs/...`.
+pub trait FileProvider: fmt::Debug + Send + Sync {
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+
+    fn is_file(&self, path: &Path) -> bool;
+
+    fn is_dir(&self, path: &Path) -> bool;
+
+    /// Every file under `dir` (recursively), sorted. Empty if `dir` is not a directory.
+    fn files_under(&self, dir: &Path) -> Vec<PathBuf>;
+}
+
+impl<P: FileProvider + ?Sized> FileProvider for Arc<P> {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        (**self).read_to_string(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        (**self).is_file(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        (**self).is_dir(path)
+    }
+
+    fn files_under(&self, dir: &Path) -> Vec<PathBuf> {
+        (**self).files_under(dir)
+    }
+}
+
+/// The real file system. Symlinks are not followed while walking.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiskFiles;
+
+impl FileProvider for DiskFiles {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn files_under(&self, dir: &Path) -> Vec<PathBuf> {
+        if !dir.is_dir() {
+            return Vec::new();
+        }
+        let mut out: Vec<PathBuf> = WalkDir::new(dir)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(Result::ok)
+            .map(|e| e.into_path())
+            .filter(|p| p.is_file())
+            .collect();
+        out.sort();
+        out
+    }
+}
+
+/// A pack held in memory: file path → contents. Directories exist implicitly
+/// when a file lives under them.
+///
+/// Keys are compared component-wise, so they must use the same root the
+/// checker is given (`PackChecker::new("pack")` reads `pack/.claude/...`).
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFiles {
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl MemoryFiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace a file.
+    pub fn insert(&mut self, path: impl Into<PathBuf>, content: impl Into<String>) {
+        self.files.insert(path.into(), content.into());
+    }
+
+    /// Builder form of `insert`.
+    pub fn with_file(mut self, path: impl Into<PathBuf>, content: impl Into<String>) -> Self {
+        self.insert(path, content);
+        self
+    }
+
+    pub fn remove(&mut self, path: &Path) -> Option<String> {
+        self.files.remove(path)
+    }
+}
+
+impl<P: Into<PathBuf>, S: Into<String>> FromIterator<(P, S)> for MemoryFiles {
+    fn from_iter<I: IntoIterator<Item = (P, S)>>(iter: I) -> Self {
+        let mut files = Self::new();
+        for (path, content) in iter {
+            files.insert(path, content);
+        }
+        files
+    }
+}
+
+impl FileProvider for MemoryFiles {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.files.get(path).cloned().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no such in-memory file: {}", path.display()),
+            )
+        })
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.files.keys().any(|p| p != path && p.starts_with(path))
+    }
+
+    fn files_under(&self, dir: &Path) -> Vec<PathBuf> {
+        // BTreeMap order is component-wise, i.e. sorted like `DiskFiles`.
+        self.files
+            .keys()
+            .filter(|p| *p != dir && p.starts_with(dir))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Open editor buffers over another provider: an open file reads as its
+/// buffer, whether or not it exists underneath.
+///
+/// Buffers change while checks may be reading, so they sit behind a lock;
+/// share the overlay as an `Arc<Overlay<_>>` to keep editing it.
+#[derive(Debug, Default)]
+pub struct Overlay<P> {
+    base: P,
+    buffers: RwLock<BTreeMap<PathBuf, String>>,
+}
+
+impl<P: FileProvider> Overlay<P> {
+    pub fn new(base: P) -> Self {
+        Self {
+            base,
+            buffers: RwLock::default(),
+        }
+    }
+
+    /// Open or update the buffer for `path`. Returns whether the set of files
+    /// changed (the buffer is a file the base does not have).
+    pub fn set(&self, path: impl Into<PathBuf>, text: impl Into<String>) -> bool {
+        let path = path.into();
+        let added = !self.base.is_file(&path);
+        let mut buffers = self.buffers.write().unwrap_or_else(|e| e.into_inner());
+        buffers.insert(path, text.into()).is_none() && added
+    }
+
+    /// Close the buffer for `path`, so it reads from the base again. Returns
+    /// whether the set of files changed.
+    pub fn clear(&self, path: &Path) -> bool {
+        let mut buffers = self.buffers.write().unwrap_or_else(|e| e.into_inner());
+        buffers.remove(path).is_some() && !self.base.is_file(path)
+    }
+
+    /// Whether `path` has an open buffer.
+    pub fn is_open(&self, path: &Path) -> bool {
+        self.buffers().contains_key(path)
+    }
+
+    fn buffers(&self) -> RwLockReadGuard<'_, BTreeMap<PathBuf, String>> {
+        self.buffers.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<P: FileProvider> FileProvider for Overlay<P> {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        match self.buffers().get(path) {
+            Some(text) => Ok(text.clone()),
+            None => self.base.read_to_string(path),
+        }
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.is_open(path) || self.base.is_file(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.base.is_dir(path)
+            || self
+                .buffers()
+                .keys()
+                .any(|p| p != path && p.starts_with(path))
+    }
+
+    fn files_under(&self, dir: &Path) -> Vec<PathBuf> {
+        let mut out = self.base.files_under(dir);
+        out.extend(
+            self.buffers()
+                .keys()
+                .filter(|p| *p != dir && p.starts_with(dir))
+                .cloned(),
+        );
+        out.sort();
+        out.dedup();
+        out
+    }
+}
+
+/// The tree of one commit, read with `git ls-tree` and `git cat-file`.
+///
+/// Paths are where the files would be in the work tree (the repository's
+/// canonical top level joined with the path in the commit), so a pack at the
+/// top level is checked with `PackChecker::new(files.toplevel())`. Contents
+/// come from one `git cat-file --batch` process, started on the first read.
+#[derive(Debug)]
+pub struct GitFiles {
+    /// Canonicalized, like the repo roots `Ctx` is given.
+    toplevel: PathBuf,
+    /// The commit ID `rev` resolved to when opened.
+    commit: String,
+    /// Every blob in the commit's tree, as a `/`-separated path in the tree.
+    files: BTreeSet<String>,
+    cat_file: Mutex<Option<CatFile>>,
+}
+
+impl GitFiles {
+    /// The tree of `rev` in the git repository containing `dir`. The revision
+    /// is resolved once, so later commits do not change what is read.
+    pub fn open(dir: &Path, rev: &str) -> anyhow::Result<Self> {
+        let toplevel = git(dir, &["rev-parse", "--show-toplevel"])
+            .with_context(|| format!("{} is not in a git repository", dir.display()))?;
+        let toplevel = PathBuf::from(String::from_utf8_lossy(&toplevel).trim());
+        let toplevel = toplevel
+            .canonicalize()
+            .with_context(|| format!("Failed to canonicalize {}", toplevel.display()))?;
+        let commit = git(
+            &toplevel,
+            &[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{rev}^{{commit}}"),
+            ],
+        )
+        .with_context(|| format!("unknown git revision `{rev}`"))?;
+        let commit = String::from_utf8_lossy(&commit).trim().to_string();
+
+        let listing = git(
+            &toplevel,
+            &["ls-tree", "-r", "-z", "--full-tree", "--name-only", &commit],
+        )
+        .with_context(|| format!("list the tree of `{rev}`"))?;
+        let files = listing
+            .split(|&b| b == 0)
+            .filter(|name| !name.is_empty())
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .collect();
+
+        Ok(Self {
+            toplevel,
+            commit,
+            files,
+            cat_file: Mutex::default(),
+        })
+    }
+
+    /// The repository's top-level directory.
+    pub fn toplevel(&self) -> &Path {
+        &self.toplevel
+    }
+
+    /// The commit being read.
+    pub fn commit(&self) -> &str {
+        &self.commit
+    }
+
+    /// `path` as a path in the tree (`""` for the top level), if it is under
+    /// the top level.
+    fn tree_path(&self, path: &Path) -> Option<String> {
+        let rel = path.strip_prefix(&self.toplevel).ok()?;
+        let parts = rel
+            .components()
+            .map(|c| match c {
+                Component::Normal(s) => s.to_str(),
+                _ => None,
+            })
+            .collect::<Option<Vec<&str>>>()?;
+        Some(parts.join("/"))
+    }
+
+    /// Where a path in the tree would be in the work tree.
+    fn work_tree_path(&self, name: &str) -> PathBuf {
+        name.split('/')
+            .fold(self.toplevel.clone(), |path, part| path.join(part))
+    }
+
+    /// The tree paths under directory `dir` (a tree path), in tree order.
+    fn under<'a>(&'a self, dir: &str) -> impl Iterator<Item = &'a String> {
+        let prefix = if dir.is_empty() {
+            String::new()
+        } else {
+            format!("{dir}/")
+        };
+        self.files
+            .range(prefix.clone()..)
+            .take_while(move |name| name.starts_with(&prefix))
+    }
+}
+
+impl FileProvider for GitFiles {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        let Some(name) = self.tree_path(path).filter(|n| self.files.contains(n)) else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no such file in {}: {}", self.commit, path.display()),
+            ));
+        };
+        let mut cat_file = self.cat_file.lock().unwrap_or_else(|e| e.into_inner());
+        if cat_file.is_none() {
+            *cat_file = Some(CatFile::spawn(&self.toplevel)?);
+        }
+        let blob = cat_file
+            .as_mut()
+            .expect("cat-file was just started")
+            .read(&format!("{}:{name}", self.commit));
+        let blob = match blob {
+            Ok(blob) => blob,
+            Err(e) => {
+                // The process may be unusable now; start a fresh one next time.
+                *cat_file = None;
+                return Err(e);
+            }
+        };
+        String::from_utf8(blob).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.tree_path(path)
+            .is_some_and(|name| self.files.contains(&name))
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.tree_path(path)
+            .is_some_and(|dir| self.under(&dir).next().is_some())
+    }
+
+    fn files_under(&self, dir: &Path) -> Vec<PathBuf> {
+        let Some(dir) = self.tree_path(dir) else {
+            return Vec::new();
+        };
+        let mut out: Vec<PathBuf> = self
+            .under(&dir)
+            .map(|name| self.work_tree_path(name))
+            .collect();
+        // Tree order is byte order; sort component-wise like the other providers.
+        out.sort();
+        out
+    }
+}
+
+/// A running `git cat-file --batch`: write an object name, read its contents.
+#[derive(Debug)]
+struct CatFile {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl CatFile {
+    fn spawn(toplevel: &Path) -> io::Result<Self> {
+        let mut child = Command::new("git")
+            .arg("-C")
+            .arg(toplevel)
+            .args(["cat-file", "--batch"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("piped stdin");
+        let stdout = BufReader::new(child.stdout.take().expect("piped stdout"));
+        Ok(Self {
+            child,
+            stdin,
+            stdout,
+        })
+    }
+
+    /// The contents of `object` (`<commit>:<path>`).
+    fn read(&mut self, object: &str) -> io::Result<Vec<u8>> {
+        writeln!(self.stdin, "{object}")?;
+        self.stdin.flush()?;
+
+        // `<oid> <type> <size>`, or `<object> missing`
+        let mut header = String::new();
+        self.stdout.read_line(&mut header)?;
+        let size = header
+            .trim_end()
+            .rsplit_once(' ')
+            .and_then(|(_, size)| size.parse::<usize>().ok())
+            .filter(|_| !header.trim_end().ends_with(" missing"))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("git cat-file: {}", header.trim_end()),
+                )
+            })?;
+
+        let mut blob = vec![0; size + 1];
+        self.stdout.read_exact(&mut blob)?;
+        blob.pop(); // the newline after the contents
+        Ok(blob)
+    }
+}
+
+impl Drop for CatFile {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Run `git` in `dir` and return its stdout.
+fn git(dir: &Path, args: &[&str]) -> anyhow::Result<Vec<u8>> {
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .context("run git")?;
+    if !out.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    Ok(out.stdout)
+}
+
+/// Test utilities: a throwaway git repository to read revisions from.
+#[cfg(test)]
+pub mod test_utils {
+    use std::path::Path;
+
+    /// Write `files` (repo-relative path, content) under `root`, commit them
+    /// (initializing the repository on first use) and return the commit id.
+    pub fn commit_files(root: &Path, files: &[(&str, &str)]) -> String {
+        for (path, content) in files {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let identity = ["-c", "user.name=t", "-c", "user.email=t@t"];
+        super::git(root, &["init", "-q"]).unwrap();
+        super::git(root, &["add", "-A"]).unwrap();
+        super::git(root, &[&identity[..], &["commit", "-qm", "test"]].concat()).unwrap();
+        let rev = super::git(root, &["rev-parse", "HEAD"]).unwrap();
+        String::from_utf8(rev).unwrap().trim().to_string()
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_files_directories_are_implicit() {
+        let files = MemoryFiles::from_iter([
+            ("pack/.claude/agents/b.md", "b"),
+            ("pack/.claude/agents/a.md", "a"),
+            ("pack/CLAUDE.md", "# CLAUDE.md"),
+        ]);
+
+        assert!(files.is_dir(Path::new("pack/.claude")));
+        assert!(!files.is_dir(Path::new("pack/.claude/agents/a.md")));
+        assert!(!files.is_dir(Path::new("pack/.cla")));
+        assert!(files.is_file(Path::new("pack/CLAUDE.md")));
+        assert_eq!(
+            files.files_under(Path::new("pack/.claude")),
+            vec![
+                PathBuf::from("pack/.claude/agents/a.md"),
+                PathBuf::from("pack/.claude/agents/b.md"),
+            ]
+        );
+        assert_eq!(
+            files
+                .read_to_string(Path::new("pack/.claude/agents/a.md"))
+                .unwrap(),
+            "a"
+        );
+        assert_eq!(
+            files
+                .read_to_string(Path::new("pack/x.md"))
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_overlay_buffers_shadow_base() {
+        let base = MemoryFiles::from_iter([("pack/.claude/agents/a.md", "disk")]);
+        let overlay = Overlay::new(base);
+        let a = Path::new("pack/.claude/agents/a.md");
+        let b = Path::new("pack/.claude/agents/b.md");
+
+        assert!(!overlay.set(a, "buffer"), "a exists underneath");
+        assert_eq!(overlay.read_to_string(a).unwrap(), "buffer");
+        assert!(overlay.set(b, "new"), "b is a new file");
+        assert!(!overlay.set(b, "newer"), "b is already open");
+        assert_eq!(
+            overlay.files_under(Path::new("pack/.claude")),
+            vec![a.to_path_buf(), b.to_path_buf()]
+        );
+
+        assert!(!overlay.clear(a));
+        assert_eq!(overlay.read_to_string(a).unwrap(), "disk");
+        assert!(overlay.clear(b));
+        assert!(!overlay.is_file(b));
+    }
+
+    #[test]
+    fn test_git_files_read_the_commit_not_the_work_tree() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let rev = test_utils::commit_files(
+            &root,
+            &[
+                (".claude/agents/a.md", "committed"),
+                (".claude/agents/c.md", ""),
+                (".claude-x/d.md", "sibling"),
+            ],
+        );
+        std::fs::write(root.join(".claude/agents/a.md"), "edited").unwrap();
+        std::fs::write(root.join(".claude/agents/b.md"), "untracked").unwrap();
+
+        let files = GitFiles::open(&root.join(".claude"), &rev).unwrap();
+        let a = root.join(".claude/agents/a.md");
+        assert_eq!(files.toplevel(), root);
+        let c = root.join(".claude/agents/c.md");
+        // One cat-file process serves every read, empty blobs included.
+        for _ in 0..3 {
+            assert_eq!(files.read_to_string(&a).unwrap(), "committed");
+            assert_eq!(files.read_to_string(&c).unwrap(), "");
+        }
+        assert!(files.is_dir(&root.join(".claude")));
+        assert!(files.is_dir(&root));
+        assert!(!files.is_dir(&root.join(".clau")));
+        assert!(!files.is_file(&root.join(".claude/agents/b.md")));
+        assert_eq!(files.files_under(&root.join(".claude")), vec![a, c]);
+        assert_eq!(files.files_under(&root).len(), 3);
+        assert_eq!(
+            files
+                .read_to_string(&root.join(".claude/agents/b.md"))
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+
+        let err = GitFiles::open(&root, "no-such-ref").unwrap_err();
+        assert!(format!("{err:#}").contains("unknown git revision `no-such-ref`"));
+    }
+}
@@ -0,0 +1,385 @@
+//! `--fix`: apply the mechanical rewrites registered in `checks::fixers()`.
+//!
+//! Every rewrite is planned against the whole pack before anything is written.
+//! Writing is all-or-nothing as far as the filesystem allows: each fixed file is
+//! staged next to its target, and only once every file is staged are they
+//! renamed into place.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+use similar::TextDiff;
+
+use crate::checks::{CheckCtx, FixSpec, Rewrite};
+use crate::suppress::Suppressions;
+
+/// The planned rewrite of one file.
+#[derive(Debug, Clone)]
+pub struct FileFix {
+    pub path: PathBuf,
+    /// Repo-relative path.
+    pub rel: String,
+    pub original: String,
+    pub fixed: String,
+    /// `(check id, 1-based line in the original)` for every rewrite.
+    pub edits: Vec<(u32, usize)>,
+}
+
+impl FileFix {
+    /// Unified diff from the original to the fixed content (`a/` and `b/` prefixes).
+    pub fn unified_diff(&self) -> String {
+        let rel = self.rel.replace('\\', "/");
+        TextDiff::from_lines(&self.original, &self.fixed)
+            .unified_diff()
+            .context_radius(3)
+            .header(&format!("a/{rel}"), &format!("b/{rel}"))
+            .to_string()
+    }
+
+    /// Check IDs with at least one rewrite in this file, ascending.
+    pub fn check_ids(&self) -> Vec<u32> {
+        let mut ids: Vec<u32> = self.edits.iter().map(|(id, _)| *id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+}
+
+/// One line of a file being rewritten, keeping its original number and ending.
+struct Line {
+    no: usize,
+    body: String,
+    eol: String,
+}
+
+/// Plan the rewrites of `fixers` over the pack. Lines covered by an inline
+/// suppression directive for the fixer's check are left alone.
+pub fn plan(cx: &CheckCtx, fixers: &[FixSpec], sup: &Suppressions) -> anyhow::Result<Vec<FileFix>> {
+    // path -> fixers that apply to it, in registry order
+    let mut by_file: BTreeMap<PathBuf, Vec<&FixSpec>> = BTreeMap::new();
+    for fixer in fixers {
+        for path in (fixer.files)(cx) {
+            by_file.entry(path).or_default().push(fixer);
+        }
+    }
+
+    let mut out = Vec::new();
+    for (path, fixers) in by_file {
+        // Best-effort, like the checks: skip unreadable/non-UTF8 files.
+        let Ok(original) = cx.ctx.read_utf8(&path) else {
+            continue;
+        };
+        let rel = cx.ctx.rel(&path);
+        let (fixed, edits) = fix_content(cx, &rel, &original, &fixers, sup);
+        if !edits.is_empty() {
+            out.push(FileFix {
+                path,
+                rel,
+                original: original.to_string(),
+                fixed,
+                edits,
+            });
+        }
+    }
+
+    out.sort_by(|a, b| a.rel.cmp(&b.rel));
+    Ok(out)
+}
+
+fn fix_content(
+    cx: &CheckCtx,
+    rel: &str,
+    content: &str,
+    fixers: &[&FixSpec],
+    sup: &Suppressions,
+) -> (String, Vec<(u32, usize)>) {
+    let mut edits = Vec::new();
+    let mut lines: Vec<Line> = content
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(i, raw)| {
+            let body = raw.trim_end_matches(['\n', '\r']);
+            Line {
+                no: i + 1,
+                body: body.to_string(),
+                eol: raw[body.len()..].to_string(),
+            }
+        })
+        .collect();
+
+    // Line rewrites first: they keep line numbers stable for suppression lookups.
+    for line in &mut lines {
+        for fixer in fixers {
+            if let Rewrite::Line(rewrite) = fixer.rewrite
+                && !sup.covers(fixer.check_id, rel, line.no)
+                && let Some(new) = rewrite(cx, &line.body)
+            {
+                line.body = new;
+                edits.push((fixer.check_id, line.no));
+            }
+        }
+    }
+
+    for fixer in fixers {
+        if let Rewrite::JoinNext(should_join) = fixer.rewrite {
+            lines = join_lines(cx, rel, lines, fixer.check_id, should_join, sup, &mut edits);
+        }
+    }
+
+    edits.sort_by_key(|(id, line)| (*line, *id));
+    let fixed = lines
+        .iter()
+        .map(|l| format!("{}{}", l.body, l.eol))
+        .collect();
+    (fixed, edits)
+}
+
+fn join_lines(
+    cx: &CheckCtx,
+    rel: &str,
+    lines: Vec<Line>,
+    check_id: u32,
+    should_join: fn(&CheckCtx, &str) -> bool,
+    sup: &Suppressions,
+    edits: &mut Vec<(u32, usize)>,
+) -> Vec<Line> {
+    let mut out: Vec<Line> = Vec::with_capacity(lines.len());
+    let mut iter = lines.into_iter().peekable();
+
+    while let Some(mut line) = iter.next() {
+        if should_join(cx, &line.body) && !sup.covers(check_id, rel, line.no) {
+            let head = line
+                .body
+                .trim_end()
+                .trim_end_matches('\\')
+                .trim_end()
+                .to_string();
+            match iter.peek() {
+                // A blank next line: just drop the dangling continuation.
+                Some(next) if next.body.trim().is_empty() => line.body = head,
+                Some(_) => {
+                    let next = iter.next().expect("peeked");
+                    line.body = format!("{head} {}", next.body.trim_start());
+                    line.eol = next.eol;
+                }
+                None => line.body = head,
+            }
+            edits.push((check_id, line.no));
+        }
+        out.push(line);
+    }
+
+    out
+}
+
+/// Write every planned fix. Files are staged first; if staging any file fails,
+/// nothing is renamed into place.
+pub fn apply(fixes: &[FileFix]) -> anyhow::Result<()> {
+    let mut staged: Vec<PathBuf> = Vec::new();
+
+    for fix in fixes {
+        match stage(fix) {
+            Ok(tmp) => staged.push(tmp),
+            Err(e) => {
+                for tmp in &staged {
+                    let _ = std::fs::remove_file(tmp);
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    for (fix, tmp) in fixes.iter().zip(&staged) {
+        std::fs::rename(tmp, &fix.path)
+            .with_context(|| format!("replace {} with fixed content", fix.rel))?;
+    }
+
+    Ok(())
+}
+
+/// Write the fixed content next to the target, refusing if the file changed
+/// since it was planned.
+fn stage(fix: &FileFix) -> anyhow::Result<PathBuf> {
+    // An empty original is a new file (`docs generate`): it must still not exist
+    let current = match std::fs::read_to_string(&fix.path) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && fix.original.is_empty() => {
+            String::new()
+        }
+        read => read.with_context(|| format!("re-read {} before fixing", fix.rel))?,
+    };
+    if current != fix.original {
+        bail!("{} changed while fixing; re-run --fix", fix.rel);
+    }
+
+    let tmp = staging_path(&fix.path);
+    std::fs::write(&tmp, &fix.fixed).with_context(|| format!("stage fix for {}", fix.rel))?;
+    if let Ok(meta) = std::fs::metadata(&fix.path) {
+        let _ = std::fs::set_permissions(&tmp, meta.permissions());
+    }
+    Ok(tmp)
+}
+
+fn staging_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{name}.pack-check-fix"))
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks;
+    use crate::contracts::Contracts;
+    use crate::contracts::test_utils::REGEXES;
+    use crate::ctx::Ctx;
+    use crate::inventory::Inventory;
+    use tempfile::TempDir;
+
+    fn setup_pack(files: &[(&str, &str)]) -> TempDir {
+        let tmp = TempDir::new().unwrap();
+        for dir in ["agents", "commands", "skills"] {
+            std::fs::create_dir_all(tmp.path().join(".claude").join(dir)).unwrap();
+        }
+        for (rel, content) in files {
+            let path = tmp.path().join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        tmp
+    }
+
+    fn plan_pack(tmp: &TempDir) -> Vec<FileFix> {
+        let ctx = Ctx::discover(Some(tmp.path().to_path_buf())).unwrap();
+        let inv = Inventory::from_ctx(&ctx).unwrap();
+        let contracts = Contracts::default();
+        let cx = CheckCtx {
+            ctx: &ctx,
+            inv: &inv,
+            re: &REGEXES,
+            c: &contracts,
+        };
+        let sup = Suppressions::scan(&ctx).unwrap();
+        plan(&cx, &checks::fixers(), &sup).unwrap()
+    }
+
+    #[test]
+    fn test_plan_rewrites_taxonomy_and_shim() {
+        let tmp = setup_pack(&[(
+            ".claude/agents/a.md",
+            "Covers FR-001 and @FR-002.\nRun demoswarm count pattern\nNFR-PERF-001 vs FR-003\n",
+        )]);
+
+        let fixes = plan_pack(&tmp);
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(
+            fixes[0].fixed,
+            "Covers REQ-001 and @REQ-002.\n\
+             Run bash .claude/scripts/demoswarm.sh count pattern\n\
+             NFR-PERF-001 vs FR-003\n"
+        );
+        assert_eq!(fixes[0].check_ids(), vec![7, 48]);
+    }
+
+    #[test]
+    fn test_plan_joins_shim_line_continuation() {
+        let tmp = setup_pack(&[(
+            ".claude/agents/a.md",
+            "```bash\r\nbash .claude/scripts/demoswarm.sh \\\r\n    secrets scan --file x\r\n```\r\n",
+        )]);
+
+        let fixes = plan_pack(&tmp);
+        assert_eq!(
+            fixes[0].fixed,
+            "```bash\r\nbash .claude/scripts/demoswarm.sh secrets scan --file x\r\n```\r\n"
+        );
+        assert_eq!(fixes[0].edits, vec![(47, 2)]);
+    }
+
+    #[test]
+    fn test_plan_rewrites_openq_prefixes() {
+        let tmp = setup_pack(&[(
+            ".runs/feat/signal/open_questions.md",
+            "- QID: OQ-PLN-001\n- QID: OQ-SIG-002\n",
+        )]);
+
+        let fixes = plan_pack(&tmp);
+        assert_eq!(fixes[0].rel, ".runs/feat/signal/open_questions.md");
+        assert_eq!(fixes[0].fixed, "- QID: OQ-PLAN-001\n- QID: OQ-SIG-002\n");
+    }
+
+    #[test]
+    fn test_plan_respects_suppression_directives() {
+        let tmp = setup_pack(&[(
+            ".claude/agents/a.md",
+            "<!-- pack-check-disable-next-line 7 -->\nFR-001 on purpose\nFR-002\n",
+        )]);
+
+        let fixes = plan_pack(&tmp);
+        assert_eq!(
+            fixes[0].fixed,
+            "<!-- pack-check-disable-next-line 7 -->\nFR-001 on purpose\nREQ-002\n"
+        );
+    }
+
+    #[test]
+    fn test_plan_clean_pack_is_empty() {
+        let tmp = setup_pack(&[(".claude/agents/a.md", "Covers REQ-001.\n")]);
+        assert!(plan_pack(&tmp).is_empty());
+    }
+
+    #[test]
+    fn test_unified_diff_has_headers() {
+        let tmp = setup_pack(&[(".claude/agents/a.md", "FR-001\n")]);
+        let diff = plan_pack(&tmp)[0].unified_diff();
+
+        assert!(diff.contains("--- a/.claude/agents/a.md"), "{diff}");
+        assert!(diff.contains("+++ b/.claude/agents/a.md"), "{diff}");
+        assert!(diff.contains("-FR-001"));
+        assert!(diff.contains("+REQ-001"));
+    }
+
+    #[test]
+    fn test_apply_writes_and_converges() {
+        let tmp = setup_pack(&[(".claude/commands/c.md", "demoswarm ms get\n")]);
+        let fixes = plan_pack(&tmp);
+
+        apply(&fixes).unwrap();
+
+        let written = std::fs::read_to_string(tmp.path().join(".claude/commands/c.md")).unwrap();
+        assert_eq!(written, "bash .claude/scripts/demoswarm.sh ms get\n");
+        assert!(plan_pack(&tmp).is_empty());
+        assert!(
+            !tmp.path()
+                .join(".claude/commands/.c.md.pack-check-fix")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn test_apply_refuses_changed_file() {
+        let tmp = setup_pack(&[
+            (".claude/agents/a.md", "FR-001\n"),
+            (".claude/agents/b.md", "FR-002\n"),
+        ]);
+        let fixes = plan_pack(&tmp);
+        std::fs::write(tmp.path().join(".claude/agents/b.md"), "edited\n").unwrap();
+
+        let err = apply(&fixes).unwrap_err();
+        assert!(err.to_string().contains("changed while fixing"), "{err}");
+        // Nothing was written, and no staging files are left behind
+        let a = std::fs::read_to_string(tmp.path().join(".claude/agents/a.md")).unwrap();
+        assert_eq!(a, "FR-001\n");
+        assert!(
+            !tmp.path()
+                .join(".claude/agents/.a.md.pack-check-fix")
+                .exists()
+        );
+    }
+}
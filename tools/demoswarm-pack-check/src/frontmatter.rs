@@ -0,0 +1,183 @@
+//! YAML frontmatter of agents, commands and skills, parsed with line numbers.
+//!
+//! `markdown::Frontmatter` only finds the `---` block; this parses it as YAML
+//! and keeps each top-level field's line, so schema findings (check 70) point
+//! at the field.
+
+use serde_norway::Value;
+
+use crate::markdown::Frontmatter;
+
+/// The top-level fields of a frontmatter block, in file order.
+#[derive(Debug, Clone, Default)]
+pub struct Fields {
+    entries: Vec<Field>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub key: String,
+    pub value: Value,
+    /// 1-based line in the file (the frontmatter's first line if not found).
+    pub line: usize,
+}
+
+/// Frontmatter that is not valid YAML, or not a mapping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YamlError {
+    /// 1-based line in the file.
+    pub line: usize,
+    pub message: String,
+}
+
+impl Fields {
+    pub fn parse(fm: &Frontmatter) -> Result<Self, YamlError> {
+        let value: Value = serde_norway::from_str(&fm.raw).map_err(|e| {
+            let location = e.location();
+            let mut message = e.to_string();
+            // The error's location is relative to the block; `line` reports it
+            // in the file instead.
+            if let Some(l) = &location {
+                let mark = format!(" at line {} column {}", l.line(), l.column());
+                message = message.replacen(&mark, "", 1);
+            }
+            YamlError {
+                line: fm.start + location.map_or(1, |l| l.line()),
+                message,
+            }
+        })?;
+
+        let mapping = match value {
+            Value::Mapping(mapping) => mapping,
+            // An empty block
+            Value::Null => return Ok(Self::default()),
+            _ => {
+                return Err(YamlError {
+                    line: fm.start + 1,
+                    message: "frontmatter is not a mapping of `key: value` fields".to_string(),
+                });
+            }
+        };
+
+        let entries = mapping
+            .into_iter()
+            .map(|(key, value)| {
+                let key = match key {
+                    Value::String(s) => s,
+                    other => scalar_text(&other).unwrap_or_default(),
+                };
+                let line = fm
+                    .raw
+                    .lines()
+                    .position(|l| {
+                        l.strip_prefix(key.as_str())
+                            .is_some_and(|r| r.starts_with(':'))
+                    })
+                    .map_or(fm.start, |i| fm.start + 1 + i);
+                Field { key, value, line }
+            })
+            .collect();
+        Ok(Self { entries })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Field> {
+        self.entries.iter().find(|f| f.key == key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Field> {
+        self.entries.iter()
+    }
+}
+
+impl Field {
+    /// The value as trimmed text, if it is a non-empty scalar.
+    pub fn text(&self) -> Option<String> {
+        scalar_text(&self.value).filter(|s| !s.is_empty())
+    }
+
+    /// A list value: a YAML sequence of scalars, or one comma-separated
+    /// string (`tools: Read, Grep`). `None` for anything else.
+    pub fn list(&self) -> Option<Vec<String>> {
+        match &self.value {
+            Value::Sequence(items) => items.iter().map(scalar_text).collect(),
+            Value::String(s) => Some(
+                s.split(',')
+                    .map(|t| t.trim().to_string())
+                    .filter(|t| !t.is_empty())
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::Markdown;
+
+    fn fields(text: &str) -> Result<Fields, YamlError> {
+        let md = Markdown::parse(text);
+        Fields::parse(md.frontmatter.as_ref().unwrap())
+    }
+
+    #[test]
+    fn test_fields_and_lines() {
+        let f = fields(
+            "---\nname: planner\ndescription: >\n  Plans work.\ntools: [Read, Grep]\nmodel: haiku\n---\n",
+        )
+        .unwrap();
+
+        let keys: Vec<(&str, usize)> = f.iter().map(|f| (f.key.as_str(), f.line)).collect();
+        assert_eq!(
+            keys,
+            [("name", 2), ("description", 3), ("tools", 5), ("model", 6)]
+        );
+        assert_eq!(f.get("description").unwrap().text().unwrap(), "Plans work.");
+        assert_eq!(f.get("tools").unwrap().list().unwrap(), ["Read", "Grep"]);
+        assert!(f.get("color").is_none());
+    }
+
+    #[test]
+    fn test_comma_separated_list() {
+        let f = fields("---\ntools: Read, Bash(git:*),\n---\n").unwrap();
+        assert_eq!(
+            f.get("tools").unwrap().list().unwrap(),
+            ["Read", "Bash(git:*)"]
+        );
+    }
+
+    #[test]
+    fn test_malformed_yaml_reports_file_line() {
+        let err = fields("---\nname: planner\ndescription: a: b\n---\n").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert!(!err.message.contains("at line 2 column"), "{}", err.message);
+        assert!(
+            err.message.starts_with("mapping values are not allowed"),
+            "{}",
+            err.message
+        );
+
+        let err = fields("---\nname: a\nname: b\n---\n").unwrap_err();
+        assert!(err.message.contains("duplicate"), "{}", err.message);
+    }
+
+    #[test]
+    fn test_non_mapping_and_empty() {
+        assert_eq!(fields("---\n- a\n---\n").unwrap_err().line, 2);
+        assert_eq!(fields("---\n---\n").unwrap().iter().count(), 0);
+    }
+}
@@ -5,8 +5,6 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use walkdir::WalkDir;
-
 use crate::ctx::Ctx;
 
 /// Precomputed file lists for the pack.
@@ -38,7 +36,11 @@ impl Inventory {
         let command_md_files = ctx.command_md_files()?;
 
         // Skills are in subdirectories: .claude/skills/<name>/SKILL.md
-        let skill_md_files = list_skill_files(&ctx.skills_dir);
+        let skill_md_files: Vec<PathBuf> = ctx
+            .files_under(&ctx.skills_dir)
+            .into_iter()
+            .filter(|p| p.file_name().is_some_and(|n| n == "SKILL.md"))
+            .collect();
 
         // Filter flow commands
         let flow_cmd_files: Vec<_> = command_md_files
@@ -89,21 +91,3 @@ fn build_stem_index(files: &[PathBuf]) -> HashMap<String, PathBuf> {
         })
         .collect()
 }
-
-/// List all SKILL.md files under the skills directory.
-fn list_skill_files(skills_dir: &std::path::Path) -> Vec<PathBuf> {
-    let mut out = Vec::new();
-    if !skills_dir.is_dir() {
-        return out;
-    }
-
-    for entry in WalkDir::new(skills_dir).follow_links(false) {
-        let Ok(entry) = entry else { continue };
-        let path = entry.path();
-        if path.is_file() && path.file_name().is_some_and(|n| n == "SKILL.md") {
-            out.push(path.to_path_buf());
-        }
-    }
-    out.sort();
-    out
-}
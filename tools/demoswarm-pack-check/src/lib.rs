@@ -1,13 +1,34 @@
 #![forbid(unsafe_code)]
 
+mod baseline;
+mod checker;
 mod checks;
 mod cli;
 mod contracts;
+mod contracts_file;
 mod ctx;
+mod diff;
+mod files;
+mod fix;
+mod frontmatter;
 mod inventory;
+mod lsp;
+mod markdown;
 mod reporter;
 mod runner;
+mod sarif;
+mod session;
+mod shim;
+mod suppress;
+mod topology;
 mod util;
+mod watch;
 
+pub use baseline::BaselineEntry;
+pub use checker::PackChecker;
+pub use checks::{Category, Selection};
 pub use cli::{Cli, OutputFormat};
+pub use contracts::Contracts;
+pub use files::{DiskFiles, FileProvider, GitFiles, MemoryFiles, Overlay};
+pub use reporter::{BaselineSummary, Diagnostic, Level, Location, PackCounts, RunReport};
 pub use runner::run;
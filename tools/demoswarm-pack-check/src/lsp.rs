@@ -0,0 +1,554 @@
+//! `--lsp`: a language server (stdio) for the pack's markdown.
+//!
+//! Open buffers are laid over the disk (`Overlay`), so diagnostics follow
+//! unsaved edits; each change re-runs only the checks that read the file
+//! (`Session`). On top of diagnostics: hover shows the rationale of the checks
+//! firing on a line, go-to-definition jumps from an agent name to
+//! `.claude/agents/<name>.md`, and completion offers the pack's agent names.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use anyhow::Context;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Response};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionOptions, DiagnosticSeverity,
+    DidChangeTextDocumentParams, DidChangeWatchedFilesParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, FileChangeType, Hover, HoverContents, HoverProviderCapability,
+    Location, MarkupContent, MarkupKind, NumberOrString, OneOf, Position, PublishDiagnosticsParams,
+    Range, ServerCapabilities, TextDocumentPositionParams, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+    notification::{
+        DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest, Request as _},
+};
+use serde_json::Value;
+
+use crate::{
+    checker::PackChecker,
+    checks,
+    files::{DiskFiles, Overlay},
+    reporter::{Diagnostic, Level},
+    session::Session,
+};
+
+/// Serve the pack over stdin/stdout until the client shuts the server down.
+pub fn serve(checker: PackChecker, baseline: Option<&Path>) -> anyhow::Result<ExitCode> {
+    // Resolve the root on disk first: buffers are keyed by the paths in the
+    // client's file URIs, which are absolute.
+    let root = checker.load()?.ctx.repo_root;
+    let overlay = Arc::new(Overlay::new(DiskFiles));
+    let checker = checker.at(root).files(overlay.clone());
+
+    let (connection, io_threads) = Connection::stdio();
+    connection
+        .initialize(serde_json::to_value(capabilities())?)
+        .context("LSP initialize")?;
+
+    let mut server = Server::new(Session::start(&checker, baseline)?, overlay);
+    publish(&connection, &mut server)?;
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(req) => {
+                if connection.handle_shutdown(&req)? {
+                    break;
+                }
+                let response = match server.request(&req.method, req.params) {
+                    Ok(Some(result)) => Response::new_ok(req.id, result),
+                    Ok(None) => Response::new_err(
+                        req.id,
+                        ErrorCode::MethodNotFound as i32,
+                        format!("unsupported request: {}", req.method),
+                    ),
+                    Err(err) => Response::new_err(
+                        req.id,
+                        ErrorCode::InvalidParams as i32,
+                        format!("{err:#}"),
+                    ),
+                };
+                connection.sender.send(response.into())?;
+            }
+            Message::Notification(not) => {
+                // A half-written contracts file, say: keep serving; stderr is
+                // the client's log.
+                if let Err(err) = server.notify(&not.method, not.params) {
+                    eprintln!("pack-check: {err:#}");
+                }
+                publish(&connection, &mut server)?;
+            }
+            Message::Response(_) => {}
+        }
+    }
+
+    // The reader thread hands over `exit` before stopping; drop the receiver so
+    // it can.
+    drop(connection);
+    io_threads.join()?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        ..ServerCapabilities::default()
+    }
+}
+
+fn publish(connection: &Connection, server: &mut Server) -> anyhow::Result<()> {
+    for params in server.publish() {
+        let not = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        connection.sender.send(not.into())?;
+    }
+    Ok(())
+}
+
+struct Server<'a> {
+    session: Session<'a>,
+    overlay: Arc<Overlay<DiskFiles>>,
+    /// What each file was last sent, so fixed files get an empty list.
+    published: HashMap<Url, Vec<lsp_types::Diagnostic>>,
+}
+
+impl<'a> Server<'a> {
+    fn new(session: Session<'a>, overlay: Arc<Overlay<DiskFiles>>) -> Self {
+        Self {
+            session,
+            overlay,
+            published: HashMap::new(),
+        }
+    }
+
+    /// Apply a client notification. Unknown ones are ignored.
+    fn notify(&mut self, method: &str, params: Value) -> anyhow::Result<()> {
+        match method {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = serde_json::from_value(params)?;
+                self.edit(&params.text_document.uri, Some(params.text_document.text))
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = serde_json::from_value(params)?;
+                // Full sync: the last change holds the whole document.
+                let text = params.content_changes.into_iter().last().map(|c| c.text);
+                match text {
+                    Some(text) => self.edit(&params.text_document.uri, Some(text)),
+                    None => Ok(()),
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = serde_json::from_value(params)?;
+                self.edit(&params.text_document.uri, None)
+            }
+            DidChangeWatchedFiles::METHOD => {
+                let params: DidChangeWatchedFilesParams = serde_json::from_value(params)?;
+                let mut paths = Vec::new();
+                let mut tree_changed = false;
+                for change in params.changes {
+                    // An open buffer already says what the file holds.
+                    if let Some(path) = self.pack_path(&change.uri)
+                        && !self.overlay.is_open(&path)
+                    {
+                        tree_changed |= change.typ != FileChangeType::CHANGED;
+                        paths.push(path);
+                    }
+                }
+                if !paths.is_empty() {
+                    self.session.changed(&paths, tree_changed)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Set (`Some`) or close (`None`) the buffer for `uri` and re-run the
+    /// checks it affects.
+    fn edit(&mut self, uri: &Url, text: Option<String>) -> anyhow::Result<()> {
+        let Some(path) = self.pack_path(uri) else {
+            return Ok(());
+        };
+        let tree_changed = match text {
+            Some(text) => self.overlay.set(&path, text),
+            None => self.overlay.clear(&path),
+        };
+        self.session.changed(&[path], tree_changed)?;
+        Ok(())
+    }
+
+    /// Answer a client request; `None` if the method is not supported.
+    fn request(&self, method: &str, params: Value) -> anyhow::Result<Option<Value>> {
+        let result = match method {
+            HoverRequest::METHOD => serde_json::to_value(self.hover(&position(params)?))?,
+            GotoDefinition::METHOD => serde_json::to_value(self.definition(&position(params)?))?,
+            Completion::METHOD => serde_json::to_value(self.completion(&position(params)?))?,
+            _ => return Ok(None),
+        };
+        Ok(Some(result))
+    }
+
+    /// The rationale of every check firing on the hovered line.
+    fn hover(&self, at: &TextDocumentPositionParams) -> Option<Hover> {
+        let path = self.pack_path(&at.text_document.uri)?;
+        let rel = self.session.pack().ctx.rel(&path);
+        let line = at.position.line as usize + 1;
+
+        let sections: Vec<String> = self
+            .session
+            .diagnostics()
+            .filter(|d| {
+                d.location
+                    .as_ref()
+                    .is_some_and(|loc| loc.path == rel && loc.line.unwrap_or(1) == line)
+            })
+            .map(|d| {
+                let rationale = checks::rationale(d.check_id).unwrap_or_default();
+                format!(
+                    "**pack-check [{}]** {}\n\n{rationale}",
+                    d.check_id, d.message
+                )
+            })
+            .collect();
+        if sections.is_empty() {
+            return None;
+        }
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: sections.join("\n\n---\n\n"),
+            }),
+            range: None,
+        })
+    }
+
+    /// The agent file for the agent name under the cursor.
+    fn definition(&self, at: &TextDocumentPositionParams) -> Option<Location> {
+        let path = self.pack_path(&at.text_document.uri)?;
+        let text = self.session.pack().ctx.read_utf8(&path).ok()?;
+        let line = text.lines().nth(at.position.line as usize)?;
+        let name = word_at(line, at.position.character)?;
+        let agent = self.session.pack().inv.agent(name)?;
+
+        Some(Location {
+            uri: Url::from_file_path(agent).ok()?,
+            range: Range::default(),
+        })
+    }
+
+    /// The pack's agent names; the client filters them by what was typed.
+    fn completion(&self, at: &TextDocumentPositionParams) -> Option<Vec<CompletionItem>> {
+        self.pack_path(&at.text_document.uri)?;
+        let pack = self.session.pack();
+        let agents: BTreeSet<(&String, &PathBuf)> = pack.inv.agents_by_stem.iter().collect();
+
+        Some(
+            agents
+                .into_iter()
+                .map(|(name, path)| CompletionItem {
+                    label: name.clone(),
+                    kind: Some(CompletionItemKind::REFERENCE),
+                    detail: Some(pack.ctx.rel(path)),
+                    ..CompletionItem::default()
+                })
+                .collect(),
+        )
+    }
+
+    /// Diagnostics for every file whose list changed since the last call.
+    fn publish(&mut self) -> Vec<PublishDiagnosticsParams> {
+        let ctx = &self.session.pack().ctx;
+        let mut current: HashMap<Url, Vec<lsp_types::Diagnostic>> = HashMap::new();
+        for d in self.session.diagnostics() {
+            // Findings about the pack as a whole (a missing agent, say) have
+            // no file to show them in.
+            let Some(loc) = &d.location else { continue };
+            let path = ctx.repo_root.join(&loc.path);
+            let Ok(uri) = Url::from_file_path(&path) else {
+                continue;
+            };
+            let text = ctx.read_utf8(&path).ok();
+            current
+                .entry(uri)
+                .or_default()
+                .push(to_lsp(d, text.as_deref()));
+        }
+
+        let mut out: Vec<PublishDiagnosticsParams> = Vec::new();
+        for (uri, diagnostics) in &current {
+            if self.published.get(uri) != Some(diagnostics) {
+                out.push(PublishDiagnosticsParams::new(
+                    uri.clone(),
+                    diagnostics.clone(),
+                    None,
+                ));
+            }
+        }
+        for uri in self.published.keys() {
+            if !current.contains_key(uri) {
+                out.push(PublishDiagnosticsParams::new(uri.clone(), Vec::new(), None));
+            }
+        }
+        out.sort_by(|a, b| a.uri.cmp(&b.uri));
+
+        self.published = current;
+        out
+    }
+
+    /// The path of `uri` if it is one of the pack's files (`.claude/**`,
+    /// `CLAUDE.md`).
+    fn pack_path(&self, uri: &Url) -> Option<PathBuf> {
+        let ctx = &self.session.pack().ctx;
+        let path = uri.to_file_path().ok()?;
+        (path.starts_with(&ctx.claude_dir) || path == ctx.repo_root.join("CLAUDE.md"))
+            .then_some(path)
+    }
+}
+
+fn position(params: Value) -> anyhow::Result<TextDocumentPositionParams> {
+    Ok(serde_json::from_value(params)?)
+}
+
+/// A pack-check diagnostic from its column (or the line start) to the end of
+/// its last line (the first line when it has none).
+fn to_lsp(d: &Diagnostic, text: Option<&str>) -> lsp_types::Diagnostic {
+    let loc = d.location.as_ref();
+    let start = loc.and_then(|l| l.line).unwrap_or(1).saturating_sub(1);
+    // `Location` columns count chars from 1; LSP counts UTF-16 units from 0.
+    let start_character = loc.and_then(|l| l.column).map_or(0, |column| {
+        let before = column.saturating_sub(1);
+        text.and_then(|t| t.lines().nth(start))
+            .map_or(before, |line| {
+                line.chars().take(before).map(char::len_utf16).sum()
+            })
+    });
+    let end = loc
+        .and_then(|l| l.end_line)
+        .map_or(start, |l| l.saturating_sub(1));
+    let end_character = text
+        .and_then(|t| t.lines().nth(end))
+        .map_or(0, |l| l.encode_utf16().count());
+
+    lsp_types::Diagnostic {
+        range: Range::new(
+            Position::new(start as u32, start_character as u32),
+            Position::new(end as u32, end_character as u32),
+        ),
+        severity: Some(match d.level {
+            Level::Fail => DiagnosticSeverity::ERROR,
+            Level::Warn | Level::Pass => DiagnosticSeverity::WARNING,
+        }),
+        code: Some(NumberOrString::Number(d.check_id as i32)),
+        source: Some("pack-check".to_string()),
+        message: d.message.clone(),
+        ..lsp_types::Diagnostic::default()
+    }
+}
+
+/// The agent-name-like word (`[A-Za-z0-9_-]+`) around UTF-16 offset
+/// `character` of `line`.
+fn word_at(line: &str, character: u32) -> Option<&str> {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+
+    let mut units = 0;
+    let cursor = line
+        .char_indices()
+        .find(|(_, c)| {
+            units += c.len_utf16();
+            units > character as usize
+        })
+        .map_or(line.len(), |(i, _)| i);
+
+    let start = line[..cursor]
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| is_word(c))
+        .last()
+        .map_or(cursor, |(i, _)| i);
+    let end = line[cursor..]
+        .find(|c: char| !is_word(c))
+        .map_or(line.len(), |i| cursor + i);
+    (start < end).then(|| &line[start..end])
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    /// A pack with a clean agent and a flow command that names it.
+    fn pack() -> (TempDir, PathBuf) {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join(".claude/agents")).unwrap();
+        std::fs::create_dir_all(root.join(".claude/commands")).unwrap();
+        std::fs::write(
+            root.join(".claude/agents/work-planner.md"),
+            "Traces REQ-001\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join(".claude/commands/flow-1-signal.md"),
+            "# Flow 1\n\n- [ ] Run `work-planner`\n",
+        )
+        .unwrap();
+        (tmp, root)
+    }
+
+    /// Check 7 only: the rest flag a two-file pack all over.
+    fn checker(root: &Path, overlay: &Arc<Overlay<DiskFiles>>) -> PackChecker {
+        PackChecker::new(root)
+            .files(overlay.clone())
+            .selection(checks::Selection {
+                only: vec![7],
+                ..Default::default()
+            })
+    }
+
+    fn uri(path: &Path) -> Url {
+        Url::from_file_path(path).unwrap()
+    }
+
+    fn at(path: &Path, line: u32, character: u32) -> Value {
+        json!({
+            "textDocument": { "uri": uri(path) },
+            "position": { "line": line, "character": character },
+        })
+    }
+
+    #[test]
+    fn test_buffer_edits_publish_and_clear_diagnostics() {
+        let (_tmp, root) = pack();
+        let overlay = Arc::new(Overlay::new(DiskFiles));
+        let checker = checker(&root, &overlay);
+        let mut server = Server::new(Session::start(&checker, None).unwrap(), overlay);
+        server.publish();
+
+        let agent = root.join(".claude/agents/work-planner.md");
+        let open = json!({
+            "textDocument": {
+                "uri": uri(&agent), "languageId": "markdown", "version": 1,
+                "text": "# Planner\nTraces FR-001\n",
+            }
+        });
+        server.notify(DidOpenTextDocument::METHOD, open).unwrap();
+        let published = server.publish();
+
+        assert_eq!(published.len(), 1, "{published:?}");
+        assert_eq!(published[0].uri, uri(&agent));
+        let diag = &published[0].diagnostics[0];
+        assert_eq!(diag.code, Some(NumberOrString::Number(7)));
+        assert_eq!(diag.severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(
+            diag.range,
+            Range::new(Position::new(1, 6), Position::new(1, 13))
+        );
+        assert!(std::fs::read_to_string(&agent).unwrap().contains("REQ-001"));
+
+        let hover = server
+            .request(HoverRequest::METHOD, at(&agent, 1, 3))
+            .unwrap()
+            .unwrap();
+        let text = hover["contents"]["value"].as_str().unwrap();
+        assert!(text.starts_with("**pack-check [7]**"), "{text}");
+        assert!(text.contains("No old taxonomy"), "{text}");
+        let none = server.request(HoverRequest::METHOD, at(&agent, 0, 0));
+        assert_eq!(none.unwrap(), Some(Value::Null));
+
+        let close = json!({ "textDocument": { "uri": uri(&agent) } });
+        server.notify(DidCloseTextDocument::METHOD, close).unwrap();
+        let published = server.publish();
+        assert_eq!(published.len(), 1);
+        assert!(published[0].diagnostics.is_empty());
+        assert!(server.publish().is_empty(), "nothing changed since");
+    }
+
+    #[test]
+    fn test_definition_and_completion_of_agent_names() {
+        let (_tmp, root) = pack();
+        let overlay = Arc::new(Overlay::new(DiskFiles));
+        let checker = checker(&root, &overlay);
+        let server = Server::new(Session::start(&checker, None).unwrap(), overlay);
+        let flow = root.join(".claude/commands/flow-1-signal.md");
+
+        let definition = server
+            .request(GotoDefinition::METHOD, at(&flow, 2, 14))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            definition["uri"],
+            json!(uri(&root.join(".claude/agents/work-planner.md")))
+        );
+        let not_an_agent = server.request(GotoDefinition::METHOD, at(&flow, 2, 3));
+        assert_eq!(not_an_agent.unwrap(), Some(Value::Null));
+
+        let completion = server
+            .request(Completion::METHOD, at(&flow, 2, 0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(completion[0]["label"], "work-planner");
+        assert_eq!(completion[0]["detail"], ".claude/agents/work-planner.md");
+
+        let outside = at(&root.join("README.md"), 0, 0);
+        assert_eq!(
+            server.request(Completion::METHOD, outside).unwrap(),
+            Some(Value::Null)
+        );
+        assert_eq!(
+            server.request("textDocument/rename", json!({})).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_to_lsp_range_starts_at_column_in_utf16() {
+        let d = Diagnostic {
+            level: Level::Fail,
+            check_id: 39,
+            check_title: "Title".to_string(),
+            message: "msg".to_string(),
+            location: Some(
+                crate::reporter::Location::file("a.md")
+                    .with_line(2)
+                    .with_column(4),
+            ),
+        };
+        // `😀` is one char but two UTF-16 units.
+        let text = "x
+😀 git add .
+";
+        let range = to_lsp(&d, Some(text)).range;
+        assert_eq!(range.start, Position::new(1, 4));
+        assert_eq!(range.end, Position::new(1, 12));
+
+        assert_eq!(to_lsp(&d, None).range.start, Position::new(1, 3));
+        let mut whole_line = d;
+        whole_line.location = Some(crate::reporter::Location::file("a.md").with_line(2));
+        assert_eq!(
+            to_lsp(&whole_line, Some(text)).range.start,
+            Position::new(1, 0)
+        );
+    }
+
+    #[test]
+    fn test_word_at() {
+        let line = "- [ ] Run `work-planner` → ok";
+        assert_eq!(word_at(line, 11), Some("work-planner"));
+        assert_eq!(word_at(line, 23), Some("work-planner"));
+        assert_eq!(word_at(line, 10), None);
+        assert_eq!(word_at(line, 28), Some("ok"));
+        assert_eq!(word_at(line, 29), Some("ok"), "end of line");
+        assert_eq!(word_at("", 0), None);
+    }
+}
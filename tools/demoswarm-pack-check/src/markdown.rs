@@ -0,0 +1,535 @@
+//! Parsed markdown model shared by the checks.
+//!
+//! Line-oriented on purpose: every check reports line numbers, so the model
+//! classifies each line (frontmatter, prose, blockquote, fence delimiter, code)
+//! and records the structure the checks ask about: headings (as a tree), their
+//! sections, fenced blocks with language tags, frontmatter, and pipe tables.
+//!
+//! Fences are recognized at any indentation (agent docs nest them in lists),
+//! with ``` or ~~~; a fence closes on a line of the same character that is at
+//! least as long. An unclosed fence runs to the end of the file.
+
+use std::sync::Arc;
+
+/// Which lines of a markdown file a check scans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// Every line, structure ignored.
+    All,
+    /// Instruction text: outside fences, frontmatter and blockquotes.
+    Prose,
+    /// Inside fenced blocks whose language is one of these (empty = any fence).
+    Code(&'static [&'static str]),
+    /// Inside shell blocks: tagged with one of `SHELL_LANGS`, or untagged (pack
+    /// docs often leave the tag off commands).
+    Shell,
+    /// Prose and code, i.e. everything but frontmatter and quoted examples.
+    Both,
+}
+
+/// Language tags treated as shell in fenced blocks.
+pub const SHELL_LANGS: &[&str] = &["bash", "sh", "shell", "zsh"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    Frontmatter,
+    Prose,
+    /// A `>` blockquote line outside fences (quoted examples).
+    Quote,
+    /// An opening or closing fence line.
+    FenceDelimiter,
+    /// Inside the fenced block with this index in `Markdown::fences`.
+    Code(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frontmatter {
+    /// 1-based lines of the opening and closing `---`.
+    pub start: usize,
+    pub end: usize,
+    /// Text between the delimiters.
+    pub raw: String,
+}
+
+impl Frontmatter {
+    /// Value of a top-level `key: value` field (trimmed, non-empty).
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.raw.lines().find_map(|line| {
+            let value = line.strip_prefix(key)?.strip_prefix(':')?.trim();
+            (!value.is_empty()).then_some(value)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    pub level: usize,
+    pub text: String,
+    /// 1-based line.
+    pub line: usize,
+    /// Index of the enclosing heading in `Markdown::headings`.
+    pub parent: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fence {
+    /// Lowercased first word of the info string; `None` for a bare fence.
+    pub lang: Option<String>,
+    /// 1-based line of the opening delimiter.
+    pub start: usize,
+    /// 1-based line of the closing delimiter; `None` if the fence is unclosed.
+    pub end: Option<usize>,
+}
+
+impl Fence {
+    fn lang_in(&self, langs: &[&str]) -> bool {
+        langs.is_empty() || self.lang.as_deref().is_some_and(|l| langs.contains(&l))
+    }
+}
+
+/// A heading and the lines up to the next heading of the same or a higher level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section {
+    /// Index into `Markdown::headings`.
+    pub heading: usize,
+    /// 1-based line of the heading.
+    pub start: usize,
+    /// 1-based last line of the section (inclusive).
+    pub end: usize,
+}
+
+/// A pipe table (`| a | b |` header, `|---|---|` delimiter, then rows).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    /// Trimmed header cells.
+    pub header: Vec<String>,
+    /// `(1-based line, trimmed cells)` per body row.
+    pub rows: Vec<(usize, Vec<String>)>,
+}
+
+impl Table {
+    /// Index of the header cell equal to `name` (case-insensitive).
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.header
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Markdown {
+    content: Arc<str>,
+    kinds: Vec<LineKind>,
+    pub frontmatter: Option<Frontmatter>,
+    pub headings: Vec<Heading>,
+    pub fences: Vec<Fence>,
+}
+
+impl Markdown {
+    pub fn parse(content: impl Into<Arc<str>>) -> Self {
+        let content: Arc<str> = content.into();
+        let lines: Vec<&str> = content.lines().collect();
+
+        let frontmatter = parse_frontmatter(&lines);
+        let mut kinds = Vec::with_capacity(lines.len());
+        let mut headings: Vec<Heading> = Vec::new();
+        let mut fences: Vec<Fence> = Vec::new();
+        // (fence char, opening length) of the fence we're inside
+        let mut open: Option<(char, usize)> = None;
+
+        for (i, line) in lines.iter().enumerate() {
+            let line_no = i + 1;
+
+            if frontmatter.as_ref().is_some_and(|fm| line_no <= fm.end) {
+                kinds.push(LineKind::Frontmatter);
+                continue;
+            }
+
+            let trimmed = line.trim_start();
+            if let Some((ch, len)) = open {
+                if is_closing_fence(trimmed, ch, len) {
+                    fences.last_mut().expect("open fence").end = Some(line_no);
+                    open = None;
+                    kinds.push(LineKind::FenceDelimiter);
+                } else {
+                    kinds.push(LineKind::Code(fences.len() - 1));
+                }
+                continue;
+            }
+
+            if let Some((ch, len, info)) = opening_fence(trimmed) {
+                open = Some((ch, len));
+                fences.push(Fence {
+                    lang: info
+                        .split_whitespace()
+                        .next()
+                        .map(|l| l.to_ascii_lowercase()),
+                    start: line_no,
+                    end: None,
+                });
+                kinds.push(LineKind::FenceDelimiter);
+            } else if trimmed.starts_with('>') {
+                kinds.push(LineKind::Quote);
+            } else {
+                if let Some((level, text)) = parse_heading(line) {
+                    let parent = headings.iter().rposition(|h| h.level < level);
+                    headings.push(Heading {
+                        level,
+                        text,
+                        line: line_no,
+                        parent,
+                    });
+                }
+                kinds.push(LineKind::Prose);
+            }
+        }
+
+        Self {
+            content,
+            kinds,
+            frontmatter,
+            headings,
+            fences,
+        }
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.kinds.len()
+    }
+
+    /// Classification of a 1-based line.
+    pub fn kind(&self, line_no: usize) -> Option<LineKind> {
+        self.kinds.get(line_no.checked_sub(1)?).copied()
+    }
+
+    /// The fenced block containing a 1-based line, if any.
+    pub fn fence_at(&self, line_no: usize) -> Option<&Fence> {
+        match self.kind(line_no)? {
+            LineKind::Code(idx) => self.fences.get(idx),
+            _ => None,
+        }
+    }
+
+    pub fn in_scope(&self, line_no: usize, scope: Scope) -> bool {
+        let Some(kind) = self.kind(line_no) else {
+            return false;
+        };
+        match (scope, kind) {
+            (Scope::All, _) => true,
+            (Scope::Prose, LineKind::Prose) => true,
+            (Scope::Code(langs), LineKind::Code(idx)) => self.fences[idx].lang_in(langs),
+            (Scope::Shell, LineKind::Code(idx)) => {
+                let fence = &self.fences[idx];
+                fence.lang.is_none() || fence.lang_in(SHELL_LANGS)
+            }
+            (Scope::Both, LineKind::Prose | LineKind::Code(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// `(1-based line, text)` for every line in `scope`.
+    pub fn lines(&self, scope: Scope) -> impl Iterator<Item = (usize, &str)> {
+        self.content
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(move |(line_no, _)| self.in_scope(*line_no, scope))
+    }
+
+    /// Every heading's section, in document order.
+    pub fn sections(&self) -> Vec<Section> {
+        (0..self.headings.len())
+            .map(|idx| self.section_of(idx))
+            .collect()
+    }
+
+    /// The section of the first heading whose text is `title` (case-insensitive).
+    pub fn section(&self, title: &str) -> Option<Section> {
+        self.headings
+            .iter()
+            .position(|h| h.text.eq_ignore_ascii_case(title))
+            .map(|idx| self.section_of(idx))
+    }
+
+    /// Every pipe table outside fences and quotes, in document order.
+    pub fn tables(&self) -> Vec<Table> {
+        let lines: Vec<(usize, &str)> = self.lines(Scope::Prose).collect();
+        let mut tables = Vec::new();
+        let mut i = 0;
+
+        while i + 1 < lines.len() {
+            let (header_line, header) = lines[i];
+            let (delim_line, delim) = lines[i + 1];
+            let is_delim = table_cells(delim).is_some_and(|cells| {
+                cells.iter().all(|c| {
+                    let c = c.trim_matches(':');
+                    !c.is_empty() && c.chars().all(|ch| ch == '-')
+                })
+            });
+            let (Some(header), true) = (
+                table_cells(header),
+                is_delim && delim_line == header_line + 1,
+            ) else {
+                i += 1;
+                continue;
+            };
+
+            let mut rows = Vec::new();
+            let mut prev = delim_line;
+            i += 2;
+            while let Some(&(line_no, line)) = lines.get(i) {
+                let Some(cells) = table_cells(line).filter(|_| line_no == prev + 1) else {
+                    break;
+                };
+                rows.push((line_no, cells));
+                prev = line_no;
+                i += 1;
+            }
+            tables.push(Table { header, rows });
+        }
+
+        tables
+    }
+
+    fn section_of(&self, idx: usize) -> Section {
+        let heading = &self.headings[idx];
+        let end = self.headings[idx + 1..]
+            .iter()
+            .find(|h| h.level <= heading.level)
+            .map(|h| h.line - 1)
+            .unwrap_or(self.line_count());
+        Section {
+            heading: idx,
+            start: heading.line,
+            end,
+        }
+    }
+}
+
+fn parse_frontmatter(lines: &[&str]) -> Option<Frontmatter> {
+    if lines.first()?.trim_end() != "---" {
+        return None;
+    }
+    let close = lines[1..].iter().position(|l| l.trim_end() == "---")? + 1;
+    Some(Frontmatter {
+        start: 1,
+        end: close + 1,
+        raw: lines[1..close].join("\n"),
+    })
+}
+
+/// `(fence char, length, info string)` for an opening fence line.
+fn opening_fence(trimmed: &str) -> Option<(char, usize, &str)> {
+    let ch = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|c| *c == ch).count();
+    if len < 3 {
+        return None;
+    }
+    let info = trimmed[len..].trim();
+    // CommonMark: a backtick fence's info string cannot contain backticks.
+    if ch == '`' && info.contains('`') {
+        return None;
+    }
+    Some((ch, len, info))
+}
+
+fn is_closing_fence(trimmed: &str, ch: char, open_len: usize) -> bool {
+    let len = trimmed.chars().take_while(|c| *c == ch).count();
+    len >= open_len && trimmed[len..].trim().is_empty()
+}
+
+/// Trimmed cells of a `| a | b |` row; `\|` stays inside a cell.
+fn table_cells(line: &str) -> Option<Vec<String>> {
+    let inner = line.trim().strip_prefix('|')?;
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+
+    let mut cells = vec![String::new()];
+    let mut chars = inner.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if chars.peek() == Some(&'|') => {
+                cells.last_mut().expect("a cell").push('|');
+                chars.next();
+            }
+            '|' => cells.push(String::new()),
+            _ => cells.last_mut().expect("a cell").push(ch),
+        }
+    }
+    Some(cells.iter().map(|c| c.trim().to_string()).collect())
+}
+
+/// ATX heading (`## Title`), level 1-6.
+fn parse_heading(line: &str) -> Option<(usize, String)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    let text = rest.trim().trim_end_matches('#').trim_end();
+    Some((level, text.to_string()))
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "\
+---
+name: demo-agent
+description: Demo
+---
+# Demo
+
+Run the tools.
+
+## Usage
+
+```bash
+git add .
+```
+
+> Don't: git reset --hard
+
+### Details
+~~~
+plain
+~~~
+## Skills
+    ```sh
+    demoswarm count
+";
+
+    #[test]
+    fn test_frontmatter_fields() {
+        let md = Markdown::parse(DOC);
+        let fm = md.frontmatter.as_ref().unwrap();
+        assert_eq!((fm.start, fm.end), (1, 4));
+        assert_eq!(fm.get("name"), Some("demo-agent"));
+        assert_eq!(fm.get("missing"), None);
+        assert_eq!(md.kind(2), Some(LineKind::Frontmatter));
+    }
+
+    #[test]
+    fn test_unclosed_frontmatter_is_prose() {
+        let md = Markdown::parse("---\nname: x\n");
+        assert!(md.frontmatter.is_none());
+        assert_eq!(md.kind(2), Some(LineKind::Prose));
+    }
+
+    #[test]
+    fn test_fences_with_languages() {
+        let md = Markdown::parse(DOC);
+        assert_eq!(md.fences.len(), 3);
+        assert_eq!(md.fences[0].lang.as_deref(), Some("bash"));
+        assert_eq!((md.fences[0].start, md.fences[0].end), (11, Some(13)));
+        assert_eq!(md.fences[1].lang, None);
+        // Indented, unclosed fence runs to end of file
+        assert_eq!(md.fences[2].lang.as_deref(), Some("sh"));
+        assert_eq!(md.fences[2].end, None);
+        assert_eq!(md.fence_at(23).unwrap().start, 22);
+    }
+
+    #[test]
+    fn test_line_kinds() {
+        let md = Markdown::parse(DOC);
+        assert_eq!(md.kind(7), Some(LineKind::Prose));
+        assert_eq!(md.kind(11), Some(LineKind::FenceDelimiter));
+        assert_eq!(md.kind(12), Some(LineKind::Code(0)));
+        assert_eq!(md.kind(15), Some(LineKind::Quote));
+        assert_eq!(md.kind(0), None);
+        assert_eq!(md.kind(99), None);
+    }
+
+    #[test]
+    fn test_headings_tree_and_sections() {
+        let md = Markdown::parse(DOC);
+        let texts: Vec<&str> = md.headings.iter().map(|h| h.text.as_str()).collect();
+        assert_eq!(texts, vec!["Demo", "Usage", "Details", "Skills"]);
+        assert_eq!(md.headings[1].parent, Some(0));
+        assert_eq!(md.headings[2].parent, Some(1));
+        assert_eq!(md.headings[3].parent, Some(0));
+
+        let usage = md.section("usage").unwrap();
+        assert_eq!((usage.start, usage.end), (9, 20));
+        let details = md.section("Details").unwrap();
+        assert_eq!((details.start, details.end), (17, 20));
+        assert_eq!(md.sections().len(), 4);
+    }
+
+    #[test]
+    fn test_heading_inside_fence_is_ignored() {
+        let md = Markdown::parse("```md\n# Not a heading\n```\n#NoSpace\n## Real ##\n");
+        assert_eq!(md.headings.len(), 1);
+        assert_eq!(md.headings[0].text, "Real");
+    }
+
+    #[test]
+    fn test_shell_scope_includes_untagged_fences() {
+        let md = Markdown::parse("```\ngit push\n```\n```python\nrun()\n```\n```Bash\nls\n```\n");
+        let lines = md.lines(Scope::Shell).map(|(n, _)| n).collect::<Vec<_>>();
+        assert_eq!(lines, vec![2, 8]);
+        let tagged = md
+            .lines(Scope::Code(SHELL_LANGS))
+            .map(|(n, _)| n)
+            .collect::<Vec<_>>();
+        assert_eq!(tagged, vec![8]);
+    }
+
+    #[test]
+    fn test_closing_fence_must_match() {
+        let md = Markdown::parse("````\n```\nstill code\n````\nprose\n");
+        assert_eq!(md.kind(2), Some(LineKind::Code(0)));
+        assert_eq!(md.kind(3), Some(LineKind::Code(0)));
+        assert_eq!(md.kind(5), Some(LineKind::Prose));
+    }
+
+    #[test]
+    fn test_scopes() {
+        let md = Markdown::parse(DOC);
+        let lines = |scope| md.lines(scope).map(|(n, _)| n).collect::<Vec<_>>();
+
+        assert!(lines(Scope::Prose).contains(&7));
+        assert!(!lines(Scope::Prose).contains(&12));
+        assert!(!lines(Scope::Prose).contains(&15));
+        assert_eq!(lines(Scope::Code(SHELL_LANGS)), vec![12, 23]);
+        assert_eq!(lines(Scope::Code(&[])), vec![12, 19, 23]);
+        assert_eq!(lines(Scope::Shell), vec![12, 19, 23]);
+        assert!(lines(Scope::Both).contains(&12) && lines(Scope::Both).contains(&7));
+        assert!(!lines(Scope::Both).contains(&15) && !lines(Scope::Both).contains(&2));
+        assert_eq!(lines(Scope::All).len(), md.line_count());
+    }
+
+    #[test]
+    fn test_tables() {
+        let md = Markdown::parse(
+            "| Agent | Description |\n|---|:---:|\n| `a` | Does a \\| b |\n| b |\n\nnot a row\n\
+             | x |\n\n```md\n| In | Fence |\n|---|---|\n```\n",
+        );
+        let tables = md.tables();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].header, ["Agent", "Description"]);
+        assert_eq!(tables[0].column("description"), Some(1));
+        assert_eq!(
+            tables[0].rows,
+            [
+                (3, vec!["`a`".to_string(), "Does a | b".to_string()]),
+                (4, vec!["b".to_string()]),
+            ]
+        );
+    }
+}
@@ -1,8 +1,14 @@
+use std::collections::HashSet;
+use std::fmt;
 use std::process::ExitCode;
 
 use serde::Serialize;
 
+use crate::baseline::{Baseline, BaselineEntry};
+use crate::checks::CheckSpec;
 use crate::cli::OutputFormat;
+use crate::sarif;
+use crate::suppress::Suppressions;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -12,12 +18,82 @@ pub enum Level {
     Fail,
 }
 
+/// Where a diagnostic points in the pack (repo-relative path, 1-based positions).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Location {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+}
+
+impl Location {
+    /// A whole-file location.
+    pub fn file(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            line: None,
+            column: None,
+            end_line: None,
+        }
+    }
+
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn with_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    pub fn with_end_line(mut self, end_line: usize) -> Self {
+        self.end_line = Some(end_line);
+        self
+    }
+}
+
+/// One located item listed under a headline diagnostic.
+///
+/// Text output prints it as `path:line:text` (grep-style); JSON output turns
+/// each finding into its own located diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub location: Location,
+    pub text: String,
+}
+
+impl Finding {
+    pub fn new(location: Location, text: impl Into<String>) -> Self {
+        Self {
+            location,
+            text: text.into(),
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.location.line, self.text.is_empty()) {
+            (Some(line), _) => write!(f, "{}:{}:{}", self.location.path, line, self.text),
+            (None, true) => write!(f, "{}", self.location.path),
+            (None, false) => write!(f, "{} {}", self.location.path, self.text),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Diagnostic {
     pub level: Level,
     pub check_id: u32,
     pub check_title: String,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -27,6 +103,8 @@ pub struct PackCounts {
     pub skills: usize,
 }
 
+/// The outcome of a run: what `--format json` prints and `PackChecker::run`
+/// returns. Passing diagnostics are left out.
 #[derive(Debug, Clone, Serialize)]
 pub struct RunReport {
     pub schema_version: u32,
@@ -35,6 +113,31 @@ pub struct RunReport {
     pub warnings: usize,
     pub counts: PackCounts,
     pub diagnostics: Vec<Diagnostic>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<BaselineSummary>,
+}
+
+impl RunReport {
+    /// No errors, and no warnings either when `strict_warnings` is set.
+    pub fn passed(&self, strict_warnings: bool) -> bool {
+        self.errors == 0 && (!strict_warnings || self.warnings == 0)
+    }
+}
+
+/// Outcome of applying `--baseline`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BaselineSummary {
+    /// Diagnostics hidden because the baseline accepts them.
+    pub suppressed: usize,
+    /// Baseline entries whose check ran but which no longer fire.
+    pub stale: Vec<BaselineEntry>,
+}
+
+/// A diagnostic held by a buffered reporter until it is appended.
+#[derive(Debug)]
+enum Buffered {
+    Line(Level, String, Option<Location>),
+    Findings(Level, String, Vec<Finding>),
 }
 
 #[derive(Debug)]
@@ -50,6 +153,14 @@ pub struct Reporter {
     current_check_title: String,
 
     diagnostics: Vec<Diagnostic>,
+
+    baseline: Option<Baseline>,
+    suppressions: Option<Suppressions>,
+    suppressed: usize,
+    checks_run: HashSet<u32>,
+
+    /// `Some` for a per-check buffer: diagnostics are held, not recorded.
+    buffer: Option<Vec<Buffered>>,
 }
 
 impl Reporter {
@@ -63,9 +174,137 @@ impl Reporter {
             current_check_id: 0,
             current_check_title: String::new(),
             diagnostics: Vec::new(),
+            baseline: None,
+            suppressions: None,
+            suppressed: 0,
+            checks_run: HashSet::new(),
+            buffer: None,
+        }
+    }
+
+    /// A reporter for one check running off the main thread. It holds the
+    /// check's diagnostics in order until `append` replays them.
+    pub fn buffered() -> Self {
+        Self {
+            buffer: Some(Vec::new()),
+            ..Self::silent()
+        }
+    }
+
+    /// Replay a buffered reporter's diagnostics as if the check had reported
+    /// here directly (baseline, suppressions, counts and printing all apply).
+    pub fn append(&mut self, buffered: Reporter) {
+        for item in buffered.buffer.unwrap_or_default() {
+            match item {
+                Buffered::Line(level, msg, location) => self.emit(level, &msg, location),
+                Buffered::Findings(level, msg, findings) => {
+                    self.emit_findings(level, &msg, findings)
+                }
+            }
+        }
+    }
+
+    /// Mark the directives that a buffered check's diagnostics hit, without
+    /// reporting anything (`--only 63` runs every check just for this).
+    pub fn absorb(&mut self, check_id: u32, buffered: Reporter) {
+        self.checks_run.insert(check_id);
+        let Some(sup) = &mut self.suppressions else {
+            return;
+        };
+        let located = buffered
+            .buffer
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|item| match item {
+                Buffered::Line(level, _, location) => vec![(level, location)],
+                Buffered::Findings(level, _, findings) if findings.is_empty() => {
+                    vec![(level, None)]
+                }
+                Buffered::Findings(level, _, findings) => findings
+                    .into_iter()
+                    .map(|f| (level, Some(f.location)))
+                    .collect(),
+            });
+        for (level, location) in located {
+            sup.suppresses(&Diagnostic {
+                level,
+                check_id,
+                check_title: String::new(),
+                message: String::new(),
+                location,
+            });
         }
     }
 
+    fn count(&mut self, level: Level) {
+        match level {
+            Level::Pass => {}
+            Level::Warn => self.warnings += 1,
+            Level::Fail => self.errors += 1,
+        }
+    }
+
+    /// A reporter that only records diagnostics (for `RunReport`s).
+    pub fn silent() -> Self {
+        Self::new(OutputFormat::Json, false, false)
+    }
+
+    /// Hide diagnostics accepted by `baseline`; they no longer count or print.
+    pub fn with_baseline(mut self, baseline: Baseline) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+
+    /// Honor inline `pack-check-disable*` directives.
+    pub fn with_suppressions(mut self, suppressions: Suppressions) -> Self {
+        self.suppressions = Some(suppressions);
+        self
+    }
+
+    pub fn has_suppressions(&self) -> bool {
+        self.suppressions.as_ref().is_some_and(|s| !s.is_empty())
+    }
+
+    /// Warn about directives that suppressed nothing. Call after all checks ran;
+    /// `all_ran` says whether the full registry ran (no `--only`/`--skip`).
+    pub fn report_unused_suppressions(&mut self, all_ran: bool) {
+        let Some(sup) = &self.suppressions else {
+            return;
+        };
+        let mut problems: Vec<(Location, String)> = sup
+            .malformed()
+            .iter()
+            .map(|m| (m.location.clone(), m.message.clone()))
+            .collect();
+        problems.extend(
+            sup.unused(|id| self.checks_run.contains(&id), all_ran)
+                .into_iter()
+                .map(|dir| {
+                    (
+                        dir.location(),
+                        format!(
+                            "Suppression directive '{}' matches no diagnostic (remove it)",
+                            dir.describe()
+                        ),
+                    )
+                }),
+        );
+
+        if problems.is_empty() {
+            self.pass("All suppression directives suppress something");
+            return;
+        }
+        problems.sort_by(|a, b| a.0.path.cmp(&b.0.path).then(a.0.line.cmp(&b.0.line)));
+        for (loc, message) in problems {
+            self.warn_at(loc, message);
+        }
+    }
+
+    /// Every diagnostic emitted so far (after baseline suppression).
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
     pub fn print_banner(&self) {
         if self.format != OutputFormat::Text {
             return;
@@ -79,6 +318,7 @@ impl Reporter {
     pub fn section(&mut self, id: u32, title: &str) {
         self.current_check_id = id;
         self.current_check_title = title.to_string();
+        self.checks_run.insert(id);
 
         if self.format != OutputFormat::Text {
             return;
@@ -94,17 +334,41 @@ impl Reporter {
     }
 
     pub fn pass(&mut self, msg: impl AsRef<str>) {
-        self.emit(Level::Pass, msg.as_ref());
+        self.emit(Level::Pass, msg.as_ref(), None);
     }
 
     pub fn warn(&mut self, msg: impl AsRef<str>) {
-        self.warnings += 1;
-        self.emit(Level::Warn, msg.as_ref());
+        self.emit(Level::Warn, msg.as_ref(), None);
     }
 
     pub fn fail(&mut self, msg: impl AsRef<str>) {
-        self.errors += 1;
-        self.emit(Level::Fail, msg.as_ref());
+        self.emit(Level::Fail, msg.as_ref(), None);
+    }
+
+    pub fn warn_at(&mut self, loc: Location, msg: impl AsRef<str>) {
+        self.emit(Level::Warn, msg.as_ref(), Some(loc));
+    }
+
+    pub fn fail_at(&mut self, loc: Location, msg: impl AsRef<str>) {
+        self.emit(Level::Fail, msg.as_ref(), Some(loc));
+    }
+
+    /// Warn with a headline, listing located findings underneath. Each
+    /// finding is its own diagnostic and counts as one warning.
+    pub fn warn_findings<I>(&mut self, msg: impl AsRef<str>, findings: I)
+    where
+        I: IntoIterator<Item = Finding>,
+    {
+        self.emit_findings(Level::Warn, msg.as_ref(), findings);
+    }
+
+    /// Fail with a headline, listing located findings underneath. Each
+    /// finding is its own diagnostic and counts as one error.
+    pub fn fail_findings<I>(&mut self, msg: impl AsRef<str>, findings: I)
+    where
+        I: IntoIterator<Item = Finding>,
+    {
+        self.emit_findings(Level::Fail, msg.as_ref(), findings);
     }
 
     pub fn indent_lines<I, S>(&self, lines: I)
@@ -141,9 +405,20 @@ impl Reporter {
         println!();
     }
 
-    pub fn finish(self, repo_root: &str, counts: PackCounts) -> anyhow::Result<ExitCode> {
+    pub fn finish(
+        self,
+        repo_root: &str,
+        counts: PackCounts,
+        checks: &[CheckSpec],
+    ) -> anyhow::Result<ExitCode> {
+        let baseline = self.baseline_summary();
+
         match self.format {
             OutputFormat::Text => {
+                if let Some(summary) = &baseline {
+                    self.print_baseline_summary(summary);
+                }
+
                 let code = if self.errors == 0 && (!self.strict_warnings || self.warnings == 0) {
                     if self.warnings == 0 {
                         println!("{}", self.colorize(Level::Pass, "All checks passed!"));
@@ -174,22 +449,23 @@ impl Reporter {
                 Ok(code)
             }
             OutputFormat::Json => {
-                let report = RunReport {
-                    schema_version: 1,
-                    repo_root: repo_root.to_string(),
-                    errors: self.errors,
-                    warnings: self.warnings,
-                    counts,
-                    diagnostics: self
-                        .diagnostics
-                        .into_iter()
-                        .filter(|d| d.level != Level::Pass)
-                        .collect(),
-                };
+                let strict_warnings = self.strict_warnings;
+                let report = self.into_report(repo_root, counts);
 
                 println!("{}", serde_json::to_string_pretty(&report)?);
 
-                if report.errors == 0 && (!self.strict_warnings || report.warnings == 0) {
+                if report.passed(strict_warnings) {
+                    Ok(ExitCode::SUCCESS)
+                } else {
+                    Ok(ExitCode::from(1))
+                }
+            }
+            OutputFormat::Sarif => {
+                let log = sarif::build_log(repo_root, checks, &self.diagnostics);
+
+                println!("{}", serde_json::to_string_pretty(&log)?);
+
+                if self.errors == 0 && (!self.strict_warnings || self.warnings == 0) {
                     Ok(ExitCode::SUCCESS)
                 } else {
                     Ok(ExitCode::from(1))
@@ -198,19 +474,137 @@ impl Reporter {
         }
     }
 
-    fn emit(&mut self, level: Level, msg: &str) {
-        // JSON mode: keep a structured record.
-        if self.format == OutputFormat::Json {
-            self.diagnostics.push(Diagnostic {
-                level,
-                check_id: self.current_check_id,
-                check_title: self.current_check_title.clone(),
-                message: msg.to_string(),
-            });
+    /// The recorded diagnostics as a report, without printing anything.
+    pub fn into_report(self, repo_root: &str, counts: PackCounts) -> RunReport {
+        let baseline = self.baseline_summary();
+        RunReport {
+            schema_version: 1,
+            repo_root: repo_root.to_string(),
+            errors: self.errors,
+            warnings: self.warnings,
+            counts,
+            diagnostics: self
+                .diagnostics
+                .into_iter()
+                .filter(|d| d.level != Level::Pass)
+                .collect(),
+            baseline,
+        }
+    }
+
+    fn baseline_summary(&self) -> Option<BaselineSummary> {
+        let baseline = self.baseline.as_ref()?;
+        Some(BaselineSummary {
+            suppressed: self.suppressed,
+            stale: baseline.stale(&self.checks_run),
+        })
+    }
+
+    fn print_baseline_summary(&self, summary: &BaselineSummary) {
+        println!("Baseline:");
+        println!("  Suppressed: {}", summary.suppressed);
+        println!("  Stale:      {}", summary.stale.len());
+        if !summary.stale.is_empty() {
+            println!("  No longer firing (refresh with --write-baseline):");
+            for entry in &summary.stale {
+                match &entry.path {
+                    Some(path) => println!("    [{}] {path}: {}", entry.check_id, entry.message),
+                    None => println!("    [{}] {}", entry.check_id, entry.message),
+                }
+            }
+        }
+        println!();
+    }
+
+    /// Records (and in text mode prints) one diagnostic per finding that is
+    /// not suppressed, counting each.
+    fn emit_findings<I>(&mut self, level: Level, msg: &str, findings: I)
+    where
+        I: IntoIterator<Item = Finding>,
+    {
+        let findings: Vec<Finding> = findings.into_iter().collect();
+        if let Some(buffer) = &mut self.buffer {
+            buffer.push(Buffered::Findings(level, msg.to_string(), findings));
+            return;
+        }
+        if findings.is_empty() {
+            return self.emit(level, msg, None);
+        }
+
+        // One located diagnostic per finding, headline as context.
+        let headline = msg.trim_end().trim_end_matches(':');
+        let mut shown = Vec::new();
+        for finding in findings {
+            let message = if finding.text.trim().is_empty() {
+                headline.to_string()
+            } else {
+                format!("{headline}: {}", finding.text.trim())
+            };
+            let diag = self.diagnostic(level, &message, Some(finding.location.clone()));
+            if self.is_suppressed(&diag) {
+                continue;
+            }
+            self.diagnostics.push(diag);
+            self.count(level);
+            shown.push(finding);
+        }
+
+        if !shown.is_empty() && self.format == OutputFormat::Text {
+            self.print_line(level, msg);
+            self.indent_lines(shown.iter().map(|f| f.to_string()));
+        }
+    }
+
+    /// Records (and in text mode prints) one diagnostic, counting it unless
+    /// it is suppressed.
+    fn emit(&mut self, level: Level, msg: &str, location: Option<Location>) {
+        if let Some(buffer) = &mut self.buffer {
+            buffer.push(Buffered::Line(level, msg.to_string(), location));
+            return;
+        }
+        let diag = self.diagnostic(level, msg, location);
+        if self.is_suppressed(&diag) {
             return;
         }
+        self.diagnostics.push(diag);
+        self.count(level);
+
+        if self.format == OutputFormat::Text {
+            self.print_line(level, msg);
+        }
+    }
+
+    fn diagnostic(&self, level: Level, msg: &str, location: Option<Location>) -> Diagnostic {
+        Diagnostic {
+            level,
+            check_id: self.current_check_id,
+            check_title: self.current_check_title.clone(),
+            message: msg.to_string(),
+            location,
+        }
+    }
+
+    fn is_suppressed(&mut self, diag: &Diagnostic) -> bool {
+        // Inline directives first, so they are marked as used even for baselined diagnostics.
+        if self
+            .suppressions
+            .as_mut()
+            .is_some_and(|sup| sup.suppresses(diag))
+        {
+            return true;
+        }
 
-        // Text mode: print immediately.
+        let suppressed = self
+            .baseline
+            .as_mut()
+            .is_some_and(|baseline| baseline.suppresses(diag));
+        if suppressed {
+            self.suppressed += 1;
+        }
+        suppressed
+    }
+
+    fn print_line(&self, level: Level, msg: &str) {
         let prefix = match level {
             Level::Pass => self.colorize(Level::Pass, "✓"),
             Level::Warn => self.colorize(Level::Warn, "⚠"),
@@ -279,6 +673,7 @@ mod tests {
             check_id: 1,
             check_title: "Test Check".to_string(),
             message: "Test message".to_string(),
+            location: None,
         };
 
         let cloned = diag.clone();
@@ -298,6 +693,7 @@ mod tests {
             check_id: 42,
             check_title: "Title".to_string(),
             message: "Msg".to_string(),
+            location: None,
         };
 
         let json = serde_json::to_string(&diag).unwrap();
@@ -353,7 +749,9 @@ mod tests {
                 check_id: 1,
                 check_title: "Check".to_string(),
                 message: "Error".to_string(),
+                location: None,
             }],
+            baseline: None,
         };
 
         let cloned = report.clone();
@@ -515,7 +913,7 @@ mod tests {
             commands: 2,
             skills: 3,
         };
-        let result = rep.finish("/test/repo", counts);
+        let result = rep.finish("/test/repo", counts, &[]);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), std::process::ExitCode::SUCCESS);
     }
@@ -530,7 +928,7 @@ mod tests {
             commands: 2,
             skills: 3,
         };
-        let result = rep.finish("/test/repo", counts);
+        let result = rep.finish("/test/repo", counts, &[]);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), std::process::ExitCode::from(1));
     }
@@ -545,12 +943,41 @@ mod tests {
             commands: 2,
             skills: 3,
         };
-        let result = rep.finish("/test/repo", counts);
+        let result = rep.finish("/test/repo", counts, &[]);
         assert!(result.is_ok());
         // With strict_warnings, warnings cause failure
         assert_eq!(result.unwrap(), std::process::ExitCode::from(1));
     }
 
+    #[test]
+    fn test_reporter_sarif_records_diagnostics() {
+        let mut rep = Reporter::new(OutputFormat::Sarif, false, false);
+        rep.section(4, "Check");
+        rep.fail_at(Location::file("a.md"), "broken");
+
+        assert_eq!(rep.errors, 1);
+        assert_eq!(rep.diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn test_reporter_finish_sarif_exit_codes() {
+        let counts = || PackCounts {
+            agents: 0,
+            commands: 0,
+            skills: 0,
+        };
+
+        let rep = Reporter::new(OutputFormat::Sarif, false, false);
+        let result = rep.finish("/test/repo", counts(), &[]);
+        assert_eq!(result.unwrap(), std::process::ExitCode::SUCCESS);
+
+        let mut rep = Reporter::new(OutputFormat::Sarif, false, true);
+        rep.section(1, "Check");
+        rep.warn("warning");
+        let result = rep.finish("/test/repo", counts(), &[]);
+        assert_eq!(result.unwrap(), std::process::ExitCode::from(1));
+    }
+
     #[test]
     fn test_reporter_finish_json_with_warnings_not_strict() {
         let mut rep = Reporter::new(OutputFormat::Json, false, false);
@@ -561,7 +988,7 @@ mod tests {
             commands: 2,
             skills: 3,
         };
-        let result = rep.finish("/test/repo", counts);
+        let result = rep.finish("/test/repo", counts, &[]);
         assert!(result.is_ok());
         // Without strict_warnings, warnings don't cause failure
         assert_eq!(result.unwrap(), std::process::ExitCode::SUCCESS);
@@ -580,7 +1007,7 @@ mod tests {
             commands: 2,
             skills: 3,
         };
-        let _ = rep.finish("/test/repo", counts);
+        let _ = rep.finish("/test/repo", counts, &[]);
     }
 
     // -------------------------------------------------------------------------
@@ -595,7 +1022,7 @@ mod tests {
             commands: 2,
             skills: 3,
         };
-        let result = rep.finish("/test/repo", counts);
+        let result = rep.finish("/test/repo", counts, &[]);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), std::process::ExitCode::SUCCESS);
     }
@@ -610,7 +1037,7 @@ mod tests {
             commands: 2,
             skills: 3,
         };
-        let result = rep.finish("/test/repo", counts);
+        let result = rep.finish("/test/repo", counts, &[]);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), std::process::ExitCode::SUCCESS);
     }
@@ -625,7 +1052,7 @@ mod tests {
             commands: 2,
             skills: 3,
         };
-        let result = rep.finish("/test/repo", counts);
+        let result = rep.finish("/test/repo", counts, &[]);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), std::process::ExitCode::from(1));
     }
@@ -640,7 +1067,7 @@ mod tests {
             commands: 2,
             skills: 3,
         };
-        let result = rep.finish("/test/repo", counts);
+        let result = rep.finish("/test/repo", counts, &[]);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), std::process::ExitCode::from(1));
     }
@@ -672,6 +1099,270 @@ mod tests {
         assert_eq!(rep.diagnostics[2].message, "fail msg");
     }
 
+    // -------------------------------------------------------------------------
+    // Location / Finding tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_location_serialize_skips_missing_positions() {
+        let loc = Location::file(".claude/agents/a.md");
+        let json = serde_json::to_string(&loc).unwrap();
+        assert_eq!(json, r#"{"path":".claude/agents/a.md"}"#);
+
+        let loc = loc.with_line(3).with_column(5).with_end_line(7);
+        let json = serde_json::to_string(&loc).unwrap();
+        assert!(json.contains("\"line\":3"));
+        assert!(json.contains("\"column\":5"));
+        assert!(json.contains("\"end_line\":7"));
+    }
+
+    #[test]
+    fn test_diagnostic_without_location_omits_field() {
+        let diag = Diagnostic {
+            level: Level::Fail,
+            check_id: 1,
+            check_title: "Title".to_string(),
+            message: "Msg".to_string(),
+            location: None,
+        };
+        let json = serde_json::to_string(&diag).unwrap();
+        assert!(!json.contains("location"));
+    }
+
+    #[test]
+    fn test_finding_display_grep_style() {
+        let with_line = Finding::new(Location::file("a.md").with_line(4), "text");
+        assert_eq!(with_line.to_string(), "a.md:4:text");
+
+        let file_only = Finding::new(Location::file("a.md"), "");
+        assert_eq!(file_only.to_string(), "a.md");
+
+        let file_with_note = Finding::new(Location::file("a.md"), "(note)");
+        assert_eq!(file_with_note.to_string(), "a.md (note)");
+    }
+
+    #[test]
+    fn test_reporter_fail_at_records_location() {
+        let mut rep = Reporter::new(OutputFormat::Json, false, false);
+        rep.section(4, "Check");
+        rep.fail_at(Location::file("a.md").with_line(2), "broken");
+
+        assert_eq!(rep.errors, 1);
+        let loc = rep.diagnostics[0].location.as_ref().unwrap();
+        assert_eq!(loc.path, "a.md");
+        assert_eq!(loc.line, Some(2));
+    }
+
+    #[test]
+    fn test_reporter_fail_findings_json_one_diagnostic_per_finding() {
+        let mut rep = Reporter::new(OutputFormat::Json, false, false);
+        rep.section(7, "Check");
+        rep.fail_findings(
+            "Found bad things:",
+            [
+                Finding::new(Location::file("a.md").with_line(1), "bad one"),
+                Finding::new(Location::file("b.md").with_line(9), "bad two"),
+            ],
+        );
+
+        // Two located diagnostics, each counted.
+        assert_eq!(rep.errors, 2);
+        assert_eq!(rep.diagnostics.len(), 2);
+        assert_eq!(rep.diagnostics[0].message, "Found bad things: bad one");
+        assert_eq!(
+            rep.diagnostics[1].location.as_ref().unwrap().path,
+            "b.md".to_string()
+        );
+    }
+
+    #[test]
+    fn test_reporter_fail_findings_empty_keeps_headline() {
+        let mut rep = Reporter::new(OutputFormat::Json, false, false);
+        rep.section(7, "Check");
+        rep.warn_findings("Headline", Vec::new());
+
+        assert_eq!(rep.warnings, 1);
+        assert_eq!(rep.diagnostics.len(), 1);
+        assert!(rep.diagnostics[0].location.is_none());
+    }
+
+    // -------------------------------------------------------------------------
+    // Baseline suppression tests
+    // -------------------------------------------------------------------------
+
+    fn baseline_of(entries: &[(u32, Option<&str>, &str)]) -> Baseline {
+        Baseline::from_entries(entries.iter().map(|(id, path, msg)| BaselineEntry {
+            check_id: *id,
+            path: path.map(str::to_string),
+            message: msg.to_string(),
+        }))
+    }
+
+    #[test]
+    fn test_reporter_baseline_suppresses_known_fail() {
+        let baseline = baseline_of(&[(4, Some("a.md"), "broken")]);
+        let mut rep = Reporter::new(OutputFormat::Json, false, false).with_baseline(baseline);
+        rep.section(4, "Check");
+        rep.fail_at(Location::file("a.md").with_line(9), "broken");
+        rep.fail_at(Location::file("b.md"), "broken");
+
+        assert_eq!(rep.errors, 1);
+        assert_eq!(rep.suppressed, 1);
+        assert_eq!(rep.diagnostics.len(), 1);
+        assert_eq!(rep.diagnostics[0].location.as_ref().unwrap().path, "b.md");
+    }
+
+    #[test]
+    fn test_reporter_baseline_findings_partially_suppressed() {
+        let baseline = baseline_of(&[(7, Some("a.md"), "Bad: old")]);
+        let mut rep = Reporter::new(OutputFormat::Text, false, false).with_baseline(baseline);
+        rep.section(7, "Check");
+        rep.fail_findings(
+            "Bad:",
+            [
+                Finding::new(Location::file("a.md").with_line(1), "old"),
+                Finding::new(Location::file("a.md").with_line(2), "new"),
+            ],
+        );
+
+        assert_eq!(rep.errors, 1);
+        assert_eq!(rep.diagnostics.len(), 1);
+        assert_eq!(rep.diagnostics[0].message, "Bad: new");
+    }
+
+    #[test]
+    fn test_reporter_baseline_findings_fully_suppressed() {
+        let baseline = baseline_of(&[(7, Some("a.md"), "Bad: old")]);
+        let mut rep = Reporter::new(OutputFormat::Text, false, false).with_baseline(baseline);
+        rep.section(7, "Check");
+        rep.warn_findings(
+            "Bad:",
+            [Finding::new(Location::file("a.md").with_line(1), "old")],
+        );
+
+        assert_eq!(rep.warnings, 0);
+        assert!(rep.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_reporter_baseline_summary_lists_stale_for_checks_run() {
+        let baseline = baseline_of(&[(4, None, "fixed"), (9, None, "not run")]);
+        let mut rep = Reporter::new(OutputFormat::Json, false, false).with_baseline(baseline);
+        rep.section(4, "Check");
+        rep.pass("all good");
+
+        let summary = rep.baseline_summary().unwrap();
+        assert_eq!(summary.suppressed, 0);
+        assert_eq!(summary.stale.len(), 1);
+        assert_eq!(summary.stale[0].message, "fixed");
+    }
+
+    // -------------------------------------------------------------------------
+    // Buffered reporter tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_reporter_append_replays_buffer_in_order() {
+        let mut buffer = Reporter::buffered();
+        buffer.warn("first");
+        buffer.fail_findings(
+            "Bad:",
+            [Finding::new(Location::file("a.md").with_line(1), "old")],
+        );
+        buffer.pass("last");
+        assert!(
+            buffer.diagnostics.is_empty(),
+            "buffer records nothing itself"
+        );
+
+        let baseline = baseline_of(&[(7, Some("a.md"), "Bad: old")]);
+        let mut rep = Reporter::new(OutputFormat::Json, false, false).with_baseline(baseline);
+        rep.section(7, "Check");
+        rep.append(buffer);
+
+        let messages: Vec<&str> = rep.diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(messages, vec!["first", "last"]);
+        assert!(rep.diagnostics.iter().all(|d| d.check_id == 7));
+        assert_eq!((rep.errors, rep.warnings, rep.suppressed), (0, 1, 1));
+    }
+
+    #[test]
+    fn test_reporter_without_baseline_has_no_summary() {
+        let rep = Reporter::new(OutputFormat::Json, false, false);
+        assert!(rep.baseline_summary().is_none());
+    }
+
+    // -------------------------------------------------------------------------
+    // Inline suppression tests
+    // -------------------------------------------------------------------------
+
+    fn suppressions_for(path: &str, content: &str) -> Suppressions {
+        let mut sup = Suppressions::default();
+        sup.add_file(path, content);
+        sup
+    }
+
+    #[test]
+    fn test_reporter_inline_directive_suppresses_finding() {
+        let sup = suppressions_for(
+            "a.md",
+            "<!-- pack-check-disable-next-line 7 -->\nbad\nbad\n",
+        );
+        let mut rep = Reporter::new(OutputFormat::Json, false, false).with_suppressions(sup);
+        rep.section(7, "Check");
+        rep.fail_findings(
+            "Bad:",
+            [
+                Finding::new(Location::file("a.md").with_line(2), "first"),
+                Finding::new(Location::file("a.md").with_line(3), "second"),
+            ],
+        );
+
+        assert_eq!(rep.errors, 1);
+        assert_eq!(rep.diagnostics.len(), 1);
+        assert_eq!(rep.diagnostics[0].message, "Bad: second");
+        // Inline suppressions are not baseline suppressions
+        assert_eq!(rep.suppressed, 0);
+    }
+
+    #[test]
+    fn test_reporter_reports_unused_directive() {
+        let sup = suppressions_for(
+            "a.md",
+            "<!-- pack-check-disable-next-line 7 -->\nbad\n<!-- pack-check-disable 8 -->\n",
+        );
+        let mut rep = Reporter::new(OutputFormat::Json, false, false).with_suppressions(sup);
+        assert!(rep.has_suppressions());
+        rep.section(7, "Check");
+        rep.fail_at(Location::file("a.md").with_line(2), "bad");
+        rep.section(8, "Other");
+        rep.pass("clean");
+
+        rep.section(63, "Checking suppression directives are used...");
+        rep.report_unused_suppressions(true);
+
+        assert_eq!(rep.errors, 0);
+        assert_eq!(rep.warnings, 1);
+        let unused = rep.diagnostics.last().unwrap();
+        assert_eq!(unused.check_id, 63);
+        assert!(
+            unused.message.contains("pack-check-disable 8"),
+            "{}",
+            unused.message
+        );
+        assert_eq!(unused.location.as_ref().unwrap().line, Some(3));
+    }
+
+    #[test]
+    fn test_reporter_unused_directive_ignored_when_check_did_not_run() {
+        let sup = suppressions_for("a.md", "<!-- pack-check-disable-next-line 7 -->\n");
+        let mut rep = Reporter::new(OutputFormat::Json, false, false).with_suppressions(sup);
+        rep.section(63, "Checking suppression directives are used...");
+        rep.report_unused_suppressions(false);
+
+        assert_eq!(rep.warnings, 0);
+    }
+
     // -------------------------------------------------------------------------
     // Reporter::colorize tests
     // -------------------------------------------------------------------------
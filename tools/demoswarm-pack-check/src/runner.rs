@@ -1,72 +1,259 @@
-//! Runner: orchestration only.
+//! Runner: the CLI around `PackChecker`.
 //!
-//! Build Ctx → Inventory → Contracts → Regexes, then run all checks, then report.
+//! Parse the flags into a checker, run it with a printing reporter, then add
+//! what only the CLI does: baselines, `--fix`, `--graph`, `--watch`, `--lsp`
+//! and the `docs generate` and `diff` subcommands. The `runs` subcommand is a
+//! checker run in runs mode.
 
+use std::path::Path;
 use std::process::ExitCode;
 
-use anyhow::Context;
+use anyhow::{Context, bail};
 
 use crate::{
-    checks::{self, CheckCtx},
-    cli::{Cli, OutputFormat},
-    contracts::{Contracts, Regexes},
-    ctx::Ctx,
-    inventory::Inventory,
-    reporter::{PackCounts, Reporter},
+    baseline::Baseline,
+    checker::PackChecker,
+    checks::{self, CheckSpec, FixSpec, HandoffGraph},
+    cli::{Cli, Command, DocsCommand, GraphFormat, OutputFormat},
+    diff,
+    fix::{self, FileFix},
+    lsp,
+    reporter::Reporter,
+    suppress::Suppressions,
+    watch,
 };
 
 pub fn run(cli: Cli) -> anyhow::Result<ExitCode> {
-    // Discover repo root
-    let ctx = Ctx::discover(cli.repo_root.clone()).context("discover repo root")?;
+    if let Some(Command::Docs(DocsCommand::Generate { check })) = cli.command {
+        if cli.git_ref.is_some() && !check {
+            bail!("`docs generate` writes the working tree; at --git-ref it needs --check");
+        }
+        return generate_docs(&cli, check);
+    }
+    if let Some(Command::Diff { from, to }) = &cli.command {
+        if cli.git_ref.is_some() {
+            bail!("`diff` takes its git revisions as --from and --to, not --git-ref");
+        }
+        if cli.fix || cli.graph.is_some() || cli.watch || cli.lsp {
+            bail!(
+                "`diff` compares two packs; it does not combine with --fix, --graph, --watch or --lsp"
+            );
+        }
+        return diff_packs(&cli, from, to);
+    }
+    if matches!(cli.command, Some(Command::Runs { .. }))
+        && (cli.fix || cli.graph.is_some() || cli.watch || cli.lsp)
+    {
+        bail!("`runs` checks .runs/; it does not combine with --fix, --graph, --watch or --lsp");
+    }
 
-    // Build inventory
-    let inv = Inventory::from_ctx(&ctx).context("build inventory")?;
+    // Select checks first, so a bad selection is a usage error before any output
+    let checks = checker(&cli).checks()?;
+
+    if let Some(format) = cli.graph {
+        return export_graph(&cli, format);
+    }
+
+    if cli.lsp {
+        return lsp::serve(checker(&cli), cli.baseline.as_deref());
+    }
+
+    if cli.watch {
+        return watch::watch(&checker(&cli), cli.baseline.as_deref());
+    }
 
-    // Build contracts
-    let contracts = Contracts::default();
+    // Rewrite first, so the checks below report on the fixed pack
+    if cli.fix {
+        fix_pack(&cli, &checks)?;
+        if cli.dry_run {
+            return Ok(ExitCode::SUCCESS);
+        }
+    }
 
-    // Compile regexes
-    let re = Regexes::compile().context("compile regexes")?;
+    // Load baseline (before any output, so a bad file is a usage error)
+    let baseline = match &cli.baseline {
+        Some(path) => Some(Baseline::load(path)?),
+        None => None,
+    };
 
     // Create reporter
     let mut rep = Reporter::new(cli.format, !cli.no_color, cli.strict_warnings);
-    if cli.format == OutputFormat::Text {
-        rep.print_banner();
+    if let Some(baseline) = baseline {
+        rep = rep.with_baseline(baseline);
+    }
+
+    let run = checker(&cli).execute(rep)?;
+    let (rep, repo_root, counts, checks) = (run.rep, run.repo_root, run.counts, run.checks);
+
+    // Record the current diagnostics as the accepted baseline
+    if let Some(path) = &cli.write_baseline {
+        let baseline = Baseline::from_diagnostics(rep.diagnostics());
+        baseline.write(path)?;
+        rep.finish(&repo_root, counts, &checks)?;
+        eprintln!(
+            "Wrote baseline with {} entr{} to {}",
+            baseline.len(),
+            if baseline.len() == 1 { "y" } else { "ies" },
+            path.display()
+        );
+        return Ok(ExitCode::SUCCESS);
     }
 
-    // Build check context
-    let check_ctx = CheckCtx {
-        ctx: &ctx,
-        inv: &inv,
-        re: &re,
-        c: &contracts,
+    rep.finish(&repo_root, counts, &checks)
+}
+
+/// The checker the command line asks for.
+fn checker(cli: &Cli) -> PackChecker {
+    let checker = match &cli.repo_root {
+        Some(root) => PackChecker::new(root),
+        None => PackChecker::from_cwd(),
+    }
+    .selection(cli.selection());
+    let checker = match &cli.docs_root {
+        Some(docs_root) => checker.docs_root(docs_root),
+        None => checker,
     };
+    let checker = match &cli.command {
+        Some(Command::Runs { run_id }) => checker.runs(run_id.clone()),
+        _ => checker,
+    };
+    let checker = match &cli.git_ref {
+        Some(rev) => checker.git_ref(rev),
+        None => checker,
+    };
+    match cli.jobs {
+        Some(jobs) => checker.jobs(jobs.get()),
+        None => checker,
+    }
+}
 
-    // Run all checks (keep-going: catch errors and continue)
-    for check in checks::all() {
-        rep.section(check.id, check.title);
-        if let Err(e) = (check.run)(&check_ctx, &mut rep) {
-            rep.fail(format!("check crashed: {e:#}"));
+/// `--fix`: rewrite what the selected checks' fixers can correct, then plan
+/// again on the written pack to confirm the rewrites converged.
+fn fix_pack(cli: &Cli, checks: &[CheckSpec]) -> anyhow::Result<()> {
+    let fixers: Vec<FixSpec> = checks::fixers()
+        .into_iter()
+        .filter(|f| checks.iter().any(|c| c.id == f.check_id))
+        .collect();
+
+    let fixes = plan_fixes(cli, &fixers)?;
+    if cli.dry_run {
+        for fix in &fixes {
+            print!("{}", fix.unified_diff());
         }
-        rep.blank_line();
+        eprintln!("{}", fix_summary("Would fix", &fixes));
+        return Ok(());
     }
 
-    // Summary
-    if cli.format == OutputFormat::Text {
-        rep.print_summary_header();
+    fix::apply(&fixes)?;
+
+    // Fresh context: file contents cached while planning are stale now
+    let remaining = plan_fixes(cli, &fixers)?;
+    if !remaining.is_empty() {
+        let files: Vec<&str> = remaining.iter().map(|f| f.rel.as_str()).collect();
+        bail!(
+            "--fix did not converge; still rewritable: {}",
+            files.join(", ")
+        );
     }
 
-    let counts = PackCounts {
-        agents: inv.agent_md_files.len(),
-        commands: inv.command_md_files.len(),
-        skills: inv.skill_md_files.len(),
-    };
+    eprintln!("{}", fix_summary("Fixed", &fixes));
+    Ok(())
+}
+
+fn plan_fixes(cli: &Cli, fixers: &[FixSpec]) -> anyhow::Result<Vec<FileFix>> {
+    let pack = checker(cli).load()?;
+    let sup = Suppressions::scan(&pack.ctx).context("scan suppression directives")?;
+    fix::plan(&pack.cx(), fixers, &sup).context("plan fixes")
+}
+
+/// `docs generate`: rewrite the generated sections of the reference docs, or
+/// with `--check` print what would change and fail if anything would.
+fn generate_docs(cli: &Cli, check: bool) -> anyhow::Result<ExitCode> {
+    let pack = checker(cli).load()?;
+    let docs_root = pack
+        .ctx
+        .repo_root
+        .join(cli.docs_root.as_deref().unwrap_or(Path::new("docs")));
+    let fixes = checks::generate_docs(&pack.cx(), &docs_root).context("generate docs")?;
+
+    if fixes.is_empty() {
+        eprintln!("Generated docs are up to date");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let files: Vec<&str> = fixes.iter().map(|f| f.rel.as_str()).collect();
+    if check {
+        for fix in &fixes {
+            print!("{}", fix.unified_diff());
+        }
+        eprintln!(
+            "Generated docs are out of date (run `pack-check docs generate`): {}",
+            files.join(", ")
+        );
+        return Ok(ExitCode::FAILURE);
+    }
+
+    std::fs::create_dir_all(docs_root.join("reference"))
+        .with_context(|| format!("create {}", docs_root.join("reference").display()))?;
+    fix::apply(&fixes)?;
+    eprintln!("Generated {}", files.join(", "));
+    Ok(ExitCode::SUCCESS)
+}
+
+/// `diff`: print what changed in contract terms from one pack to another.
+fn diff_packs(cli: &Cli, from: &str, to: &str) -> anyhow::Result<ExitCode> {
+    if cli.format == OutputFormat::Sarif {
+        bail!("`diff` prints text or json, not sarif");
+    }
+    let old = pack_at(cli, from)
+        .load()
+        .with_context(|| format!("load {from}"))?;
+    let new = pack_at(cli, to)
+        .load()
+        .with_context(|| format!("load {to}"))?;
+    let diff = diff::diff_packs(from, &old.cx(), to, &new.cx())?;
+
+    match cli.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+        _ => print!("{}", diff.render_text()),
+    }
+    Ok(ExitCode::SUCCESS)
+}
 
-    if cli.format == OutputFormat::Text {
-        rep.print_counts(&counts);
+/// A checker for the pack at `source`: a directory containing `.claude/`, or
+/// else the pack at --repo-root (as `--git-ref` reads it) at git revision `source`.
+fn pack_at(cli: &Cli, source: &str) -> PackChecker {
+    if Path::new(source).is_dir() {
+        return PackChecker::new(source);
+    }
+    match &cli.repo_root {
+        Some(root) => PackChecker::new(root),
+        None => PackChecker::from_cwd(),
     }
+    .git_ref(source)
+}
 
-    rep.finish(&ctx.repo_root.display().to_string(), counts)
+/// `--graph`: print the handoff graph instead of running the checks.
+fn export_graph(cli: &Cli, format: GraphFormat) -> anyhow::Result<ExitCode> {
+    let pack = checker(cli).load()?;
+    let graph = HandoffGraph::build(&pack.cx()).context("build handoff graph")?;
+    print!("{}", graph.render(format)?);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn fix_summary(verb: &str, fixes: &[FileFix]) -> String {
+    let edits: usize = fixes.iter().map(|f| f.edits.len()).sum();
+    let mut out = format!(
+        "{verb} {edits} line{} in {} file{}",
+        if edits == 1 { "" } else { "s" },
+        fixes.len(),
+        if fixes.len() == 1 { "" } else { "s" }
+    );
+    for fix in fixes {
+        let ids: Vec<String> = fix.check_ids().iter().map(|id| id.to_string()).collect();
+        out.push_str(&format!("\n  {} (checks {})", fix.rel, ids.join(", ")));
+    }
+    out
 }
 
 // =============================================================================
@@ -76,6 +263,10 @@ pub fn run(cli: Cli) -> anyhow::Result<ExitCode> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cli::OutputFormat;
+    use crate::ctx::Ctx;
+    use crate::inventory::Inventory;
+    use crate::reporter::PackCounts;
     use std::path::PathBuf;
     use tempfile::TempDir;
 
@@ -90,6 +281,7 @@ mod tests {
             format: OutputFormat::Text,
             no_color: false,
             strict_warnings: false,
+            ..Default::default()
         };
 
         assert!(cli.repo_root.is_none());
@@ -105,6 +297,7 @@ mod tests {
             format: OutputFormat::Json,
             no_color: true,
             strict_warnings: true,
+            ..Default::default()
         };
 
         assert_eq!(cli.repo_root, Some(PathBuf::from("/test/path")));
@@ -113,6 +306,196 @@ mod tests {
         assert!(cli.strict_warnings);
     }
 
+    #[test]
+    fn test_cli_parses_selection_flags() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from([
+            "pack-check",
+            "--only",
+            "7,52",
+            "--skip",
+            "53",
+            "--category",
+            "drift,control_plane",
+            "--skip-category",
+            "wisdom",
+        ])
+        .unwrap();
+
+        let sel = cli.selection();
+        assert_eq!(sel.only, vec![7, 52]);
+        assert_eq!(sel.skip, vec![53]);
+        assert_eq!(
+            sel.categories,
+            vec![crate::Category::Drift, crate::Category::ControlPlane]
+        );
+        assert_eq!(sel.skip_categories, vec![crate::Category::Wisdom]);
+    }
+
+    #[test]
+    fn test_cli_dry_run_requires_fix() {
+        use clap::Parser;
+
+        assert!(Cli::try_parse_from(["pack-check", "--dry-run"]).is_err());
+        let cli = Cli::try_parse_from(["pack-check", "--fix", "--dry-run"]).unwrap();
+        assert!(cli.fix && cli.dry_run);
+    }
+
+    #[test]
+    fn test_cli_rejects_unknown_category() {
+        use clap::Parser;
+
+        assert!(Cli::try_parse_from(["pack-check", "--category", "nope"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_docs_generate() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from([
+            "pack-check",
+            "docs",
+            "generate",
+            "--check",
+            "--repo-root",
+            "pack",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Docs(DocsCommand::Generate { check: true }))
+        );
+        assert_eq!(cli.repo_root, Some(PathBuf::from("pack")));
+        assert!(Cli::try_parse_from(["pack-check", "docs"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_runs_with_check_flags() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from([
+            "pack-check",
+            "runs",
+            "--run-id",
+            "feat-a",
+            "--only",
+            "77,78",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Runs {
+                run_id: Some("feat-a".to_string())
+            })
+        );
+        assert_eq!(cli.only, vec![77, 78]);
+        assert_eq!(cli.format, OutputFormat::Json);
+
+        let fix = Cli::try_parse_from(["pack-check", "--fix", "runs"]).unwrap();
+        assert!(run(fix).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_diff() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from([
+            "pack-check",
+            "diff",
+            "--from",
+            "upstream/main",
+            "--to",
+            "../fork",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Diff {
+                from: "upstream/main".to_string(),
+                to: "../fork".to_string(),
+            })
+        );
+        assert_eq!(cli.format, OutputFormat::Json);
+        assert!(Cli::try_parse_from(["pack-check", "diff", "--from", "a"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_git_ref() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(["pack-check", "runs", "--git-ref", "origin/main"]).unwrap();
+        assert_eq!(cli.git_ref.as_deref(), Some("origin/main"));
+        assert!(Cli::try_parse_from(["pack-check", "--git-ref", "HEAD", "--fix"]).is_err());
+        assert!(Cli::try_parse_from(["pack-check", "--git-ref", "HEAD", "--watch"]).is_err());
+
+        for args in [
+            &["pack-check", "docs", "generate", "--git-ref", "HEAD"][..],
+            &[
+                "pack-check",
+                "diff",
+                "--from",
+                "a",
+                "--to",
+                "b",
+                "--git-ref",
+                "HEAD",
+            ],
+        ] {
+            assert!(run(Cli::try_parse_from(args).unwrap()).is_err());
+        }
+    }
+
+    /// Test that `docs generate --check` fails until `docs generate` has written the docs.
+    #[test]
+    fn test_run_docs_generate() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".claude/agents")).unwrap();
+        std::fs::write(tmp.path().join(".claude/agents/a.md"), "# A\n").unwrap();
+        let cli = |check| Cli {
+            command: Some(Command::Docs(DocsCommand::Generate { check })),
+            repo_root: Some(tmp.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        assert_eq!(run(cli(true)).unwrap(), ExitCode::FAILURE);
+        assert!(!tmp.path().join("docs").exists());
+        assert_eq!(run(cli(false)).unwrap(), ExitCode::SUCCESS);
+        assert!(tmp.path().join("docs/reference/agents-index.md").is_file());
+        assert_eq!(run(cli(true)).unwrap(), ExitCode::SUCCESS);
+    }
+
+    /// Test that `diff` compares a git revision with a directory, and rejects
+    /// a source that is neither.
+    #[test]
+    fn test_run_diff() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        crate::files::test_utils::commit_files(root, &[(".claude/agents/a.md", "# A\n")]);
+        std::fs::write(root.join(".claude/agents/b.md"), "# B\n").unwrap();
+
+        let cli = |from: &str, format| Cli {
+            command: Some(Command::Diff {
+                from: from.to_string(),
+                to: root.display().to_string(),
+            }),
+            repo_root: Some(root.to_path_buf()),
+            format,
+            ..Default::default()
+        };
+        assert_eq!(
+            run(cli("HEAD", OutputFormat::Json)).unwrap(),
+            ExitCode::SUCCESS
+        );
+        assert!(run(cli("HEAD", OutputFormat::Sarif)).is_err());
+        let err = run(cli("no-such-ref", OutputFormat::Text)).unwrap_err();
+        assert!(format!("{err:#}").contains("unknown git revision `no-such-ref`"));
+    }
+
     // -------------------------------------------------------------------------
     // run() integration tests with minimal .claude directory
     // -------------------------------------------------------------------------
@@ -127,6 +510,7 @@ mod tests {
             format: OutputFormat::Json,
             no_color: true,
             strict_warnings: false,
+            ..Default::default()
         };
 
         let result = run(cli);
@@ -157,6 +541,7 @@ mod tests {
             format: OutputFormat::Json,
             no_color: true,
             strict_warnings: false,
+            ..Default::default()
         };
 
         // Should not panic or error - will have many warnings/errors but should complete
@@ -181,6 +566,7 @@ mod tests {
             format: OutputFormat::Text,
             no_color: true,
             strict_warnings: false,
+            ..Default::default()
         };
 
         // Should not panic or error
@@ -188,6 +574,30 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    /// Test that run() executes with minimal .claude directory (SARIF mode).
+    #[test]
+    fn test_run_with_minimal_claude_dir_sarif() {
+        let tmp = TempDir::new().unwrap();
+
+        // Create minimal .claude directory structure
+        let claude_dir = tmp.path().join(".claude");
+        std::fs::create_dir(&claude_dir).unwrap();
+        std::fs::create_dir(claude_dir.join("agents")).unwrap();
+        std::fs::create_dir(claude_dir.join("commands")).unwrap();
+        std::fs::create_dir(claude_dir.join("skills")).unwrap();
+
+        let cli = Cli {
+            repo_root: Some(tmp.path().to_path_buf()),
+            format: OutputFormat::Sarif,
+            no_color: true,
+            strict_warnings: false,
+            ..Default::default()
+        };
+
+        let result = run(cli);
+        assert!(result.is_ok());
+    }
+
     /// Test run() with strict_warnings mode.
     #[test]
     fn test_run_with_strict_warnings() {
@@ -205,6 +615,7 @@ mod tests {
             format: OutputFormat::Json,
             no_color: true,
             strict_warnings: true,
+            ..Default::default()
         };
 
         // Should complete, likely with non-zero exit code due to missing required files
@@ -212,6 +623,149 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    /// Test that run() rejects an unknown check id before running anything.
+    #[test]
+    fn test_run_with_unknown_check_id_fails() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join(".claude")).unwrap();
+
+        let cli = Cli {
+            repo_root: Some(tmp.path().to_path_buf()),
+            format: OutputFormat::Json,
+            no_color: true,
+            strict_warnings: false,
+            only: vec![999],
+            ..Default::default()
+        };
+
+        let err = run(cli).unwrap_err();
+        assert!(format!("{err:#}").contains("unknown check id: 999"));
+    }
+
+    /// Test that run() executes a single selected check.
+    #[test]
+    fn test_run_with_only_selection() {
+        let tmp = TempDir::new().unwrap();
+        let claude_dir = tmp.path().join(".claude");
+        std::fs::create_dir(&claude_dir).unwrap();
+        std::fs::create_dir(claude_dir.join("agents")).unwrap();
+        std::fs::create_dir(claude_dir.join("commands")).unwrap();
+        std::fs::create_dir(claude_dir.join("skills")).unwrap();
+
+        let cli = Cli {
+            repo_root: Some(tmp.path().to_path_buf()),
+            format: OutputFormat::Json,
+            no_color: true,
+            strict_warnings: false,
+            only: vec![10],
+            ..Default::default()
+        };
+
+        assert!(run(cli).is_ok());
+    }
+
+    /// Test that a freshly written baseline makes the next run pass.
+    #[test]
+    fn test_run_write_baseline_then_apply() {
+        let tmp = TempDir::new().unwrap();
+        let claude_dir = tmp.path().join(".claude");
+        std::fs::create_dir(&claude_dir).unwrap();
+        std::fs::create_dir(claude_dir.join("agents")).unwrap();
+        std::fs::create_dir(claude_dir.join("commands")).unwrap();
+        std::fs::create_dir(claude_dir.join("skills")).unwrap();
+        let baseline_path = tmp.path().join("pack-check-baseline.json");
+
+        // The minimal pack fails (missing required agents)...
+        let cli = Cli {
+            repo_root: Some(tmp.path().to_path_buf()),
+            format: OutputFormat::Json,
+            no_color: true,
+            strict_warnings: true,
+            ..Default::default()
+        };
+        assert_eq!(run(cli.clone()).unwrap(), ExitCode::from(1));
+
+        // ...writing a baseline always exits 0...
+        let write = Cli {
+            write_baseline: Some(baseline_path.clone()),
+            ..cli.clone()
+        };
+        assert_eq!(run(write).unwrap(), ExitCode::SUCCESS);
+        assert!(baseline_path.is_file());
+
+        // ...and with the baseline applied nothing new is reported.
+        let apply = Cli {
+            baseline: Some(baseline_path),
+            ..cli
+        };
+        assert_eq!(run(apply).unwrap(), ExitCode::SUCCESS);
+    }
+
+    /// Test that --fix --dry-run leaves the pack untouched and --fix rewrites it.
+    #[test]
+    fn test_run_fix_and_dry_run() {
+        let tmp = TempDir::new().unwrap();
+        let agents = tmp.path().join(".claude").join("agents");
+        std::fs::create_dir_all(&agents).unwrap();
+        let agent = agents.join("a.md");
+        std::fs::write(&agent, "Traces FR-001\n").unwrap();
+
+        let dry = Cli {
+            repo_root: Some(tmp.path().to_path_buf()),
+            format: OutputFormat::Json,
+            only: vec![7],
+            fix: true,
+            dry_run: true,
+            ..Default::default()
+        };
+        assert_eq!(run(dry.clone()).unwrap(), ExitCode::SUCCESS);
+        assert_eq!(std::fs::read_to_string(&agent).unwrap(), "Traces FR-001\n");
+
+        let fix = Cli {
+            dry_run: false,
+            ..dry
+        };
+        assert_eq!(run(fix).unwrap(), ExitCode::SUCCESS);
+        assert_eq!(std::fs::read_to_string(&agent).unwrap(), "Traces REQ-001\n");
+    }
+
+    /// Test that inline directives are scanned and reported on without error.
+    #[test]
+    fn test_run_with_suppression_directives() {
+        let tmp = TempDir::new().unwrap();
+        let claude_dir = tmp.path().join(".claude");
+        std::fs::create_dir_all(claude_dir.join("agents")).unwrap();
+        std::fs::write(
+            claude_dir.join("agents").join("a.md"),
+            "<!-- pack-check-disable-next-line 1 -->\nnothing to see\n",
+        )
+        .unwrap();
+
+        let cli = Cli {
+            repo_root: Some(tmp.path().to_path_buf()),
+            format: OutputFormat::Json,
+            ..Default::default()
+        };
+
+        assert!(run(cli).is_ok());
+    }
+
+    /// Test that a missing baseline file is an error.
+    #[test]
+    fn test_run_with_missing_baseline_fails() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join(".claude")).unwrap();
+
+        let cli = Cli {
+            repo_root: Some(tmp.path().to_path_buf()),
+            format: OutputFormat::Json,
+            baseline: Some(tmp.path().join("nope.json")),
+            ..Default::default()
+        };
+
+        assert!(run(cli).is_err());
+    }
+
     /// Test that run() handles path canonicalization errors.
     #[test]
     fn test_run_with_nonexistent_path() {
@@ -220,6 +774,7 @@ mod tests {
             format: OutputFormat::Json,
             no_color: true,
             strict_warnings: false,
+            ..Default::default()
         };
 
         let result = run(cli);
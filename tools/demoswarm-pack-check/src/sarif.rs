@@ -0,0 +1,365 @@
+//! SARIF 2.1.0 output.
+//!
+//! Maps the check registry onto SARIF rules and each non-pass diagnostic onto
+//! a result, so pack-check findings can be uploaded to code-scanning tools.
+//! Only the subset of the schema that pack-check can fill is modelled here.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::checks::CheckSpec;
+use crate::reporter::{Diagnostic, Level, Location};
+
+pub const SARIF_VERSION: &str = "2.1.0";
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Base id that result URIs are relative to (the repo root).
+const SRCROOT: &str = "%SRCROOT%";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<Run>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Run {
+    pub tool: Tool,
+    pub original_uri_base_ids: HashMap<&'static str, ArtifactLocation>,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Tool {
+    pub driver: Driver,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Driver {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    pub id: String,
+    pub short_description: Message,
+    pub properties: RuleProperties,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleProperties {
+    pub check_id: u32,
+    pub category: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Message {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: &'static str,
+    pub message: Message,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<ResultLocation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultLocation {
+    pub physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<Region>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactLocation {
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri_base_id: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    pub start_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+}
+
+/// Stable SARIF rule id for a check number (e.g. 7 -> "PC007").
+pub fn rule_id(check_id: u32) -> String {
+    format!("PC{check_id:03}")
+}
+
+/// Build a SARIF log from the check registry and the collected diagnostics.
+///
+/// Pass-level diagnostics are dropped; everything else becomes a result.
+pub fn build_log(repo_root: &str, checks: &[CheckSpec], diagnostics: &[Diagnostic]) -> SarifLog {
+    let mut rules: Vec<Rule> = Vec::new();
+    let mut rule_index: HashMap<u32, usize> = HashMap::new();
+
+    for check in checks {
+        if rule_index.contains_key(&check.id) {
+            continue;
+        }
+        rule_index.insert(check.id, rules.len());
+        rules.push(Rule {
+            id: rule_id(check.id),
+            short_description: Message {
+                text: check.title.trim_end_matches("...").to_string(),
+            },
+            properties: RuleProperties {
+                check_id: check.id,
+                category: check.category.as_str(),
+            },
+        });
+    }
+
+    let results = diagnostics
+        .iter()
+        .filter(|d| d.level != Level::Pass)
+        .map(|d| {
+            // Diagnostics from checks outside the registry (unit tests) still
+            // get a rule so every result is resolvable.
+            let index = *rule_index.entry(d.check_id).or_insert_with(|| {
+                rules.push(Rule {
+                    id: rule_id(d.check_id),
+                    short_description: Message {
+                        text: d.check_title.trim_end_matches("...").to_string(),
+                    },
+                    properties: RuleProperties {
+                        check_id: d.check_id,
+                        category: "uncategorized",
+                    },
+                });
+                rules.len() - 1
+            });
+
+            SarifResult {
+                rule_id: rule_id(d.check_id),
+                rule_index: index,
+                level: sarif_level(d.level),
+                message: Message {
+                    text: d.message.clone(),
+                },
+                locations: d.location.iter().map(result_location).collect(),
+            }
+        })
+        .collect();
+
+    let mut base_ids = HashMap::new();
+    base_ids.insert(
+        SRCROOT,
+        ArtifactLocation {
+            uri: root_uri(repo_root),
+            uri_base_id: None,
+        },
+    );
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![Run {
+            tool: Tool {
+                driver: Driver {
+                    name: "pack-check",
+                    version: env!("CARGO_PKG_VERSION"),
+                    rules,
+                },
+            },
+            original_uri_base_ids: base_ids,
+            results,
+        }],
+    }
+}
+
+fn sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Pass => "none",
+        Level::Warn => "warning",
+        Level::Fail => "error",
+    }
+}
+
+fn result_location(loc: &Location) -> ResultLocation {
+    ResultLocation {
+        physical_location: PhysicalLocation {
+            artifact_location: ArtifactLocation {
+                uri: loc.path.replace('\\', "/"),
+                uri_base_id: Some(SRCROOT),
+            },
+            region: loc.line.map(|start_line| Region {
+                start_line,
+                start_column: loc.column,
+                end_line: loc.end_line,
+            }),
+        },
+    }
+}
+
+/// `file://` URI for the repo root, with the trailing slash SARIF requires.
+fn root_uri(repo_root: &str) -> String {
+    let mut path = repo_root.replace('\\', "/");
+    if !path.starts_with('/') {
+        path.insert(0, '/');
+    }
+    if !path.ends_with('/') {
+        path.push('/');
+    }
+    format!("file://{path}")
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::{Category, CheckCtx};
+    use crate::reporter::Reporter;
+
+    fn noop(_: &CheckCtx, _: &mut Reporter) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn spec(id: u32, title: &'static str, category: Category) -> CheckSpec {
+        CheckSpec {
+            id,
+            title,
+            category,
+            rationale: "",
+            run: noop,
+        }
+    }
+
+    fn diag(level: Level, check_id: u32, location: Option<Location>) -> Diagnostic {
+        Diagnostic {
+            level,
+            check_id,
+            check_title: "Title".to_string(),
+            message: "Something is off".to_string(),
+            location,
+        }
+    }
+
+    #[test]
+    fn test_rule_id_is_zero_padded() {
+        assert_eq!(rule_id(7), "PC007");
+        assert_eq!(rule_id(53), "PC053");
+    }
+
+    #[test]
+    fn test_build_log_rules_carry_title_and_category() {
+        let checks = vec![
+            spec(1, "Checking required agents...", Category::Structure),
+            spec(7, "Checking for raw git...", Category::Drift),
+        ];
+        let log = build_log("/repo", &checks, &[]);
+
+        let rules = &log.runs[0].tool.driver.rules;
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].id, "PC001");
+        assert_eq!(rules[0].short_description.text, "Checking required agents");
+        assert_eq!(rules[1].properties.category, "drift");
+        assert!(log.runs[0].results.is_empty());
+    }
+
+    #[test]
+    fn test_build_log_maps_levels_and_skips_pass() {
+        let checks = vec![spec(4, "Check", Category::ControlPlane)];
+        let diags = vec![
+            diag(Level::Pass, 4, None),
+            diag(Level::Warn, 4, None),
+            diag(Level::Fail, 4, None),
+        ];
+        let log = build_log("/repo", &checks, &diags);
+
+        let results = &log.runs[0].results;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].level, "warning");
+        assert_eq!(results[1].level, "error");
+        assert_eq!(results[1].rule_id, "PC004");
+        assert_eq!(results[1].rule_index, 0);
+    }
+
+    #[test]
+    fn test_build_log_physical_location() {
+        let checks = vec![spec(7, "Check", Category::Drift)];
+        let loc = Location::file(".claude/agents/a.md")
+            .with_line(12)
+            .with_column(3);
+        let log = build_log("/repo", &checks, &[diag(Level::Fail, 7, Some(loc))]);
+
+        let json = serde_json::to_value(&log).unwrap();
+        let phys = &json["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+        assert_eq!(phys["artifactLocation"]["uri"], ".claude/agents/a.md");
+        assert_eq!(phys["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+        assert_eq!(phys["region"]["startLine"], 12);
+        assert_eq!(phys["region"]["startColumn"], 3);
+        assert!(phys["region"].get("endLine").is_none());
+    }
+
+    #[test]
+    fn test_build_log_file_only_location_has_no_region() {
+        let checks = vec![spec(4, "Check", Category::ControlPlane)];
+        let loc = Location::file("CLAUDE.md");
+        let log = build_log("/repo", &checks, &[diag(Level::Warn, 4, Some(loc))]);
+
+        let json = serde_json::to_value(&log).unwrap();
+        let phys = &json["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+        assert!(phys.get("region").is_none());
+    }
+
+    #[test]
+    fn test_build_log_unknown_check_gets_rule() {
+        let log = build_log("/repo", &[], &[diag(Level::Fail, 99, None)]);
+
+        let run = &log.runs[0];
+        assert_eq!(run.tool.driver.rules.len(), 1);
+        assert_eq!(run.tool.driver.rules[0].id, "PC099");
+        assert_eq!(run.results[0].rule_index, 0);
+    }
+
+    #[test]
+    fn test_build_log_envelope() {
+        let log = build_log("/repo", &[], &[]);
+        let json = serde_json::to_value(&log).unwrap();
+
+        assert_eq!(json["version"], "2.1.0");
+        assert_eq!(json["$schema"], SARIF_SCHEMA);
+        assert_eq!(json["runs"][0]["tool"]["driver"]["name"], "pack-check");
+        assert_eq!(
+            json["runs"][0]["originalUriBaseIds"]["%SRCROOT%"]["uri"],
+            "file:///repo/"
+        );
+    }
+
+    #[test]
+    fn test_root_uri_windows_path() {
+        assert_eq!(root_uri(r"C:\work\pack"), "file:///C:/work/pack/");
+    }
+}
@@ -0,0 +1,127 @@
+//! A loaded pack whose checks re-run as its files change (`--watch`, `--lsp`).
+//!
+//! Every check's reads are traced (`Ctx::traced`), so a changed file re-runs
+//! the checks that read it. Adding or removing a file changes the inventory,
+//! and changing the contracts file changes every check's input; both re-run
+//! all selected checks.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::{
+    baseline::Baseline,
+    checker::{self, Pack, PackChecker},
+    checks::CheckSpec,
+    contracts_file,
+    ctx::Reads,
+    inventory::Inventory,
+    reporter::{Diagnostic, Level, Reporter},
+    suppress::Suppressions,
+};
+
+pub(crate) struct Session<'a> {
+    checker: &'a PackChecker,
+    baseline: Option<&'a Path>,
+    checks: Vec<CheckSpec>,
+    pack: Pack,
+    /// Per check (same order as `checks`): its current diagnostics and reads.
+    state: Vec<(Vec<Diagnostic>, Reads)>,
+}
+
+impl<'a> Session<'a> {
+    /// Load the pack and run every selected check once.
+    pub fn start(checker: &'a PackChecker, baseline: Option<&'a Path>) -> anyhow::Result<Self> {
+        let checks = checker.checks()?;
+        let pack = checker.load()?;
+        let state = checks.iter().map(|_| Default::default()).collect();
+
+        let mut session = Self {
+            checker,
+            baseline,
+            checks,
+            pack,
+            state,
+        };
+        session.rerun(&session.all())?;
+        Ok(session)
+    }
+
+    pub fn pack(&self) -> &Pack {
+        &self.pack
+    }
+
+    /// How many checks are selected.
+    pub fn total(&self) -> usize {
+        self.checks.len()
+    }
+
+    /// The current warnings and failures, in check order.
+    pub fn diagnostics(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.state.iter().flat_map(|(diags, _)| diags)
+    }
+
+    /// Re-run the checks that a change to `paths` can affect; `tree_changed`
+    /// if files were added or removed. Returns how many checks ran.
+    pub fn changed(&mut self, paths: &[PathBuf], tree_changed: bool) -> anyhow::Result<usize> {
+        let contracts_changed = paths.iter().any(|p| {
+            p.parent() == Some(self.pack.ctx.claude_dir.as_path())
+                && p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| contracts_file::FILE_NAMES.contains(&n))
+        });
+
+        let affected = if contracts_changed {
+            self.pack = self.checker.load()?;
+            self.all()
+        } else {
+            self.pack.ctx.invalidate(paths, tree_changed);
+            if tree_changed {
+                self.pack.inv = Inventory::from_ctx(&self.pack.ctx).context("build inventory")?;
+                self.all()
+            } else {
+                self.state
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, reads))| paths.iter().any(|p| reads.touches(p)))
+                    .map(|(i, _)| i)
+                    .collect()
+            }
+        };
+
+        self.rerun(&affected)?;
+        Ok(affected.len())
+    }
+
+    fn all(&self) -> Vec<usize> {
+        (0..self.checks.len()).collect()
+    }
+
+    /// Run `indices` of `checks` and replace their diagnostics and reads.
+    fn rerun(&mut self, indices: &[usize]) -> anyhow::Result<()> {
+        let selected: Vec<CheckSpec> = indices.iter().map(|&i| self.checks[i].clone()).collect();
+        let outcomes = checker::run_parallel(&self.pack.cx(), &selected, self.checker.threads());
+
+        // Suppressions and the baseline apply as in a normal run.
+        let mut rep = Reporter::silent().with_suppressions(
+            Suppressions::scan(&self.pack.ctx).context("scan suppression directives")?,
+        );
+        if let Some(path) = self.baseline {
+            rep = rep.with_baseline(Baseline::load(path)?);
+        }
+
+        for (&i, mut outcome) in indices.iter().zip(outcomes) {
+            let check = &self.checks[i];
+            rep.section(check.id, check.title);
+            let start = rep.diagnostics().len();
+            self.state[i].1 = std::mem::take(&mut outcome.reads);
+            outcome.report(&mut rep);
+            self.state[i].0 = rep.diagnostics()[start..]
+                .iter()
+                .filter(|d| d.level != Level::Pass)
+                .cloned()
+                .collect();
+        }
+        Ok(())
+    }
+}
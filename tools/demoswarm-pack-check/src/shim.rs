@@ -0,0 +1,379 @@
+//! `demoswarm.sh` invocations in pack markdown, checked against the real CLI.
+//!
+//! The shim forwards its arguments to the `demoswarm` binary, so every
+//! invocation an agent or skill documents must parse with the binary's clap
+//! grammar (`demoswarm_runs_tools::commands::Cli`). Invocations are taken from
+//! shell code blocks, joined across `\` line continuations, and cut at the
+//! first shell operator (`|`, `;`, `&`, `>`, `)`, ...).
+//!
+//! Docs use placeholders (`<path>`, `"$RUN_DIR"`, `...`); a parse error caused
+//! by one is not reported. A `[--flag]` token marks a usage synopsis, which is
+//! skipped entirely.
+
+use std::sync::LazyLock;
+
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::{ColorChoice, CommandFactory};
+use demoswarm_runs_tools::commands::Cli;
+use regex::Regex;
+
+use crate::markdown::{Markdown, Scope};
+
+static SHIM: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"demoswarm\.sh").expect("valid shim regex"));
+
+/// One shim call found in a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    /// 1-based line and column of `demoswarm.sh`.
+    pub line: usize,
+    pub column: usize,
+    /// Arguments after `demoswarm.sh`, shell quoting removed.
+    pub args: Vec<String>,
+}
+
+impl Invocation {
+    /// The call as it would be typed (`demoswarm.sh count pattern --file x`).
+    pub fn display(&self) -> String {
+        let mut out = String::from("demoswarm.sh");
+        for arg in &self.args {
+            out.push(' ');
+            if arg.is_empty() || arg.contains(char::is_whitespace) {
+                out.push_str(&format!("\"{arg}\""));
+            } else {
+                out.push_str(arg);
+            }
+        }
+        out
+    }
+}
+
+/// Every shim invocation in the `scope` lines of `md`. Bare references to the
+/// shim (no arguments) are not invocations.
+pub fn extract(md: &Markdown, scope: Scope) -> Vec<Invocation> {
+    let lines: Vec<(usize, &str)> = md.lines(scope).collect();
+    let mut out = Vec::new();
+
+    for (idx, (line_no, line)) in lines.iter().enumerate() {
+        for m in SHIM.find_iter(line) {
+            let rest = &line[m.end()..];
+            // `demoswarm.sh"` or `demoswarm.sh)` is a path mention, not a call.
+            if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+                continue;
+            }
+
+            // Join `\` continuations, staying inside the scanned block.
+            let mut text = rest.to_string();
+            let mut prev = *line_no;
+            for (next_no, next) in &lines[idx + 1..] {
+                let Some(head) = text.trim_end().strip_suffix('\\') else {
+                    break;
+                };
+                if *next_no != prev + 1 {
+                    break;
+                }
+                text = format!("{head} {}", next.trim_start());
+                prev = *next_no;
+            }
+
+            let args = tokenize(&text);
+            if args.is_empty() || args.iter().any(|a| a.starts_with('[')) {
+                continue;
+            }
+            out.push(Invocation {
+                line: *line_no,
+                column: line[..m.start()].chars().count() + 1,
+                args,
+            });
+        }
+    }
+
+    out
+}
+
+/// Split a shell command tail into words, stopping at the first operator.
+pub fn tokenize(s: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let mut in_word = false;
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    cur.push(c);
+                }
+            }
+            '"' => {
+                in_word = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' if matches!(chars.peek(), Some('"' | '\\' | '$' | '`')) => {
+                            cur.push(chars.next().expect("peeked"));
+                        }
+                        _ => cur.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    cur.push(next);
+                    in_word = true;
+                }
+            }
+            // Command substitution: keep it as (part of) one word.
+            '$' if chars.peek() == Some(&'(') => {
+                in_word = true;
+                cur.push('$');
+                let mut depth = 0usize;
+                for c in chars.by_ref() {
+                    cur.push(c);
+                    match c {
+                        '(' => depth += 1,
+                        ')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    out.push(std::mem::take(&mut cur));
+                    in_word = false;
+                }
+            }
+            '|' | ';' | '&' | ')' | '`' => break,
+            // `<path>` in docs is a placeholder word, not a redirection.
+            '<' if placeholder_len(chars.clone()).is_some() => {
+                let len = placeholder_len(chars.clone()).expect("checked");
+                cur.push('<');
+                cur.extend(chars.by_ref().take(len));
+                in_word = true;
+            }
+            '<' | '>' => {
+                // `2>/dev/null`: the fd number belongs to the redirection.
+                if in_word && cur.chars().all(|c| c.is_ascii_digit()) {
+                    in_word = false;
+                }
+                break;
+            }
+            '#' if !in_word => break,
+            _ => {
+                cur.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if in_word {
+        out.push(cur);
+    }
+    out
+}
+
+/// Length of `name>` after a `<`, if the `<` opens a `<name>` placeholder.
+fn placeholder_len(rest: impl Iterator<Item = char>) -> Option<usize> {
+    let mut len = 0;
+    for c in rest {
+        len += 1;
+        match c {
+            '>' if len > 1 => return Some(len),
+            c if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') => {}
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Parse `args` with the demoswarm CLI grammar; `Err` holds clap's message.
+pub fn validate(args: &[String]) -> Result<(), String> {
+    let cmd = Cli::command().color(ColorChoice::Never);
+    let argv = std::iter::once("demoswarm").chain(args.iter().map(String::as_str));
+
+    let err = match cmd.try_get_matches_from(argv) {
+        Ok(_) => return Ok(()),
+        Err(err) => err,
+    };
+
+    match err.kind() {
+        ErrorKind::DisplayHelp | ErrorKind::DisplayVersion => return Ok(()),
+        _ => {}
+    }
+    let offending = [
+        ContextKind::InvalidArg,
+        ContextKind::InvalidValue,
+        ContextKind::InvalidSubcommand,
+    ]
+    .into_iter()
+    .find_map(|kind| match err.get(kind) {
+        Some(ContextValue::String(s)) => Some(s.clone()),
+        _ => None,
+    });
+    if offending.as_deref().is_some_and(is_placeholder) {
+        return Ok(());
+    }
+
+    Err(summarize(&err.render().to_string()))
+}
+
+/// Doc placeholders that stand in for a real value.
+fn is_placeholder(s: &str) -> bool {
+    let s = s.trim();
+    s == "..."
+        || s == "…"
+        || s.contains('$')
+        || s.contains('{')
+        || (s.starts_with('<') && s.ends_with('>'))
+}
+
+/// First paragraph of a clap error (without `error: `), plus its tip if any.
+fn summarize(rendered: &str) -> String {
+    let mut lines = rendered.lines();
+    let mut msg = lines
+        .by_ref()
+        .take_while(|l| !l.trim().is_empty())
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_start_matches("error: ")
+        .to_string();
+    if let Some(tip) = lines.find_map(|l| l.trim().strip_prefix("tip: ")) {
+        msg.push_str(&format!("; {tip}"));
+    }
+    msg
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::SHELL_LANGS;
+
+    fn args(s: &str) -> Vec<String> {
+        tokenize(s)
+    }
+
+    #[test]
+    fn test_tokenize_quotes_and_operators() {
+        assert_eq!(
+            args(r#" count pattern --file "a b.md" --regex '^- \[' | head"#),
+            vec!["count", "pattern", "--file", "a b.md", "--regex", r"^- \["]
+        );
+        assert_eq!(args(" time now 2>/dev/null"), vec!["time", "now"]);
+        assert_eq!(args(" time now < in.txt"), vec!["time", "now"]);
+        assert_eq!(
+            args(" count bdd --dir <path> --x .runs/<run-id>/plan"),
+            vec![
+                "count",
+                "bdd",
+                "--dir",
+                "<path>",
+                "--x",
+                ".runs/<run-id>/plan"
+            ]
+        );
+        assert_eq!(args(" time now # comment"), vec!["time", "now"]);
+        assert_eq!(args(" time now; echo x"), vec!["time", "now"]);
+        assert_eq!(args(""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_tokenize_command_substitution_is_one_word() {
+        assert_eq!(
+            args(" ms get --file $(ls x) --key y)"),
+            vec!["ms", "get", "--file", "$(ls x)", "--key", "y"]
+        );
+    }
+
+    #[test]
+    fn test_extract_joins_continuations_in_shell_blocks() {
+        let md = Markdown::parse(
+            "Use `bash .claude/scripts/demoswarm.sh` for counts.\n\
+             ```bash\n\
+             COUNT=$(bash .claude/scripts/demoswarm.sh count pattern \\\n  \
+             --file \"$F\" \\\n  \
+             --regex '^x')\n\
+             ```\n",
+        );
+
+        let found = extract(&md, Scope::Code(SHELL_LANGS));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].line, 3);
+        assert_eq!(
+            found[0].args,
+            vec!["count", "pattern", "--file", "$F", "--regex", "^x"]
+        );
+    }
+
+    #[test]
+    fn test_extract_skips_references_and_synopses() {
+        let md = Markdown::parse(
+            "```bash\n\
+             ls .claude/scripts/demoswarm.sh\n\
+             echo \"demoswarm.sh\"\n\
+             bash .claude/scripts/demoswarm.sh count pattern [--null-if-missing]\n\
+             ```\n",
+        );
+        assert!(extract(&md, Scope::Code(SHELL_LANGS)).is_empty());
+    }
+
+    #[test]
+    fn test_validate_accepts_real_invocation() {
+        let a = args(" time now");
+        assert_eq!(validate(&a), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_subcommand() {
+        let err = validate(&args(" tme now")).unwrap_err();
+        assert!(err.contains("unrecognized subcommand 'tme'"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_rejects_misspelled_flag_with_tip() {
+        let err = validate(&args(" count pattern --file f --regex x --null-if-zeor")).unwrap_err();
+        assert!(err.contains("--null-if-zeor"), "{err}");
+        assert!(err.contains("--null-if-zero"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_rejects_missing_subcommand_and_required_flag() {
+        assert!(validate(&args(" count")).is_err());
+        let err = validate(&args(" count pattern --file f")).unwrap_err();
+        assert!(err.contains("--regex"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_ignores_placeholders() {
+        assert_eq!(validate(&args(" time now <extra>")), Ok(()));
+        assert_eq!(
+            validate(&args(r#" count pattern --file "$F" --regex '^x'"#)),
+            Ok(())
+        );
+        assert_eq!(validate(&args(" time now ...")), Ok(()));
+        assert_eq!(validate(&args(" time now --help")), Ok(()));
+    }
+
+    #[test]
+    fn test_display_requotes_args() {
+        let inv = Invocation {
+            line: 1,
+            column: 1,
+            args: vec!["ms".into(), "get".into(), "--section".into(), "## A".into()],
+        };
+        assert_eq!(inv.display(), "demoswarm.sh ms get --section \"## A\"");
+    }
+}
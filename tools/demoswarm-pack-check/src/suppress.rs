@@ -0,0 +1,537 @@
+//! Inline suppression directives in pack markdown.
+//!
+//! Some matches are intentional (an agent quoting a raw git command, an
+//! "anti-pattern" example naming a banned term). Pack authors mark them with
+//! HTML comments, which render invisibly:
+//!
+//! ```markdown
+//! <!-- pack-check-disable-next-line 39 -->
+//! git reset --hard   <- not reported by check 39
+//!
+//! <!-- pack-check-disable 8 -->
+//! ...                <- nothing in here is reported by check 8
+//! <!-- pack-check-enable 8 -->
+//! ```
+//!
+//! Check IDs are separated by spaces or commas; no IDs means every check.
+//! A `disable` without a matching `enable` runs to the end of the file and also
+//! covers diagnostics that point at the file as a whole. An `enable` with IDs
+//! ends only those checks' part of a block.
+//!
+//! Tokens that are not check IDs never widen a directive to every check: they
+//! are reported (with `enable`s that close nothing) by pseudo-check 63.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::checks::is_known_id;
+use crate::ctx::Ctx;
+use crate::reporter::{Diagnostic, Level, Location};
+
+static DIRECTIVE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"<!--\s*pack-check-(disable-next-line|disable|enable)\b([^>]*?)-->")
+        .expect("valid directive regex")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectiveKind {
+    DisableNextLine,
+    /// `disable` ... `enable` (or end of file).
+    Block,
+}
+
+/// One parsed directive and the lines it covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directive {
+    pub kind: DirectiveKind,
+    /// Check IDs covered; empty means all checks.
+    pub ids: Vec<u32>,
+    /// Repo-relative path of the file containing the directive.
+    pub path: String,
+    /// 1-based line of the directive comment.
+    pub line: usize,
+    pub column: usize,
+    /// Last covered line for a closed block; `None` means end of file.
+    pub end_line: Option<usize>,
+    pub used: bool,
+}
+
+impl Directive {
+    fn covers_check(&self, id: u32) -> bool {
+        self.ids.is_empty() || self.ids.contains(&id)
+    }
+
+    fn covers_line(&self, line: Option<usize>) -> bool {
+        match (self.kind, line) {
+            (DirectiveKind::DisableNextLine, Some(l)) => l == self.line + 1,
+            (DirectiveKind::DisableNextLine, None) => false,
+            (DirectiveKind::Block, Some(l)) => {
+                l >= self.line && self.end_line.is_none_or(|end| l <= end)
+            }
+            // File-level diagnostics: only an unclosed block covers the whole file.
+            (DirectiveKind::Block, None) => self.end_line.is_none(),
+        }
+    }
+
+    pub fn location(&self) -> Location {
+        Location::file(self.path.clone())
+            .with_line(self.line)
+            .with_column(self.column)
+    }
+
+    /// Human-readable form, e.g. `pack-check-disable-next-line 39`.
+    pub fn describe(&self) -> String {
+        let kind = match self.kind {
+            DirectiveKind::DisableNextLine => "pack-check-disable-next-line",
+            DirectiveKind::Block => "pack-check-disable",
+        };
+        describe(kind, &self.ids)
+    }
+}
+
+/// A directive that cannot be honored as written: a token that is not a check
+/// ID, or an `enable` that closes nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Malformed {
+    pub location: Location,
+    pub message: String,
+}
+
+/// All directives in the pack, indexed by repo-relative path.
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    by_path: HashMap<String, Vec<Directive>>,
+    malformed: Vec<Malformed>,
+}
+
+impl Suppressions {
+    /// Collect directives from every `.claude/**/*.md` file.
+    pub fn scan(ctx: &Ctx) -> anyhow::Result<Self> {
+        let mut sup = Self::default();
+
+        for path in ctx.files_under(&ctx.claude_dir) {
+            if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+            // Best-effort, like the matchers: skip unreadable/non-UTF8 files.
+            let Ok(content) = ctx.read_utf8(&path) else {
+                continue;
+            };
+            sup.add_file(&ctx.rel(&path), &content);
+        }
+
+        Ok(sup)
+    }
+
+    /// Parse and index the directives in one file.
+    pub fn add_file(&mut self, rel_path: &str, content: &str) {
+        let (directives, malformed) = parse(rel_path, content);
+        if !directives.is_empty() {
+            self.by_path.insert(normalize_path(rel_path), directives);
+        }
+        self.malformed.extend(malformed);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_path.is_empty() && self.malformed.is_empty()
+    }
+
+    /// Directives that cannot be honored as written.
+    pub fn malformed(&self) -> &[Malformed] {
+        &self.malformed
+    }
+
+    /// True if a directive covers `d`; that directive is marked as used.
+    pub fn suppresses(&mut self, d: &Diagnostic) -> bool {
+        if d.level == Level::Pass {
+            return false;
+        }
+        let Some(loc) = &d.location else {
+            return false;
+        };
+        let Some(directives) = self.by_path.get_mut(&normalize_path(&loc.path)) else {
+            return false;
+        };
+
+        match directives
+            .iter_mut()
+            .find(|dir| dir.covers_check(d.check_id) && dir.covers_line(loc.line))
+        {
+            Some(dir) => {
+                dir.used = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// True if a directive covers `check_id` at `line` of `rel_path`. Unlike
+    /// `suppresses`, nothing is marked as used (for `--fix`, which skips covered lines).
+    pub fn covers(&self, check_id: u32, rel_path: &str, line: usize) -> bool {
+        self.by_path
+            .get(&normalize_path(rel_path))
+            .is_some_and(|dirs| {
+                dirs.iter()
+                    .any(|dir| dir.covers_check(check_id) && dir.covers_line(Some(line)))
+            })
+    }
+
+    /// Directives that suppressed nothing. Only directives whose checks all ran
+    /// are considered (`all_ran` gates directives that name no IDs).
+    pub fn unused(&self, ran: impl Fn(u32) -> bool, all_ran: bool) -> Vec<&Directive> {
+        let mut out: Vec<&Directive> = self
+            .by_path
+            .values()
+            .flatten()
+            .filter(|dir| !dir.used)
+            .filter(|dir| {
+                if dir.ids.is_empty() {
+                    all_ran
+                } else {
+                    dir.ids.iter().all(|id| ran(*id))
+                }
+            })
+            .collect();
+        out.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+        out
+    }
+}
+
+/// Parse the directives in one file. `enable` ends its IDs' part of every
+/// open `disable` block (or all open blocks when it names none).
+pub fn parse(rel_path: &str, content: &str) -> (Vec<Directive>, Vec<Malformed>) {
+    let mut out: Vec<Directive> = Vec::new();
+    let mut malformed = Vec::new();
+    let mut open: Vec<usize> = Vec::new(); // indexes into `out`
+
+    for (i, line) in content.lines().enumerate() {
+        let line_no = i + 1;
+        for caps in DIRECTIVE.captures_iter(line) {
+            let whole = caps.get(0).expect("group 0");
+            let column = line[..whole.start()].chars().count() + 1;
+            let location = Location::file(rel_path)
+                .with_line(line_no)
+                .with_column(column);
+            let (ids, unknown) = parse_ids(&caps[2]);
+            let directive = describe(&format!("pack-check-{}", &caps[1]), &ids);
+            for token in &unknown {
+                malformed.push(Malformed {
+                    location: location.clone(),
+                    message: format!(
+                        "Suppression directive names `{token}`, which is not a check ID"
+                    ),
+                });
+            }
+            // Only bad tokens: ignore the directive rather than apply it to every check.
+            if ids.is_empty() && !unknown.is_empty() {
+                continue;
+            }
+
+            match &caps[1] {
+                "enable" => {
+                    let inside_all =
+                        !ids.is_empty() && open.iter().any(|&idx| out[idx].ids.is_empty());
+                    let mut matched = false;
+                    let mut kept = Vec::new();
+                    open.retain(|&idx| {
+                        let block = &mut out[idx];
+                        if !ids.is_empty() && block.ids.is_empty() {
+                            return true;
+                        }
+                        let (closed, rest): (Vec<u32>, Vec<u32>) = block
+                            .ids
+                            .iter()
+                            .partition(|id| ids.is_empty() || ids.contains(id));
+                        if closed.is_empty() && !block.ids.is_empty() {
+                            return true;
+                        }
+                        matched = true;
+                        block.end_line = Some(line_no);
+                        if !rest.is_empty() {
+                            // The rest of the block stays open as its own directive.
+                            block.ids = closed;
+                            kept.push(Directive {
+                                ids: rest,
+                                end_line: None,
+                                ..block.clone()
+                            });
+                        }
+                        false
+                    });
+                    for block in kept {
+                        open.push(out.len());
+                        out.push(block);
+                    }
+                    if !matched {
+                        let message = if inside_all {
+                            format!(
+                                "Suppression directive '{directive}' cannot end part of a pack-check-disable without IDs (use a bare pack-check-enable)"
+                            )
+                        } else {
+                            format!(
+                                "Suppression directive '{directive}' matches no open pack-check-disable"
+                            )
+                        };
+                        malformed.push(Malformed { location, message });
+                    }
+                }
+                kind => {
+                    let kind = if kind == "disable" {
+                        DirectiveKind::Block
+                    } else {
+                        DirectiveKind::DisableNextLine
+                    };
+                    if kind == DirectiveKind::Block {
+                        open.push(out.len());
+                    }
+                    out.push(Directive {
+                        kind,
+                        ids,
+                        path: rel_path.to_string(),
+                        line: line_no,
+                        column,
+                        end_line: None,
+                        used: false,
+                    });
+                }
+            }
+        }
+    }
+
+    (out, malformed)
+}
+
+/// The check IDs in a directive, and the tokens that are not check IDs.
+fn parse_ids(raw: &str) -> (Vec<u32>, Vec<String>) {
+    let mut ids = Vec::new();
+    let mut unknown = Vec::new();
+    for tok in raw
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|tok| !tok.is_empty())
+    {
+        match tok.parse() {
+            Ok(id) if is_known_id(id) => ids.push(id),
+            _ => unknown.push(tok.to_string()),
+        }
+    }
+    ids.sort_unstable();
+    ids.dedup();
+    (ids, unknown)
+}
+
+/// `kind` followed by its IDs, e.g. `pack-check-disable 8 30`.
+fn describe(kind: &str, ids: &[u32]) -> String {
+    if ids.is_empty() {
+        kind.to_string()
+    } else {
+        let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        format!("{kind} {}", ids.join(" "))
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diag(check_id: u32, path: &str, line: Option<usize>) -> Diagnostic {
+        let mut loc = Location::file(path);
+        if let Some(l) = line {
+            loc = loc.with_line(l);
+        }
+        Diagnostic {
+            level: Level::Fail,
+            check_id,
+            check_title: "Title".to_string(),
+            message: "msg".to_string(),
+            location: Some(loc),
+        }
+    }
+
+    fn sup(path: &str, content: &str) -> Suppressions {
+        let mut s = Suppressions::default();
+        s.add_file(path, content);
+        s
+    }
+
+    #[test]
+    fn test_parse_next_line_directive() {
+        let (dirs, malformed) = parse(
+            "a.md",
+            "text\n<!-- pack-check-disable-next-line 39 -->\ngit add .\n",
+        );
+        assert!(malformed.is_empty());
+        assert_eq!(dirs.len(), 1);
+        assert_eq!(dirs[0].kind, DirectiveKind::DisableNextLine);
+        assert_eq!(dirs[0].ids, vec![39]);
+        assert_eq!(dirs[0].line, 2);
+        assert_eq!(dirs[0].column, 1);
+    }
+
+    #[test]
+    fn test_parse_ids_commas_and_spaces() {
+        let (dirs, _) = parse("a.md", "<!-- pack-check-disable 8, 30 7 -->");
+        assert_eq!(dirs[0].ids, vec![7, 8, 30]);
+    }
+
+    #[test]
+    fn test_parse_block_closed_by_enable() {
+        let (dirs, malformed) = parse(
+            "a.md",
+            "<!-- pack-check-disable 8 -->\nFlow Studio\n<!-- pack-check-enable 8 -->\n",
+        );
+        assert!(malformed.is_empty());
+        assert_eq!(dirs.len(), 1);
+        assert_eq!(dirs[0].kind, DirectiveKind::Block);
+        assert_eq!(dirs[0].end_line, Some(3));
+    }
+
+    #[test]
+    fn test_parse_enable_other_ids_leaves_block_open() {
+        let (dirs, malformed) = parse(
+            "a.md",
+            "<!-- pack-check-disable 8 -->\n<!-- pack-check-enable 9 -->\n",
+        );
+        assert_eq!(dirs[0].end_line, None);
+        // An enable that closes nothing is reported.
+        assert_eq!(malformed.len(), 1);
+        assert_eq!(malformed[0].location.line, Some(2));
+        assert!(
+            malformed[0]
+                .message
+                .contains("'pack-check-enable 9' matches no open pack-check-disable"),
+            "{}",
+            malformed[0].message
+        );
+    }
+
+    #[test]
+    fn test_parse_enable_ends_only_its_ids() {
+        let mut s = sup(
+            "a.md",
+            "<!-- pack-check-disable 8 30 -->\nx\n<!-- pack-check-enable 8 -->\ny\n",
+        );
+        assert!(s.malformed().is_empty());
+        assert!(s.suppresses(&diag(8, "a.md", Some(2))));
+        assert!(!s.suppresses(&diag(8, "a.md", Some(4))));
+        assert!(s.suppresses(&diag(30, "a.md", Some(4))));
+        // Check 30's part is still open, so it covers the whole file.
+        assert!(s.suppresses(&diag(30, "a.md", None)));
+    }
+
+    #[test]
+    fn test_parse_enable_inside_disable_all_is_reported() {
+        let (dirs, malformed) = parse(
+            "a.md",
+            "<!-- pack-check-disable -->\n<!-- pack-check-enable 8 -->\n",
+        );
+        assert_eq!(dirs[0].end_line, None);
+        assert_eq!(malformed.len(), 1);
+        assert!(
+            malformed[0].message.contains("bare pack-check-enable"),
+            "{}",
+            malformed[0].message
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_ids_are_reported_not_widened() {
+        let (dirs, malformed) = parse(
+            "a.md",
+            "<!-- pack-check-disable-next-line foo -->\nx\n<!-- pack-check-disable-next-line 39, 9999 -->\ny\n",
+        );
+        // `foo` alone does not turn into "every check".
+        assert_eq!(dirs.len(), 1);
+        assert_eq!(dirs[0].ids, vec![39]);
+        let messages: Vec<&str> = malformed.iter().map(|m| m.message.as_str()).collect();
+        assert_eq!(malformed.len(), 2, "{messages:?}");
+        assert!(messages[0].contains("`foo`"));
+        assert_eq!(malformed[0].location.line, Some(1));
+        assert!(messages[1].contains("`9999`"));
+
+        let mut s = sup("a.md", "<!-- pack-check-disable-next-line foo -->\nx\n");
+        assert!(!s.suppresses(&diag(7, "a.md", Some(2))));
+        assert!(!s.is_empty());
+    }
+
+    #[test]
+    fn test_parse_ignores_unrelated_comments() {
+        let (dirs, malformed) = parse("a.md", "<!-- just a note -->\n<!-- pack-checker 1 -->");
+        assert!(dirs.is_empty() && malformed.is_empty());
+    }
+
+    #[test]
+    fn test_next_line_suppresses_only_next_line_and_check() {
+        let mut s = sup(
+            "a.md",
+            "<!-- pack-check-disable-next-line 39 -->\ngit add .\ngit add .\n",
+        );
+        assert!(!s.suppresses(&diag(39, "a.md", Some(3))));
+        assert!(!s.suppresses(&diag(7, "a.md", Some(2))));
+        assert!(!s.suppresses(&diag(39, "b.md", Some(2))));
+        assert!(s.suppresses(&diag(39, "a.md", Some(2))));
+    }
+
+    #[test]
+    fn test_block_suppresses_range() {
+        let mut s = sup(
+            "a.md",
+            "<!-- pack-check-disable 8 -->\nx\ny\n<!-- pack-check-enable 8 -->\nz\n",
+        );
+        assert!(s.suppresses(&diag(8, "a.md", Some(3))));
+        assert!(!s.suppresses(&diag(8, "a.md", Some(5))));
+        // A closed block does not cover the file as a whole.
+        assert!(!s.suppresses(&diag(8, "a.md", None)));
+    }
+
+    #[test]
+    fn test_unclosed_block_covers_file_level_diagnostics() {
+        let mut s = sup("a.md", "<!-- pack-check-disable 54 -->\n");
+        assert!(s.suppresses(&diag(54, "a.md", None)));
+        assert!(s.suppresses(&diag(54, "a.md", Some(100))));
+    }
+
+    #[test]
+    fn test_no_ids_means_all_checks() {
+        let mut s = sup("a.md", "<!-- pack-check-disable-next-line -->\nanything\n");
+        assert!(s.suppresses(&diag(7, "a.md", Some(2))));
+    }
+
+    #[test]
+    fn test_pass_and_unlocated_are_never_suppressed() {
+        let mut s = sup("a.md", "<!-- pack-check-disable -->\n");
+        let mut pass = diag(1, "a.md", Some(1));
+        pass.level = Level::Pass;
+        assert!(!s.suppresses(&pass));
+
+        let mut unlocated = diag(1, "a.md", None);
+        unlocated.location = None;
+        assert!(!s.suppresses(&unlocated));
+    }
+
+    #[test]
+    fn test_unused_reports_only_directives_for_checks_that_ran() {
+        let mut s = sup(
+            "a.md",
+            "<!-- pack-check-disable-next-line 39 -->\nx\n<!-- pack-check-disable-next-line 8 -->\ny\n<!-- pack-check-disable-next-line -->\nz\n",
+        );
+        assert!(s.suppresses(&diag(39, "a.md", Some(2))));
+
+        let unused = s.unused(|id| id == 39 || id == 8, false);
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].ids, vec![8]);
+        assert_eq!(unused[0].describe(), "pack-check-disable-next-line 8");
+
+        let unused = s.unused(|_| true, true);
+        assert_eq!(unused.len(), 2);
+    }
+}
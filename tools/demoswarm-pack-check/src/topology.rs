@@ -0,0 +1,186 @@
+//! Flow topology: the pack's flows, discovered from its `flow-N-<name>.md`
+//! commands and joined with the cleanup/receipt contracts.
+//!
+//! Checks ask this model which flows exist instead of hard-coding a list, so a
+//! pack that adds, drops or renumbers a flow is validated as it is.
+
+use std::path::PathBuf;
+
+use crate::contracts::Contracts;
+use crate::inventory::Inventory;
+
+/// A flow's cleanup agent and the receipt it seals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cleanup {
+    pub agent: String,
+    pub receipt: String,
+}
+
+/// One flow command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flow {
+    /// `N` in `flow-N-<name>.md`.
+    pub number: u32,
+    /// `<name>` in `flow-N-<name>.md`; also the `.runs/<run-id>/<name>/` directory.
+    pub name: String,
+    pub command: PathBuf,
+    /// The `cleanup_agents` contract entry for this flow (`<name>-cleanup`).
+    pub cleanup: Option<Cleanup>,
+}
+
+impl Flow {
+    /// The command stem (`flow-3-build`).
+    pub fn stem(&self) -> String {
+        format!("flow-{}-{}", self.number, self.name)
+    }
+
+    /// The receipt name the cleanup contract should use.
+    pub fn expected_receipt(&self) -> String {
+        format!("{}_receipt.json", self.name)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FlowTopology {
+    /// Flows ordered by number, then name.
+    pub flows: Vec<Flow>,
+    /// `flow-*.md` commands whose name is not `flow-N-<name>.md`.
+    pub malformed: Vec<PathBuf>,
+    /// Cleanup contract entries whose flow has no command.
+    pub orphan_cleanups: Vec<(String, Cleanup)>,
+}
+
+impl FlowTopology {
+    pub fn discover(inv: &Inventory, c: &Contracts) -> Self {
+        let mut topo = Self::default();
+
+        for path in &inv.flow_cmd_files {
+            let parsed = path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .and_then(parse_flow_file_name);
+            match parsed {
+                Some((number, name)) => topo.flows.push(Flow {
+                    number,
+                    name,
+                    command: path.clone(),
+                    cleanup: None,
+                }),
+                None => topo.malformed.push(path.clone()),
+            }
+        }
+        topo.flows
+            .sort_by(|a, b| (a.number, &a.name).cmp(&(b.number, &b.name)));
+
+        for (agent, receipt) in &c.cleanup_agents {
+            let cleanup = Cleanup {
+                agent: agent.clone(),
+                receipt: receipt.clone(),
+            };
+            let flow_name = cleanup_flow_name(agent, receipt);
+            match topo.flows.iter_mut().find(|f| f.name == flow_name) {
+                Some(flow) => flow.cleanup = Some(cleanup),
+                None => topo.orphan_cleanups.push((flow_name.to_string(), cleanup)),
+            }
+        }
+
+        topo
+    }
+
+    pub fn flow(&self, name: &str) -> Option<&Flow> {
+        self.flows.iter().find(|f| f.name == name)
+    }
+
+    /// Flow names in order (`signal`, `plan`, ...).
+    pub fn names(&self) -> Vec<String> {
+        self.flows.iter().map(|f| f.name.clone()).collect()
+    }
+}
+
+/// `flow-3-build.md` → `(3, "build")`.
+pub fn parse_flow_file_name(file_name: &str) -> Option<(u32, String)> {
+    let rest = file_name.strip_prefix("flow-")?.strip_suffix(".md")?;
+    let (number, name) = rest.split_once('-')?;
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid_name || number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((number.parse().ok()?, name.to_string()))
+}
+
+/// The flow a cleanup contract entry belongs to: `<flow>-cleanup`, else
+/// `<flow>_receipt.json`.
+fn cleanup_flow_name<'a>(agent: &'a str, receipt: &'a str) -> &'a str {
+    agent
+        .strip_suffix("-cleanup")
+        .or_else(|| receipt.strip_suffix("_receipt.json"))
+        .unwrap_or(agent)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ctx::Ctx;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_flow_file_name() {
+        assert_eq!(
+            parse_flow_file_name("flow-3-build.md"),
+            Some((3, "build".to_string()))
+        );
+        assert_eq!(
+            parse_flow_file_name("flow-10-post-deploy.md"),
+            Some((10, "post-deploy".to_string()))
+        );
+        assert_eq!(parse_flow_file_name("flow-1.md"), None);
+        assert_eq!(parse_flow_file_name("flow-x-build.md"), None);
+        assert_eq!(parse_flow_file_name("flow-1-Build.md"), None);
+        assert_eq!(parse_flow_file_name("flow-1-signal"), None);
+    }
+
+    #[test]
+    fn test_discover_joins_cleanup_contracts() {
+        let temp = TempDir::new().unwrap();
+        let commands = temp.path().join(".claude/commands");
+        std::fs::create_dir_all(&commands).unwrap();
+        std::fs::create_dir_all(temp.path().join(".claude/agents")).unwrap();
+        for name in [
+            "flow-2-plan.md",
+            "flow-1-signal.md",
+            "flow-4-review.md",
+            "flow-x.md",
+        ] {
+            std::fs::write(commands.join(name), "# Flow").unwrap();
+        }
+
+        let ctx = Ctx::discover(Some(temp.path().to_path_buf())).unwrap();
+        let inv = Inventory::from_ctx(&ctx).unwrap();
+        let topo = FlowTopology::discover(&inv, &Contracts::default());
+
+        assert_eq!(topo.names(), vec!["signal", "plan", "review"]);
+        assert_eq!(topo.flows[2].stem(), "flow-4-review");
+        assert_eq!(
+            topo.flow("review").unwrap().cleanup,
+            Some(Cleanup {
+                agent: "review-cleanup".to_string(),
+                receipt: "review_receipt.json".to_string(),
+            })
+        );
+        assert_eq!(topo.malformed.len(), 1);
+
+        let orphans: Vec<&str> = topo
+            .orphan_cleanups
+            .iter()
+            .map(|(flow, _)| flow.as_str())
+            .collect();
+        assert_eq!(orphans, vec!["build", "gate", "deploy", "wisdom"]);
+    }
+}
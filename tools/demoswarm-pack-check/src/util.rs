@@ -1,17 +1,43 @@
 use std::path::{Path, PathBuf};
 
 use regex::Regex;
-use walkdir::WalkDir;
 
 use crate::ctx::Ctx;
+use crate::markdown::Scope;
+use crate::reporter::{Finding, Location};
 
 #[derive(Debug, Clone)]
 pub struct LineMatch {
     pub path: PathBuf,
     pub line_no: usize, // 1-based
+    pub column: usize,  // 1-based, in chars
     pub line: String,
 }
 
+impl LineMatch {
+    /// Repo-relative location of the match.
+    pub fn location(&self, ctx: &Ctx) -> Location {
+        Location::file(ctx.rel(&self.path))
+            .with_line(self.line_no)
+            .with_column(self.column)
+    }
+
+    /// The match as a finding (location + trimmed line text).
+    pub fn finding(&self, ctx: &Ctx) -> Finding {
+        Finding::new(self.location(ctx), self.line.trim_end())
+    }
+}
+
+/// 1-based (line, column) of a byte offset in `content`. Column counts chars.
+pub fn line_col_at(content: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(content.len());
+    let before = &content[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
 pub fn contains_ignore_ascii_case(haystack: &str, needle: &str) -> bool {
     haystack
         .to_ascii_lowercase()
@@ -30,40 +56,39 @@ pub fn find_matches_regex_recursive(
     roots: &[PathBuf],
     re: &Regex,
     ignore_file_names: &[&str],
+) -> anyhow::Result<Vec<LineMatch>> {
+    find_matches_regex_scoped(ctx, roots, re, ignore_file_names, Scope::All)
+}
+
+/// Like `find_matches_regex_recursive`, limited to the `scope` lines of markdown
+/// files. Other files have no structure and are scanned whole.
+pub fn find_matches_regex_scoped(
+    ctx: &Ctx,
+    roots: &[PathBuf],
+    re: &Regex,
+    ignore_file_names: &[&str],
+    scope: Scope,
 ) -> anyhow::Result<Vec<LineMatch>> {
     let mut out = Vec::new();
 
     for root in roots {
-        if !root.exists() {
-            continue;
-        }
-
-        for entry in WalkDir::new(root).follow_links(false) {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
-
-            let path = entry.path();
-            if !path.is_file() {
-                continue;
-            }
-            if should_ignore(path, ignore_file_names) {
+        for path in ctx.files_under(root) {
+            if should_ignore(&path, ignore_file_names) {
                 continue;
             }
 
             // Best-effort: skip unreadable/non-UTF8 files.
-            let content = match std::fs::read_to_string(path) {
-                Ok(s) => s,
-                Err(_) => continue,
+            let Some(lines) = scoped_lines(ctx, &path, scope) else {
+                continue;
             };
 
-            for (i, line) in content.lines().enumerate() {
-                if re.is_match(line) {
+            for (line_no, line) in lines {
+                if let Some(m) = re.find(&line) {
                     out.push(LineMatch {
-                        path: path.to_path_buf(),
-                        line_no: i + 1,
-                        line: line.to_string(),
+                        path: path.clone(),
+                        line_no,
+                        column: line[..m.start()].chars().count() + 1,
+                        line,
                     });
                 }
             }
@@ -88,30 +113,18 @@ pub fn find_files_containing_recursive(
 ) -> anyhow::Result<Vec<PathBuf>> {
     let mut hits = Vec::new();
 
-    if !root.exists() {
-        return Ok(hits);
-    }
-
-    for entry in WalkDir::new(root).follow_links(false) {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-        let path = entry.path();
-        if !path.is_file() {
-            continue;
-        }
-        if should_ignore(path, ignore_file_names) {
+    for path in ctx.files_under(root) {
+        if should_ignore(&path, ignore_file_names) {
             continue;
         }
 
-        let content = match std::fs::read_to_string(path) {
+        let content = match ctx.read_utf8(&path) {
             Ok(s) => s,
             Err(_) => continue,
         };
 
         if content.contains(needle) {
-            hits.push(path.to_path_buf());
+            hits.push(path);
         }
     }
 
@@ -119,6 +132,80 @@ pub fn find_files_containing_recursive(
     Ok(hits)
 }
 
+/// Like `find_files_containing_recursive`, but reports every matching line.
+pub fn find_lines_containing_recursive(
+    ctx: &Ctx,
+    root: &Path,
+    needle: &str,
+    ignore_file_names: &[&str],
+) -> anyhow::Result<Vec<LineMatch>> {
+    find_lines_containing_scoped(ctx, root, needle, ignore_file_names, Scope::All)
+}
+
+/// Like `find_lines_containing_recursive`, limited to the `scope` lines of markdown files.
+pub fn find_lines_containing_scoped(
+    ctx: &Ctx,
+    root: &Path,
+    needle: &str,
+    ignore_file_names: &[&str],
+    scope: Scope,
+) -> anyhow::Result<Vec<LineMatch>> {
+    let mut out = Vec::new();
+
+    for path in find_files_containing_recursive(ctx, root, needle, ignore_file_names)? {
+        let Some(lines) = scoped_lines(ctx, &path, scope) else {
+            continue;
+        };
+        for (line_no, line) in lines {
+            if let Some(col) = line.find(needle) {
+                out.push(LineMatch {
+                    path: path.clone(),
+                    line_no,
+                    column: line[..col].chars().count() + 1,
+                    line,
+                });
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// `(1-based line, text)` for the `scope` lines of a file; `None` if unreadable.
+/// Non-markdown files are returned whole.
+fn scoped_lines(ctx: &Ctx, path: &Path, scope: Scope) -> Option<Vec<(usize, String)>> {
+    let is_md = path.extension().is_some_and(|e| e == "md");
+    if scope == Scope::All || !is_md {
+        let content = ctx.read_utf8(path).ok()?;
+        return Some(
+            content
+                .lines()
+                .enumerate()
+                .map(|(i, l)| (i + 1, l.to_string()))
+                .collect(),
+        );
+    }
+
+    let md = ctx.markdown(path).ok()?;
+    Some(md.lines(scope).map(|(n, l)| (n, l.to_string())).collect())
+}
+
+/// Every file under `roots` (recursively), sorted by repo-relative path.
+pub fn list_files_recursive(
+    ctx: &Ctx,
+    roots: &[PathBuf],
+    ignore_file_names: &[&str],
+) -> Vec<PathBuf> {
+    let mut out: Vec<PathBuf> = roots
+        .iter()
+        .flat_map(|root| ctx.files_under(root))
+        .filter(|p| !should_ignore(p, ignore_file_names))
+        .collect();
+
+    out.sort_by_key(|p| ctx.rel(p));
+    out
+}
+
 pub fn extract_frontmatter_name(content: &str) -> Option<String> {
     // Mimics:
     //   sed -n '/^---$/,/^---$/p' file | grep '^name:' | head -1
@@ -364,10 +451,12 @@ mod tests {
         let lm = LineMatch {
             path: PathBuf::from("/test/path.md"),
             line_no: 42,
+            column: 1,
             line: "test content".to_string(),
         };
         assert_eq!(lm.path, PathBuf::from("/test/path.md"));
         assert_eq!(lm.line_no, 42);
+        assert_eq!(lm.column, 1);
         assert_eq!(lm.line, "test content");
     }
 
@@ -376,6 +465,7 @@ mod tests {
         let lm = LineMatch {
             path: PathBuf::from("/test.md"),
             line_no: 1,
+            column: 1,
             line: "line".to_string(),
         };
         let cloned = lm.clone();
@@ -600,7 +690,9 @@ mod tests {
 
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].line_no, 2);
+        assert_eq!(result[0].column, 9);
         assert_eq!(result[1].line_no, 4);
+        assert_eq!(result[1].column, 6);
     }
 
     /// Test find_matches_regex_recursive respects ignore list.
@@ -685,4 +777,50 @@ mod tests {
             path1
         );
     }
+
+    // -------------------------------------------------------------------------
+    // line_col_at / LineMatch::finding tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_line_col_at_first_line() {
+        assert_eq!(line_col_at("abc\ndef", 0), (1, 1));
+        assert_eq!(line_col_at("abc\ndef", 2), (1, 3));
+    }
+
+    #[test]
+    fn test_line_col_at_later_line() {
+        assert_eq!(line_col_at("abc\ndef\nghi", 5), (2, 2));
+        assert_eq!(line_col_at("abc\ndef\nghi", 8), (3, 1));
+    }
+
+    #[test]
+    fn test_line_col_at_counts_chars_not_bytes() {
+        let content = "a → b";
+        let offset = content.find('b').unwrap();
+        assert_eq!(line_col_at(content, offset), (1, 5));
+    }
+
+    #[test]
+    fn test_line_match_finding_is_repo_relative() {
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join(".claude")).unwrap();
+        let ctx = Ctx::discover(Some(tmp.path().to_path_buf())).unwrap();
+
+        let lm = LineMatch {
+            path: ctx.repo_root.join(".claude").join("x.md"),
+            line_no: 3,
+            column: 7,
+            line: "hit here   ".to_string(),
+        };
+        let finding = lm.finding(&ctx);
+
+        assert_eq!(finding.location.line, Some(3));
+        assert_eq!(finding.location.column, Some(7));
+        assert!(finding.location.path.ends_with("x.md"));
+        assert!(!finding.location.path.starts_with('/'));
+        assert_eq!(finding.text, "hit here");
+    }
 }
@@ -0,0 +1,319 @@
+//! `--watch`: keep the pack loaded, poll `.claude/` and `CLAUDE.md`, and re-run
+//! only the checks an edit can affect (see `Session`).
+//!
+//! Output is a diff of the diagnostics: `+` appeared, `-` cleared.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime};
+
+use crate::{
+    checker::{Pack, PackChecker},
+    reporter::{Diagnostic, Level},
+    session::Session,
+};
+
+/// How often the pack is polled for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Watch the pack until interrupted, printing diagnostic diffs.
+pub fn watch(checker: &PackChecker, baseline: Option<&Path>) -> anyhow::Result<ExitCode> {
+    let (mut watcher, initial) = Watcher::start(checker, baseline)?;
+    println!(
+        "Watching {} (.claude/ and CLAUDE.md); Ctrl-C to stop.",
+        watcher.session.pack().ctx.repo_root.display()
+    );
+    print_update(&initial);
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        match watcher.poll() {
+            Ok(Some(update)) => print_update(&update),
+            Ok(None) => {}
+            // A half-written contracts file, say: keep watching for the fix.
+            Err(err) => eprintln!("Error: {err:#}"),
+        }
+    }
+}
+
+/// The result of one re-run.
+#[derive(Debug, Default)]
+struct Update {
+    /// Repo-relative paths that changed (empty for the initial run).
+    changed: Vec<String>,
+    rerun: usize,
+    total: usize,
+    appeared: Vec<String>,
+    cleared: Vec<String>,
+    errors: usize,
+    warnings: usize,
+}
+
+struct Watcher<'a> {
+    session: Session<'a>,
+    snapshot: Snapshot,
+}
+
+impl<'a> Watcher<'a> {
+    /// Load the pack and run every selected check once.
+    fn start(
+        checker: &'a PackChecker,
+        baseline: Option<&'a Path>,
+    ) -> anyhow::Result<(Self, Update)> {
+        let session = Session::start(checker, baseline)?;
+        let snapshot = Snapshot::take(session.pack());
+        let watcher = Self { session, snapshot };
+        let update = watcher.update(Vec::new(), watcher.session.total(), &[]);
+        Ok((watcher, update))
+    }
+
+    /// Re-run the checks affected by changes since the last poll, if any.
+    fn poll(&mut self) -> anyhow::Result<Option<Update>> {
+        let now = Snapshot::take(self.session.pack());
+        let changes = self.snapshot.changes(&now);
+        if changes.paths.is_empty() {
+            return Ok(None);
+        }
+        self.snapshot = now;
+
+        let before = self.lines();
+        let rerun = self.session.changed(&changes.paths, changes.tree_changed)?;
+        let ctx = &self.session.pack().ctx;
+        let changed = changes.paths.iter().map(|p| ctx.rel(p)).collect();
+        Ok(Some(self.update(changed, rerun, &before)))
+    }
+
+    /// Diff the current diagnostics against the lines `before` a re-run.
+    fn update(&self, changed: Vec<String>, rerun: usize, before: &[String]) -> Update {
+        let after = self.lines();
+        let (errors, warnings) =
+            self.session
+                .diagnostics()
+                .fold((0, 0), |(e, w), d| match d.level {
+                    Level::Fail => (e + 1, w),
+                    Level::Warn => (e, w + 1),
+                    Level::Pass => (e, w),
+                });
+
+        Update {
+            changed,
+            rerun,
+            total: self.session.total(),
+            appeared: missing_from(&after, before),
+            cleared: missing_from(before, &after),
+            errors,
+            warnings,
+        }
+    }
+
+    /// Every current diagnostic as a display line, in check order.
+    fn lines(&self) -> Vec<String> {
+        self.session.diagnostics().map(describe).collect()
+    }
+}
+
+/// The lines of `lines` that are not in `other`, in order.
+fn missing_from(lines: &[String], other: &[String]) -> Vec<String> {
+    let other: HashSet<&String> = other.iter().collect();
+    lines
+        .iter()
+        .filter(|l| !other.contains(l))
+        .cloned()
+        .collect()
+}
+
+/// `error [7] .claude/agents/a.md:3: message`
+fn describe(d: &Diagnostic) -> String {
+    let level = match d.level {
+        Level::Fail => "error",
+        Level::Warn => "warning",
+        Level::Pass => "pass",
+    };
+    let location = match &d.location {
+        Some(loc) => match loc.line {
+            Some(line) => format!("{}:{line}: ", loc.path),
+            None => format!("{}: ", loc.path),
+        },
+        None => String::new(),
+    };
+    format!("{level} [{}] {location}{}", d.check_id, d.message)
+}
+
+fn print_update(update: &Update) {
+    if !update.changed.is_empty() {
+        println!();
+        println!(
+            "Changed {}: re-ran {} of {} checks",
+            update.changed.join(", "),
+            update.rerun,
+            update.total
+        );
+    }
+    for line in &update.appeared {
+        println!("+ {line}");
+    }
+    for line in &update.cleared {
+        println!("- {line}");
+    }
+    if update.appeared.is_empty() && update.cleared.is_empty() && !update.changed.is_empty() {
+        println!("  (no change in diagnostics)");
+    }
+    println!("{} error(s), {} warning(s)", update.errors, update.warnings);
+}
+
+/// Modification time and size of every watched file.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Snapshot(BTreeMap<PathBuf, (Option<SystemTime>, u64)>);
+
+/// What differs between two snapshots.
+struct Changes {
+    paths: Vec<PathBuf>,
+    /// Files were added or removed.
+    tree_changed: bool,
+}
+
+impl Snapshot {
+    fn take(pack: &Pack) -> Self {
+        let ctx = &pack.ctx;
+        let mut paths = ctx.files().files_under(&ctx.claude_dir);
+        paths.push(ctx.repo_root.join("CLAUDE.md"));
+
+        Self(
+            paths
+                .into_iter()
+                .filter_map(|p| {
+                    let meta = std::fs::metadata(&p).ok()?;
+                    meta.is_file()
+                        .then(|| (p, (meta.modified().ok(), meta.len())))
+                })
+                .collect(),
+        )
+    }
+
+    fn changes(&self, now: &Snapshot) -> Changes {
+        let mut paths = Vec::new();
+        let mut tree_changed = false;
+        for (path, stamp) in &now.0 {
+            match self.0.get(path) {
+                Some(old) if old == stamp => {}
+                Some(_) => paths.push(path.clone()),
+                None => {
+                    paths.push(path.clone());
+                    tree_changed = true;
+                }
+            }
+        }
+        for path in self.0.keys() {
+            if !now.0.contains_key(path) {
+                paths.push(path.clone());
+                tree_changed = true;
+            }
+        }
+        paths.sort();
+        Changes {
+            paths,
+            tree_changed,
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn pack() -> TempDir {
+        let tmp = TempDir::new().unwrap();
+        let agents = tmp.path().join(".claude/agents");
+        std::fs::create_dir_all(&agents).unwrap();
+        std::fs::write(tmp.path().join("CLAUDE.md"), "# CLAUDE.md\n").unwrap();
+        std::fs::write(agents.join("a.md"), "Traces FR-001\n").unwrap();
+        std::fs::write(agents.join("b.md"), "Traces REQ-001\n").unwrap();
+        tmp
+    }
+
+    fn check_7(update: &Update) -> Vec<&String> {
+        update
+            .appeared
+            .iter()
+            .chain(&update.cleared)
+            .filter(|l| l.contains("[7]"))
+            .collect()
+    }
+
+    #[test]
+    fn test_edit_reruns_only_checks_that_read_the_file() {
+        let tmp = pack();
+        let checker = PackChecker::new(tmp.path()).jobs(2);
+        let (mut watcher, initial) = Watcher::start(&checker, None).unwrap();
+        assert_eq!(initial.rerun, initial.total);
+        let flagged = check_7(&initial);
+        assert!(
+            flagged.len() == 1 && flagged[0].contains(".claude/agents/a.md:1"),
+            "{initial:?}"
+        );
+        assert!(watcher.poll().unwrap().is_none());
+
+        let a = tmp.path().join(".claude/agents/a.md");
+        std::fs::write(&a, "Traces REQ-0001\n").unwrap();
+        let update = watcher.poll().unwrap().unwrap();
+
+        assert_eq!(update.changed, [".claude/agents/a.md"]);
+        assert!(
+            0 < update.rerun && update.rerun < update.total,
+            "{update:?}"
+        );
+        assert!(update.appeared.iter().all(|l| !l.contains("[7]")));
+        assert_eq!(check_7(&update).len(), 1, "{update:?}");
+        assert!(update.cleared[0].contains("a.md"));
+    }
+
+    #[test]
+    fn test_added_file_rebuilds_inventory_and_reruns_all() {
+        let tmp = pack();
+        let checker = PackChecker::new(tmp.path());
+        let (mut watcher, _) = Watcher::start(&checker, None).unwrap();
+
+        std::fs::write(tmp.path().join(".claude/agents/c.md"), "@FR-002\n").unwrap();
+        let update = watcher.poll().unwrap().unwrap();
+
+        assert_eq!(update.changed, [".claude/agents/c.md"]);
+        assert_eq!(update.rerun, update.total);
+        assert_eq!(watcher.session.pack().inv.agent_md_files.len(), 3);
+        let flagged = check_7(&update);
+        assert!(
+            !flagged.is_empty() && flagged.iter().all(|l| l.contains("c.md")),
+            "{update:?}"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_changes() {
+        let stamp = |n| (None, n);
+        let old = Snapshot(BTreeMap::from([
+            (PathBuf::from("a"), stamp(1)),
+            (PathBuf::from("b"), stamp(1)),
+        ]));
+
+        let edited = Snapshot(BTreeMap::from([
+            (PathBuf::from("a"), stamp(2)),
+            (PathBuf::from("b"), stamp(1)),
+        ]));
+        let changes = old.changes(&edited);
+        assert_eq!(changes.paths, [PathBuf::from("a")]);
+        assert!(!changes.tree_changed);
+
+        let renamed = Snapshot(BTreeMap::from([
+            (PathBuf::from("a"), stamp(1)),
+            (PathBuf::from("c"), stamp(1)),
+        ]));
+        let changes = old.changes(&renamed);
+        assert_eq!(changes.paths, [PathBuf::from("b"), PathBuf::from("c")]);
+        assert!(changes.tree_changed);
+    }
+}
@@ -0,0 +1,9 @@
+//! demoswarm library: the CLI grammar and command implementations.
+//!
+//! The `demoswarm` binary is a thin dispatcher over this crate. The clap
+//! command tree (`commands::Cli`) is public so tools that validate shim
+//! invocations (pack-check) parse against the real grammar instead of a copy.
+
+pub mod commands;
+pub mod output;
+pub mod walk;
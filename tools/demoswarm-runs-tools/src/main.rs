@@ -9,12 +9,8 @@ use std::process::ExitCode;
 
 use clap::Parser;
 
-mod commands;
-mod output;
-mod walk;
-
-use commands::{Cli, Command};
-use output::print_null;
+use demoswarm_runs_tools::commands::{self, Cli, Command};
+use demoswarm_runs_tools::output::print_null;
 
 /// Multicall dispatch table: maps argv[0] suffix to subcommand.
 const MULTICALL_MAP: &[(&str, &str)] = &[